@@ -264,6 +264,7 @@ export interface ChromiumCookie {
   name: string
   value: string
   decryptedValue?: string
+  decryptError?: string
   path: string
   expiresUtc?: Date
   isSecure: boolean
@@ -273,9 +274,58 @@ export interface ChromiumCookie {
   isPersistent: boolean
   priority: number
   sameSite: SameSite
+  sameSiteRaw: number
   sourceScheme: number
   sourcePort: number
   lastUpdateUtc?: Date
+  sourceType?: number
+  hasCrossSiteAncestor?: boolean
+  partitionKey?: PartitionKey
+}
+
+export interface EdgeLoginExtended {
+  source: string
+  strengthAlertStatus: number
+  passwordNickname: string
+  passwordCategory: number
+}
+
+export interface FormData {
+  name: string
+  url: string
+  action: string
+  fields: Array<FormFieldData>
+  isFormTag: boolean
+}
+
+export interface FormFieldData {
+  label: string
+  name: string
+  value: string
+  formControlType: string
+  autocompleteAttribute: string
+  maxLength: number
+  isAutofilled: boolean
+  checkStatus: number
+  isFocusable: boolean
+  shouldAutocomplete: boolean
+  role: number
+  textDirection: number
+  optionValues: Array<string>
+  optionContents: Array<string>
+  placeholder: string
+  cssClasses: string
+  propertiesMask: number
+  idAttribute: string
+  nameAttribute: string
+}
+
+export interface InsecureCredential {
+  insecurityType: InsecurityType
+  insecurityTypeRaw: number
+  createTime?: Date
+  isMuted: boolean
+  triggerNotificationFromBackend: boolean
 }
 
 export interface LoginData {
@@ -288,7 +338,7 @@ export interface LoginData {
   submitElement: string
   signonRealm: string
   dateCreated?: Date
-  blacklistedByUser: number
+  blacklistedByUser: boolean
   scheme: number
   passwordType: number
   timesUsed: number
@@ -301,7 +351,20 @@ export interface LoginData {
   possibleUsernamePairs?: Array<number>
   id: number
   dateLastUsed?: Date
+  movingBlockedFor?: Array<number>
   datePasswordModified?: Date
+  senderEmail: string
+  senderName: string
+  dateReceived?: Date
+  sharingNotificationDisplayed: boolean
+  keychainIdentifier: Array<number>
+  senderProfileImageUrl?: string
+  store: LoginStore
+  notes: Array<PasswordNote>
+  insecureCredentials: Array<InsecureCredential>
+  edge?: EdgeLoginExtended
+  form?: FormData
+  possibleUsernames: Array<PossibleUsername>
 }
 
 export interface MozCookie {
@@ -316,9 +379,30 @@ export interface MozCookie {
   creationTime?: Date
   isSecure: boolean
   isHttpOnly: boolean
-  inBrowserElement: number
+  inBrowserElement: boolean
   sameSite: SameSite
+  sameSiteRaw: number
+  rawSameSite?: number
   schemeMap: number
+  isPartitionedAttributeSet?: boolean
+  partitionKey?: PartitionKey
+}
+
+export interface PartitionKey {
+  topLevelSite: string
+  crossSiteAncestor: boolean
+}
+
+export interface PasswordNote {
+  key: string
+  value?: string
+  dateCreated?: Date
+  confidential: boolean
+}
+
+export interface PossibleUsername {
+  value: string
+  fieldName: string
 }
 
 export interface SafariCookie {
@@ -337,7 +421,21 @@ export interface SafariCookie {
   isHttpOnly: boolean
 }
 
+export declare const enum InsecurityType {
+  Leaked = 0,
+  Phished = 1,
+  Weak = 2,
+  Reused = 3,
+  Other = -1
+}
+
+export declare const enum LoginStore {
+  Local = 0,
+  Account = 1
+}
+
 export declare const enum SameSite {
+  Unspecified = -1,
   None = 0,
   Lax = 1,
   Strict = 2
@@ -435,4 +435,6 @@ module.exports.YandexGetter = nativeBinding.YandexGetter
 module.exports.YandexLoginGetter = nativeBinding.YandexLoginGetter
 module.exports.ZenCookieGetter = nativeBinding.ZenCookieGetter
 module.exports.ZenGetter = nativeBinding.ZenGetter
+module.exports.InsecurityType = nativeBinding.InsecurityType
+module.exports.LoginStore = nativeBinding.LoginStore
 module.exports.SameSite = nativeBinding.SameSite
@@ -1,6 +1,6 @@
 #![expect(clippy::trailing_empty_array, reason = "bindgen code")]
 
-use std::{fmt::Display, mem, path::PathBuf, str::FromStr};
+use std::{fmt::Display, path::PathBuf, str::FromStr};
 
 use chrono::{DateTime, Utc};
 use decrypt_cookies_rs::{
@@ -12,7 +12,7 @@ use decrypt_cookies_rs::{
 };
 use napi_derive::napi;
 
-use crate::SameSite;
+use crate::{PartitionKey, SameSite};
 
 macro_rules! firefoxs {
     ($($browser:ident),* $(,)?) => {
@@ -70,8 +70,7 @@ macro_rules! firefoxs {
                             .await
                             .map_err(|e| napi::Error::new(napi::Status::ObjectExpected, e.to_string()))?;
 
-                        #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                        let all = unsafe { mem::transmute::<Vec<MozCookieRs>, Vec<MozCookie>>(all) };
+                        let all = all.into_iter().map(MozCookie::from).collect();
                         Ok(all)
                     }
 
@@ -83,8 +82,7 @@ macro_rules! firefoxs {
                             .await
                             .map_err(|e| napi::Error::new(napi::Status::ObjectExpected, e.to_string()))?;
 
-                        #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                        let all = unsafe { mem::transmute::<Vec<MozCookieRs>, Vec<MozCookie>>(all) };
+                        let all = all.into_iter().map(MozCookie::from).collect();
                         Ok(all)
                     }
                 }
@@ -141,8 +139,7 @@ macro_rules! firefoxs {
                             .await
                             .map_err(|e| napi::Error::new(napi::Status::ObjectExpected, e.to_string()))?;
 
-                        #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                        let all = unsafe { mem::transmute::<Vec<MozCookieRs>, Vec<MozCookie>>(all) };
+                        let all = all.into_iter().map(MozCookie::from).collect();
                         Ok(all)
                     }
 
@@ -154,8 +151,7 @@ macro_rules! firefoxs {
                             .await
                             .map_err(|e| napi::Error::new(napi::Status::ObjectExpected, e.to_string()))?;
 
-                        #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                        let all = unsafe { mem::transmute::<Vec<MozCookieRs>, Vec<MozCookie>>(all) };
+                        let all = all.into_iter().map(MozCookie::from).collect();
                         Ok(all)
                     }
                 }
@@ -183,7 +179,37 @@ pub struct MozCookie {
     pub creation_time: Option<DateTime<Utc>>,
     pub is_secure: bool,
     pub is_http_only: bool,
-    pub in_browser_element: i32,
+    pub in_browser_element: bool,
     pub same_site: SameSite,
+    /// `same_site` as a number like Chromium's `samesite`, it keeps the values `SameSite` lacks
+    pub same_site_raw: i32,
+    pub raw_same_site: Option<i32>,
     pub scheme_map: i32,
+    pub is_partitioned_attribute_set: Option<bool>,
+    pub partition_key: Option<PartitionKey>,
+}
+
+impl From<MozCookieRs> for MozCookie {
+    fn from(value: MozCookieRs) -> Self {
+        Self {
+            id: value.id,
+            origin_attributes: value.origin_attributes,
+            name: value.name,
+            value: value.value,
+            host: value.host,
+            path: value.path,
+            expiry: value.expiry,
+            last_accessed: value.last_accessed,
+            creation_time: value.creation_time,
+            is_secure: value.is_secure,
+            is_http_only: value.is_http_only,
+            in_browser_element: value.in_browser_element,
+            same_site: value.same_site.into(),
+            same_site_raw: value.same_site.into(),
+            raw_same_site: value.raw_same_site,
+            scheme_map: value.scheme_map.bits(),
+            is_partitioned_attribute_set: value.is_partitioned_attribute_set,
+            partition_key: value.partition_key.map(Into::into),
+        }
+    }
 }
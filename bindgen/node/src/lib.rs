@@ -1,3 +1,6 @@
+use decrypt_cookies_rs::browser::cookies::{
+    PartitionKey as PartitionKeyRs, SameSite as SameSiteRs,
+};
 use napi_derive::napi;
 
 mod chromium;
@@ -10,8 +13,40 @@ mod safari;
 #[derive(Default)]
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 pub enum SameSite {
+    Unspecified = -1,
     #[default]
     None = 0,
     Lax = 1,
     Strict = 2,
 }
+
+impl From<SameSiteRs> for SameSite {
+    fn from(value: SameSiteRs) -> Self {
+        match value {
+            SameSiteRs::Unspecified | SameSiteRs::Other(_) => Self::Unspecified,
+            SameSiteRs::None => Self::None,
+            SameSiteRs::Lax => Self::Lax,
+            SameSiteRs::Strict => Self::Strict,
+        }
+    }
+}
+
+#[napi(object)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct PartitionKey {
+    pub top_level_site: String,
+    pub cross_site_ancestor: bool,
+}
+
+impl From<PartitionKeyRs> for PartitionKey {
+    fn from(value: PartitionKeyRs) -> Self {
+        Self {
+            top_level_site: value.top_level_site,
+            cross_site_ancestor: value.cross_site_ancestor,
+        }
+    }
+}
@@ -1,9 +1,13 @@
-use std::{fmt::Display, mem, path::PathBuf};
+use std::{fmt::Display, path::PathBuf};
 
 use chrono::{DateTime, Utc};
 use decrypt_cookies_rs::{
     chromium::{
-        ChromiumCookie as ChromiumCookieRs, GetCookies, GetLogins, LoginData as LoginDataRs,
+        ChromiumCookie as ChromiumCookieRs, EdgeLoginExtended as EdgeLoginExtendedRs,
+        FormData as FormDataRs, FormFieldData as FormFieldDataRs, GetCookies, GetLogins,
+        InsecureCredential as InsecureCredentialRs, InsecurityType as InsecurityTypeRs,
+        LoginData as LoginDataRs, LoginStore as LoginStoreRs, PasswordNote as PasswordNoteRs,
+        PossibleUsername as PossibleUsernameRs,
     },
     prelude::{
         ChromiumCookieGetter as ChromiumCookieGetterRs, ChromiumGetter as ChromiumGetterRs,
@@ -14,7 +18,7 @@ use pyo3::{exceptions::PyValueError, prelude::*, types::PyList};
 use pyo3_async_runtimes::tokio::future_into_py;
 
 // use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
-use crate::SameSite;
+use crate::{PartitionKey, SameSite};
 
 macro_rules! chromiums {
     ($($browser:ident),* $(,)?) => {
@@ -64,8 +68,7 @@ macro_rules! chromiums {
                                 .cookies_all()
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<ChromiumCookieRs>, Vec<ChromiumCookie>>(all) };
+                            let all: Vec<ChromiumCookie> = all.into_iter().map(ChromiumCookie::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -85,8 +88,7 @@ macro_rules! chromiums {
                                 .cookies_by_host(&host)
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<ChromiumCookieRs>, Vec<ChromiumCookie>>(all) };
+                            let all: Vec<ChromiumCookie> = all.into_iter().map(ChromiumCookie::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -102,8 +104,7 @@ macro_rules! chromiums {
                                 .logins_all()
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<LoginDataRs>, Vec<LoginData>>(all) };
+                            let all: Vec<LoginData> = all.into_iter().map(LoginData::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -123,8 +124,7 @@ macro_rules! chromiums {
                                 .logins_by_host(&host)
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<LoginDataRs>, Vec<LoginData>>(all) };
+                            let all: Vec<LoginData> = all.into_iter().map(LoginData::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -175,8 +175,7 @@ macro_rules! chromiums {
                                 .cookies_all()
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<ChromiumCookieRs>, Vec<ChromiumCookie>>(all) };
+                            let all: Vec<ChromiumCookie> = all.into_iter().map(ChromiumCookie::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -196,8 +195,7 @@ macro_rules! chromiums {
                                 .cookies_by_host(&host)
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<ChromiumCookieRs>, Vec<ChromiumCookie>>(all) };
+                            let all: Vec<ChromiumCookie> = all.into_iter().map(ChromiumCookie::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -248,8 +246,7 @@ macro_rules! chromiums {
                                 .logins_all()
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<LoginDataRs>, Vec<LoginData>>(all) };
+                            let all: Vec<LoginData> = all.into_iter().map(LoginData::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -269,8 +266,7 @@ macro_rules! chromiums {
                                 .logins_by_host(&host)
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<LoginDataRs>, Vec<LoginData>>(all) };
+                            let all: Vec<LoginData> = all.into_iter().map(LoginData::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -299,6 +295,7 @@ pub struct ChromiumCookie {
     pub name: String,
     pub value: String,
     pub decrypted_value: Option<String>,
+    pub decrypt_error: Option<String>,
     pub path: String,
     pub expires_utc: Option<DateTime<Utc>>,
     pub is_secure: bool,
@@ -308,9 +305,48 @@ pub struct ChromiumCookie {
     pub is_persistent: bool,
     pub priority: i32,
     pub same_site: SameSite,
+    /// `same_site` as a number like Chromium's `samesite`, it keeps the values `SameSite` lacks
+    pub same_site_raw: i32,
     pub source_scheme: i32,
     pub source_port: i32,
     pub last_update_utc: Option<DateTime<Utc>>,
+    pub source_type: Option<i32>,
+    pub has_cross_site_ancestor: Option<bool>,
+    pub partition_key: Option<PartitionKey>,
+}
+
+impl From<ChromiumCookieRs> for ChromiumCookie {
+    fn from(value: ChromiumCookieRs) -> Self {
+        Self {
+            creation_utc: value.creation_utc,
+            host_key: value.host_key,
+            top_frame_site_key: value.top_frame_site_key,
+            name: value.name,
+            value: value.value,
+            decrypted_value: value
+                .decrypted_value
+                .map(SecretString::into_unprotected),
+            decrypt_error: value
+                .decrypt_error
+                .map(|e| e.to_string()),
+            path: value.path,
+            expires_utc: value.expires_utc,
+            is_secure: value.is_secure,
+            is_httponly: value.is_httponly,
+            last_access_utc: value.last_access_utc,
+            has_expires: value.has_expires,
+            is_persistent: value.is_persistent,
+            priority: value.priority.into(),
+            same_site: value.same_site.into(),
+            same_site_raw: value.same_site.into(),
+            source_scheme: value.source_scheme.into(),
+            source_port: value.source_port.into(),
+            last_update_utc: value.last_update_utc,
+            source_type: value.source_type,
+            has_cross_site_ancestor: value.has_cross_site_ancestor,
+            partition_key: value.partition_key.map(Into::into),
+        }
+    }
 }
 
 // #[gen_stub_pyclass]
@@ -330,7 +366,7 @@ pub struct LoginData {
     pub submit_element: String,
     pub signon_realm: String,
     pub date_created: Option<DateTime<Utc>>,
-    pub blacklisted_by_user: i32,
+    pub blacklisted_by_user: bool,
     pub scheme: i32,
     pub password_type: i32,
     pub times_used: i64,
@@ -343,5 +379,315 @@ pub struct LoginData {
     pub possible_username_pairs: Option<Vec<u8>>,
     pub id: i32,
     pub date_last_used: Option<DateTime<Utc>>,
+    pub moving_blocked_for: Option<Vec<u8>>,
     pub date_password_modified: Option<DateTime<Utc>>,
+    pub sender_email: String,
+    pub sender_name: String,
+    pub date_received: Option<DateTime<Utc>>,
+    pub sharing_notification_displayed: bool,
+    pub keychain_identifier: Vec<u8>,
+    pub sender_profile_image_url: Option<String>,
+    pub store: LoginStore,
+    pub notes: Vec<PasswordNote>,
+    pub insecure_credentials: Vec<InsecureCredential>,
+    pub edge: Option<EdgeLoginExtended>,
+    pub form: Option<FormData>,
+    pub possible_usernames: Vec<PossibleUsername>,
+}
+
+impl From<LoginDataRs> for LoginData {
+    fn from(value: LoginDataRs) -> Self {
+        Self {
+            origin_url: value.origin_url,
+            action_url: value.action_url,
+            username_element: value.username_element,
+            username_value: value.username_value,
+            password_element: value.password_element,
+            password_value: value
+                .password_value
+                .map(SecretString::into_unprotected),
+            submit_element: value.submit_element,
+            signon_realm: value.signon_realm,
+            date_created: value.date_created,
+            blacklisted_by_user: value.blacklisted_by_user,
+            scheme: value.scheme.into(),
+            password_type: value.password_type.into(),
+            times_used: value.times_used,
+            form_data: value.form_data,
+            display_name: value.display_name,
+            icon_url: value.icon_url,
+            federation_url: value.federation_url,
+            skip_zero_click: value.skip_zero_click,
+            generation_upload_status: value
+                .generation_upload_status
+                .into(),
+            possible_username_pairs: value.possible_username_pairs,
+            id: value.id,
+            date_last_used: value.date_last_used,
+            moving_blocked_for: value.moving_blocked_for,
+            date_password_modified: value.date_password_modified,
+            sender_email: value.sender_email,
+            sender_name: value.sender_name,
+            date_received: value.date_received,
+            sharing_notification_displayed: value.sharing_notification_displayed,
+            keychain_identifier: value.keychain_identifier,
+            sender_profile_image_url: value.sender_profile_image_url,
+            store: value.store.into(),
+            notes: value
+                .notes
+                .into_iter()
+                .map(PasswordNote::from)
+                .collect(),
+            insecure_credentials: value
+                .insecure_credentials
+                .into_iter()
+                .map(InsecureCredential::from)
+                .collect(),
+            edge: value
+                .edge
+                .map(EdgeLoginExtended::from),
+            form: value.form.map(FormData::from),
+            possible_usernames: value
+                .possible_usernames
+                .into_iter()
+                .map(PossibleUsername::from)
+                .collect(),
+        }
+    }
+}
+
+// #[gen_stub_pyclass_enum]
+#[pyclass(eq, eq_int, ord, from_py_object)]
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub enum LoginStore {
+    #[default]
+    Local = 0,
+    Account = 1,
+}
+
+impl From<LoginStoreRs> for LoginStore {
+    fn from(value: LoginStoreRs) -> Self {
+        match value {
+            LoginStoreRs::Local => Self::Local,
+            LoginStoreRs::Account => Self::Account,
+        }
+    }
+}
+
+// #[gen_stub_pyclass]
+#[pyclass(get_all, set_all, eq, ord, from_py_object)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct PasswordNote {
+    pub key: String,
+    pub value: Option<String>,
+    pub date_created: Option<DateTime<Utc>>,
+    pub confidential: bool,
+}
+
+impl From<PasswordNoteRs> for PasswordNote {
+    fn from(value: PasswordNoteRs) -> Self {
+        Self {
+            key: value.key,
+            value: value
+                .value
+                .map(SecretString::into_unprotected),
+            date_created: value.date_created,
+            confidential: value.confidential,
+        }
+    }
+}
+
+// #[gen_stub_pyclass_enum]
+#[pyclass(eq, eq_int, ord, from_py_object)]
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub enum InsecurityType {
+    #[default]
+    Leaked = 0,
+    Phished = 1,
+    Weak = 2,
+    Reused = 3,
+    /// A type this crate doesn't know, see `insecurity_type_raw`
+    Other = -1,
+}
+
+// #[gen_stub_pyclass]
+#[pyclass(get_all, set_all, eq, ord, from_py_object)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct InsecureCredential {
+    pub insecurity_type: InsecurityType,
+    /// `insecurity_type` as a number like Chromium's `insecure_type`, it keeps the values `InsecurityType` lacks
+    pub insecurity_type_raw: i32,
+    pub create_time: Option<DateTime<Utc>>,
+    pub is_muted: bool,
+    pub trigger_notification_from_backend: bool,
+}
+
+impl From<InsecurityTypeRs> for InsecurityType {
+    fn from(value: InsecurityTypeRs) -> Self {
+        match value {
+            InsecurityTypeRs::Leaked => Self::Leaked,
+            InsecurityTypeRs::Phished => Self::Phished,
+            InsecurityTypeRs::Weak => Self::Weak,
+            InsecurityTypeRs::Reused => Self::Reused,
+            InsecurityTypeRs::Other(_) => Self::Other,
+        }
+    }
+}
+
+impl From<InsecureCredentialRs> for InsecureCredential {
+    fn from(value: InsecureCredentialRs) -> Self {
+        Self {
+            insecurity_type: value.insecurity_type.into(),
+            insecurity_type_raw: value.insecurity_type.into(),
+            create_time: value.create_time,
+            is_muted: value.is_muted,
+            trigger_notification_from_backend: value.trigger_notification_from_backend,
+        }
+    }
+}
+
+// #[gen_stub_pyclass]
+#[pyclass(get_all, set_all, eq, ord, from_py_object)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct EdgeLoginExtended {
+    pub source: String,
+    pub strength_alert_status: i32,
+    pub password_nickname: String,
+    pub password_category: i32,
+}
+
+impl From<EdgeLoginExtendedRs> for EdgeLoginExtended {
+    fn from(value: EdgeLoginExtendedRs) -> Self {
+        Self {
+            source: value.source,
+            strength_alert_status: value.strength_alert_status,
+            password_nickname: value.password_nickname,
+            password_category: value.password_category,
+        }
+    }
+}
+
+// #[gen_stub_pyclass]
+#[pyclass(get_all, set_all, eq, ord, from_py_object)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct FormData {
+    pub name: String,
+    pub url: String,
+    pub action: String,
+    pub fields: Vec<FormFieldData>,
+    pub is_form_tag: bool,
+}
+
+impl From<FormDataRs> for FormData {
+    fn from(value: FormDataRs) -> Self {
+        Self {
+            name: value.name,
+            url: value.url,
+            action: value.action,
+            fields: value
+                .fields
+                .into_iter()
+                .map(FormFieldData::from)
+                .collect(),
+            is_form_tag: value.is_form_tag,
+        }
+    }
+}
+
+// #[gen_stub_pyclass]
+#[pyclass(get_all, set_all, eq, ord, from_py_object)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct FormFieldData {
+    pub label: String,
+    pub name: String,
+    pub value: String,
+    pub form_control_type: String,
+    pub autocomplete_attribute: String,
+    pub max_length: u64,
+    pub is_autofilled: bool,
+    pub check_status: i32,
+    pub is_focusable: bool,
+    pub should_autocomplete: bool,
+    pub role: i32,
+    pub text_direction: i32,
+    pub option_values: Vec<String>,
+    pub option_contents: Vec<String>,
+    pub placeholder: String,
+    pub css_classes: String,
+    pub properties_mask: u32,
+    pub id_attribute: String,
+    pub name_attribute: String,
+}
+
+impl From<FormFieldDataRs> for FormFieldData {
+    fn from(value: FormFieldDataRs) -> Self {
+        Self {
+            label: value.label,
+            name: value.name,
+            value: value.value,
+            form_control_type: value.form_control_type,
+            autocomplete_attribute: value.autocomplete_attribute,
+            max_length: value.max_length,
+            is_autofilled: value.is_autofilled,
+            check_status: value.check_status,
+            is_focusable: value.is_focusable,
+            should_autocomplete: value.should_autocomplete,
+            role: value.role,
+            text_direction: value.text_direction,
+            option_values: value.option_values,
+            option_contents: value.option_contents,
+            placeholder: value.placeholder,
+            css_classes: value.css_classes,
+            properties_mask: value.properties_mask,
+            id_attribute: value.id_attribute,
+            name_attribute: value.name_attribute,
+        }
+    }
+}
+
+// #[gen_stub_pyclass]
+#[pyclass(get_all, set_all, eq, ord, from_py_object)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct PossibleUsername {
+    pub value: String,
+    pub field_name: String,
+}
+
+impl From<PossibleUsernameRs> for PossibleUsername {
+    fn from(value: PossibleUsernameRs) -> Self {
+        Self {
+            value: value.value,
+            field_name: value.field_name,
+        }
+    }
 }
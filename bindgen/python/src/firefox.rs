@@ -1,4 +1,4 @@
-use std::{fmt::Display, mem, path::PathBuf};
+use std::{fmt::Display, path::PathBuf};
 
 use chrono::{DateTime, Utc};
 use decrypt_cookies_rs::{
@@ -12,7 +12,7 @@ use pyo3::{Bound, PyResult, Python, exceptions::PyValueError, pyclass, pymethods
 use pyo3_async_runtimes::tokio::future_into_py;
 
 // use pyo3_stub_gen::derive::{gen_stub_pyclass, gen_stub_pymethods};
-use crate::SameSite;
+use crate::{PartitionKey, SameSite};
 
 macro_rules! firefoxs {
     ($($browser:ident),* $(,)?) => {
@@ -83,8 +83,7 @@ macro_rules! firefoxs {
                                 .cookies_all()
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<MozCookieRs>, Vec<MozCookie>>(all) };
+                            let all: Vec<MozCookie> = all.into_iter().map(MozCookie::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -104,8 +103,7 @@ macro_rules! firefoxs {
                                 .cookies_by_host(&host)
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<MozCookieRs>, Vec<MozCookie>>(all) };
+                            let all: Vec<MozCookie> = all.into_iter().map(MozCookie::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -177,8 +175,7 @@ macro_rules! firefoxs {
                                 .cookies_all()
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<MozCookieRs>, Vec<MozCookie>>(all) };
+                            let all: Vec<MozCookie> = all.into_iter().map(MozCookie::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -198,8 +195,7 @@ macro_rules! firefoxs {
                                 .cookies_by_host(&host)
                                 .await
                                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                            #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-                            let all = unsafe { mem::transmute::<Vec<MozCookieRs>, Vec<MozCookie>>(all) };
+                            let all: Vec<MozCookie> = all.into_iter().map(MozCookie::from).collect();
                             Ok(all)
                         })
                         .map(|v| unsafe { v.cast_into_unchecked() })
@@ -230,7 +226,37 @@ pub struct MozCookie {
     pub creation_time: Option<DateTime<Utc>>,
     pub is_secure: bool,
     pub is_http_only: bool,
-    pub in_browser_element: i32,
+    pub in_browser_element: bool,
     pub same_site: SameSite,
+    /// `same_site` as a number like Chromium's `samesite`, it keeps the values `SameSite` lacks
+    pub same_site_raw: i32,
+    pub raw_same_site: Option<i32>,
     pub scheme_map: i32,
+    pub is_partitioned_attribute_set: Option<bool>,
+    pub partition_key: Option<PartitionKey>,
+}
+
+impl From<MozCookieRs> for MozCookie {
+    fn from(value: MozCookieRs) -> Self {
+        Self {
+            id: value.id,
+            origin_attributes: value.origin_attributes,
+            name: value.name,
+            value: value.value,
+            host: value.host,
+            path: value.path,
+            expiry: value.expiry,
+            last_accessed: value.last_accessed,
+            creation_time: value.creation_time,
+            is_secure: value.is_secure,
+            is_http_only: value.is_http_only,
+            in_browser_element: value.in_browser_element,
+            same_site: value.same_site.into(),
+            same_site_raw: value.same_site.into(),
+            raw_same_site: value.raw_same_site,
+            scheme_map: value.scheme_map.bits(),
+            is_partitioned_attribute_set: value.is_partitioned_attribute_set,
+            partition_key: value.partition_key.map(Into::into),
+        }
+    }
 }
@@ -1,4 +1,6 @@
-use decrypt_cookies_rs::browser::cookies::SameSite as SameSiteRs;
+use decrypt_cookies_rs::browser::cookies::{
+    PartitionKey as PartitionKeyRs, SameSite as SameSiteRs,
+};
 use pyo3::prelude::*;
 
 // use pyo3_stub_gen::{define_stub_info_gatherer, derive::gen_stub_pyclass_enum};
@@ -19,15 +21,29 @@ mod safari;
 #[derive(Default)]
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 pub enum SameSite {
+    Unspecified = -1,
     #[default]
     Non = 0,
     Lax = 1,
     Strict = 2,
 }
 
+// #[gen_stub_pyclass]
+#[pyclass(get_all, set_all, eq, ord, from_py_object)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[repr(C)]
+pub struct PartitionKey {
+    pub top_level_site: String,
+    pub cross_site_ancestor: bool,
+}
+
 impl From<SameSiteRs> for SameSite {
     fn from(value: SameSiteRs) -> Self {
         match value {
+            SameSiteRs::Unspecified | SameSiteRs::Other(_) => Self::Unspecified,
             SameSiteRs::None => Self::Non,
             SameSiteRs::Lax => Self::Lax,
             SameSiteRs::Strict => Self::Strict,
@@ -35,10 +51,20 @@ impl From<SameSiteRs> for SameSite {
     }
 }
 
+impl From<PartitionKeyRs> for PartitionKey {
+    fn from(value: PartitionKeyRs) -> Self {
+        Self {
+            top_level_site: value.top_level_site,
+            cross_site_ancestor: value.cross_site_ancestor,
+        }
+    }
+}
+
 /// A Python module implemented in Rust.
 #[pymodule]
 pub fn decrypt_cookies(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<SameSite>()?;
+    m.add_class::<PartitionKey>()?;
 
     macro_rules! browsers {
         ($($browser:ident),* $(,)?) => {
@@ -59,6 +85,14 @@ pub fn decrypt_cookies(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     m.add_class::<ChromiumCookie>()?;
     m.add_class::<LoginData>()?;
+    m.add_class::<LoginStore>()?;
+    m.add_class::<PasswordNote>()?;
+    m.add_class::<InsecurityType>()?;
+    m.add_class::<InsecureCredential>()?;
+    m.add_class::<EdgeLoginExtended>()?;
+    m.add_class::<FormData>()?;
+    m.add_class::<FormFieldData>()?;
+    m.add_class::<PossibleUsername>()?;
 
     m.add_class::<MozCookie>()?;
 
@@ -1,4 +1,4 @@
-use std::{fmt::Display, mem, path::PathBuf};
+use std::{fmt::Display, path::PathBuf};
 
 use chrono::{DateTime, Utc};
 use decrypt_cookies_rs::prelude::{
@@ -44,23 +44,20 @@ impl SafariGetter {
     }
 
     pub fn cookies_all(&self) -> Vec<SafariCookie> {
-        let all = self.0.cookies_all().to_vec();
-        #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-        unsafe {
-            mem::transmute::<Vec<SafariCookieRs>, Vec<SafariCookie>>(all)
-        }
+        self.0
+            .cookies_all()
+            .iter()
+            .cloned()
+            .map(SafariCookie::from)
+            .collect()
     }
 
     pub fn cookies_by_host(&self, host: &str) -> Vec<SafariCookie> {
-        let all = self
-            .0
+        self.0
             .cookies_by_host(host)
             .cloned()
-            .collect();
-        #[expect(clippy::transmute_undefined_repr, reason = "already repr(C)")]
-        unsafe {
-            mem::transmute::<Vec<SafariCookieRs>, Vec<SafariCookie>>(all)
-        }
+            .map(SafariCookie::from)
+            .collect()
     }
 }
 
@@ -87,3 +84,23 @@ pub struct SafariCookie {
     pub is_secure: bool,
     pub is_http_only: bool,
 }
+
+impl From<SafariCookieRs> for SafariCookie {
+    fn from(value: SafariCookieRs) -> Self {
+        Self {
+            version: value.version,
+            flags: value.flags,
+            port: value.port,
+            comment: value.comment,
+            domain: value.domain,
+            name: value.name,
+            path: value.path,
+            value: value.value,
+            expires: value.expires,
+            creation: value.creation,
+            same_site: value.same_site.into(),
+            is_secure: value.is_secure,
+            is_http_only: value.is_http_only,
+        }
+    }
+}
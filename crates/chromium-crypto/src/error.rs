@@ -13,6 +13,12 @@ pub enum CryptoError {
         #[snafu(implicit)]
         location: Location,
     },
+    #[snafu(display("The plaintext doesn't start with the SHA-256 of `{host_key}`\n@:{location}"))]
+    HostKeyDigest {
+        host_key: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
     #[snafu(display("{source}\n@:{location}"))]
     GetPass {
         source: secret_service::Error,
@@ -38,6 +44,12 @@ pub enum CryptoError {
         #[snafu(implicit)]
         location: Location,
     },
+    #[snafu(display("The plaintext doesn't start with the SHA-256 of `{host_key}`\n@:{location}"))]
+    HostKeyDigest {
+        host_key: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
     #[snafu(display("{source}\n@:{location}"))]
     Keyring {
         source: keyring::Error,
@@ -69,6 +81,12 @@ pub enum CryptoError {
         #[snafu(implicit)]
         location: Location,
     },
+    #[snafu(display("The plaintext doesn't start with the SHA-256 of `{host_key}`\n@:{location}"))]
+    HostKeyDigest {
+        host_key: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
     #[snafu(display("{source}, path: {}\n@:{location}",path.display()))]
     Io {
         source: std::io::Error,
@@ -106,6 +124,11 @@ pub enum CryptoError {
         #[snafu(implicit)]
         location: Location,
     },
+    #[snafu(display("The ciphertext is shorter than its prefix and nonce\n@:{location}"))]
+    TooShort {
+        #[snafu(implicit)]
+        location: Location,
+    },
     #[snafu(display("CryptUnprotectData returned a null pointer\n@:{location}"))]
     CryptUnprotectDataNull {
         #[snafu(implicit)]
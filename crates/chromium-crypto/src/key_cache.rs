@@ -0,0 +1,552 @@
+//! Opt-in on-disk cache of the derived Chromium keys.
+//!
+//! Every entry is encrypted with AES-256-GCM, the key comes from a user supplied passphrase or
+//! is bound to the machine (not on macOS).
+//! An entry is dropped when it is older than the TTL or when the fingerprint of the key source
+//! (`Local State` on Windows, the keyring item on Linux) changed.
+//! macOS keychain items expose no modification time, so there only the TTL applies.
+
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aes_gcm::{
+    Aes256Gcm, KeyInit,
+    aead::{Aead, AeadCore, OsRng, Payload, rand_core::RngCore},
+};
+use sha2::{Digest, Sha256};
+use snafu::{Location, OptionExt, ResultExt, Snafu, ensure};
+use tokio::fs;
+use zeroize::Zeroizing;
+
+use crate::{Decrypter, SecretBytes, SecretString};
+
+#[derive(Debug)]
+#[derive(Snafu)]
+#[snafu(visibility(pub))]
+#[non_exhaustive]
+pub enum KeyCacheError {
+    #[snafu(display("{source}, path: {}\n@:{location}", path.display()))]
+    Io {
+        source: std::io::Error,
+        path: PathBuf,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("Decrypt or encrypt key cache failed: {source}\n@:{location}"))]
+    Cipher {
+        source: aes_gcm::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("Malformed key cache entry\n@:{location}"))]
+    Malformed {
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display(
+        "A machine bound cache would keep the keychain secret outside of the keychain, use a \
+         passphrase\n@:{location}"
+    ))]
+    MachineSecret {
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("Can not read the machine id\n@:{location}"))]
+    MachineId {
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[cfg(target_os = "linux")]
+    #[snafu(display("{source}\n@:{location}"))]
+    Keyring {
+        source: secret_service::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[cfg(target_os = "windows")]
+    #[snafu(display("{source}\n@:{location}"))]
+    LocalState {
+        source: serde_json::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, KeyCacheError>;
+
+/// What the cache file key is derived from
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CacheSecret {
+    /// PBKDF2-HMAC-SHA256 of the passphrase
+    Passphrase(SecretString),
+    /// The machine id and the current user.
+    /// Stops the file from being usable on another machine, not from being read by the same user.
+    ///
+    /// Refused on macOS, where it would let any process of the user skip the keychain prompt.
+    Machine,
+}
+
+impl CacheSecret {
+    const fn kind(&self) -> u8 {
+        match self {
+            Self::Passphrase(_) => 0,
+            Self::Machine => 1,
+        }
+    }
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub struct KeyCache {
+    dir: PathBuf,
+    secret: CacheSecret,
+    ttl: Duration,
+}
+
+impl KeyCache {
+    const MAGIC: &'static [u8] = b"DCKC";
+    const VERSION: u8 = 1;
+    const SALT_LEN: usize = 16;
+    const NONCE_LEN: usize = 12;
+    const HEADER_LEN: usize = Self::MAGIC.len() + 2 + Self::SALT_LEN + Self::NONCE_LEN;
+    const PBKDF2_ITERATIONS: u32 = 210_000;
+    /// One day
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// `dir`: where the entries are written, one file per browser profile
+    pub const fn new(dir: PathBuf, secret: CacheSecret) -> Self {
+        Self { dir, secret, ttl: Self::DEFAULT_TTL }
+    }
+
+    #[must_use]
+    pub const fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// A stable file name for a browser profile
+    pub fn entry_id(browser: &str, profile: &Path) -> String {
+        let hash = Sha256::digest(
+            profile
+                .as_os_str()
+                .as_encoded_bytes(),
+        );
+        let mut id = String::with_capacity(browser.len() + 17);
+        id.extend(
+            browser
+                .chars()
+                .filter(char::is_ascii_alphanumeric),
+        );
+        id.push('-');
+        for b in &hash[..8] {
+            _ = write!(id, "{b:02x}");
+        }
+        id
+    }
+
+    fn entry_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.key"))
+    }
+
+    /// Return the cached [`Decrypter`] if the entry is fresh and the `fingerprint` matches.
+    ///
+    /// Stale or mismatched entries are removed.
+    pub async fn load(&self, id: &str, fingerprint: &[u8]) -> Result<Option<Decrypter>> {
+        let path = self.entry_path(id);
+        let data = match fs::read(&path).await {
+            Ok(v) => v,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).context(IoSnafu { path }),
+        };
+
+        let entry = match self.open(id, &data) {
+            Ok(v) => v,
+            Err(e) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(error = %e, "drop unreadable key cache entry");
+                _ = e;
+                self.invalidate(id).await?;
+                return Ok(None);
+            },
+        };
+
+        let age = now_secs().saturating_sub(entry.created);
+        if age >= self.ttl.as_secs() || entry.fingerprint != fingerprint {
+            self.invalidate(id).await?;
+            return Ok(None);
+        }
+
+        Ok(Decrypter::from_key_material(entry.keys))
+    }
+
+    pub async fn store(&self, id: &str, fingerprint: &[u8], decrypter: &Decrypter) -> Result<()> {
+        let mut plain = Zeroizing::new(Vec::new());
+        plain.extend_from_slice(&now_secs().to_le_bytes());
+        push_chunk(&mut plain, fingerprint);
+        let keys = decrypter.key_material();
+        plain.push(keys.len() as u8);
+        for key in keys {
+            push_chunk(&mut plain, key);
+        }
+
+        let mut salt = [0_u8; Self::SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let mut data = Vec::with_capacity(Self::HEADER_LEN + plain.len() + 16);
+        data.extend_from_slice(Self::MAGIC);
+        data.push(Self::VERSION);
+        data.push(self.secret.kind());
+        data.extend_from_slice(&salt);
+        data.extend_from_slice(&nonce);
+
+        let key = self.cipher_key(&salt)?;
+        let cipher = Aes256Gcm::new(key.as_slice().into());
+        let aad = [&data[..Self::HEADER_LEN], id.as_bytes()].concat();
+        let ciphertext = cipher
+            .encrypt(&nonce, Payload { msg: &plain, aad: &aad })
+            .context(CipherSnafu)?;
+        data.extend_from_slice(&ciphertext);
+
+        fs::create_dir_all(&self.dir)
+            .await
+            .with_context(|_| IoSnafu { path: self.dir.clone() })?;
+        let path = self.entry_path(id);
+        write_private(&path, data).await
+    }
+
+    /// Remove the entry of `id`
+    pub async fn invalidate(&self, id: &str) -> Result<()> {
+        let path = self.entry_path(id);
+        match fs::remove_file(&path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e).context(IoSnafu { path }),
+            _ => Ok(()),
+        }
+    }
+
+    fn open(&self, id: &str, data: &[u8]) -> Result<Entry> {
+        ensure!(
+            data.len() > Self::HEADER_LEN
+                && data.starts_with(Self::MAGIC)
+                && data[Self::MAGIC.len()] == Self::VERSION
+                && data[Self::MAGIC.len() + 1] == self.secret.kind(),
+            MalformedSnafu
+        );
+        let (header, ciphertext) = data.split_at(Self::HEADER_LEN);
+        let salt = &header[Self::MAGIC.len() + 2..][..Self::SALT_LEN];
+        let nonce = &header[Self::MAGIC.len() + 2 + Self::SALT_LEN..];
+
+        let key = self.cipher_key(salt)?;
+        let cipher = Aes256Gcm::new(key.as_slice().into());
+        let aad = [header, id.as_bytes()].concat();
+        let plain = Zeroizing::new(
+            cipher
+                .decrypt(nonce.into(), Payload { msg: ciphertext, aad: &aad })
+                .context(CipherSnafu)?,
+        );
+
+        Entry::parse(&plain).context(MalformedSnafu)
+    }
+
+    fn cipher_key(&self, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+        let mut key = Zeroizing::new([0_u8; 32]);
+        match &self.secret {
+            CacheSecret::Passphrase(pass) => pbkdf2::pbkdf2_hmac::<Sha256>(
+                pass.expose_secret().as_bytes(),
+                salt,
+                Self::PBKDF2_ITERATIONS,
+                key.as_mut_slice(),
+            ),
+            CacheSecret::Machine => {
+                ensure!(!cfg!(target_os = "macos"), MachineSecretSnafu);
+                let machine = machine_id()?;
+                let mut hasher = Sha256::new();
+                hasher.update(b"decrypt-cookies key cache");
+                hasher.update(salt);
+                hasher.update(machine.as_bytes());
+                hasher.update(
+                    std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })
+                        .unwrap_or_default()
+                        .as_encoded_bytes(),
+                );
+                key.copy_from_slice(&hasher.finalize());
+            },
+        }
+        Ok(key)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl KeyCache {
+    /// Identify the keyring item without unlocking the collection or reading the secret
+    pub async fn keyring_fingerprint(safe_storage: &str) -> Result<Option<Vec<u8>>> {
+        use secret_service::{EncryptionType, SecretService};
+
+        let ss = SecretService::connect(EncryptionType::Plain)
+            .await
+            .context(KeyringSnafu)?;
+        let collection = ss
+            .get_default_collection()
+            .await
+            .context(KeyringSnafu)?;
+        for item in collection
+            .get_all_items()
+            .await
+            .context(KeyringSnafu)?
+        {
+            let Ok(label) = item.get_label().await
+            else {
+                continue;
+            };
+            if label != safe_storage {
+                continue;
+            }
+            let created = item
+                .get_created()
+                .await
+                .context(KeyringSnafu)?;
+            let modified = item
+                .get_modified()
+                .await
+                .context(KeyringSnafu)?;
+
+            let mut fp = label.into_bytes();
+            fp.extend_from_slice(&created.to_le_bytes());
+            fp.extend_from_slice(&modified.to_le_bytes());
+            return Ok(Some(fp));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl KeyCache {
+    /// Hash of the encrypted keys in `Local State`
+    pub async fn local_state_fingerprint(key_path: &Path) -> Result<Vec<u8>> {
+        let content = fs::read_to_string(key_path)
+            .await
+            .with_context(|_| IoSnafu { path: key_path.to_owned() })?;
+        let local_state: crate::win::local_state::LocalState =
+            serde_json::from_str(&content).context(LocalStateSnafu)?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(local_state.os_crypt.encrypted_key);
+        hasher.update([0]);
+        hasher.update(
+            local_state
+                .os_crypt
+                .app_bound_encrypted_key
+                .unwrap_or_default(),
+        );
+        Ok(hasher.finalize().to_vec())
+    }
+}
+
+struct Entry {
+    created: u64,
+    fingerprint: Vec<u8>,
+    keys: Vec<SecretBytes>,
+}
+
+impl Entry {
+    fn parse(mut plain: &[u8]) -> Option<Self> {
+        let created = u64::from_le_bytes(
+            take(&mut plain, 8)?
+                .try_into()
+                .ok()?,
+        );
+        let fingerprint = take_chunk(&mut plain)?.to_vec();
+        let count = *take(&mut plain, 1)?.first()?;
+        let keys = (0..count)
+            .map(|_| take_chunk(&mut plain).map(|k| SecretBytes::new(k.to_vec())))
+            .collect::<Option<_>>()?;
+        Some(Self { created, fingerprint, keys })
+    }
+}
+
+fn push_chunk(buf: &mut Vec<u8>, chunk: &[u8]) {
+    buf.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+    buf.extend_from_slice(chunk);
+}
+
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    let (head, tail) = buf.split_at_checked(len)?;
+    *buf = tail;
+    Some(head)
+}
+
+fn take_chunk<'a>(buf: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = u32::from_le_bytes(take(buf, 4)?.try_into().ok()?);
+    take(buf, len as usize)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Write a temporary file and rename it over `path`, a reader never sees a partial entry
+async fn write_private(path: &Path, data: Vec<u8>) -> Result<()> {
+    let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+    let res = write_new_private(&tmp, &data).await;
+    let res = match res {
+        Ok(()) => fs::rename(&tmp, path)
+            .await
+            .with_context(|_| IoSnafu { path: path.to_owned() }),
+        Err(e) => Err(e),
+    };
+    if res.is_err() {
+        _ = fs::remove_file(&tmp).await;
+    }
+    res
+}
+
+async fn write_new_private(path: &Path, data: &[u8]) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options
+        .write(true)
+        .create(true)
+        .truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options
+        .open(path)
+        .await
+        .with_context(|_| IoSnafu { path: path.to_owned() })?;
+    tokio::io::AsyncWriteExt::write_all(&mut file, data)
+        .await
+        .with_context(|_| IoSnafu { path: path.to_owned() })?;
+    file.sync_all()
+        .await
+        .with_context(|_| IoSnafu { path: path.to_owned() })
+}
+
+fn machine_id() -> Result<String> {
+    #[cfg(target_os = "linux")]
+    let id = ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .into_iter()
+        .find_map(|p| std::fs::read_to_string(p).ok());
+
+    #[cfg(target_os = "macos")]
+    let id = std::process::Command::new("ioreg")
+        .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+        .output()
+        .ok()
+        .and_then(|out| {
+            let out = String::from_utf8(out.stdout).ok()?;
+            let line = out
+                .lines()
+                .find(|l| l.contains("IOPlatformUUID"))?;
+            line.rsplit('"')
+                .nth(1)
+                .map(ToOwned::to_owned)
+        });
+
+    #[cfg(target_os = "windows")]
+    let id = std::process::Command::new("reg")
+        .args([
+            "query",
+            r"HKLM\SOFTWARE\Microsoft\Cryptography",
+            "/v",
+            "MachineGuid",
+        ])
+        .output()
+        .ok()
+        .and_then(|out| {
+            let out = String::from_utf8(out.stdout).ok()?;
+            out.split_whitespace()
+                .last()
+                .map(ToOwned::to_owned)
+        });
+
+    id.map(|v| v.trim().to_owned())
+        .filter(|v| !v.is_empty())
+        .context(MachineIdSnafu)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn roundtrip_and_invalidate() {
+        let dir =
+            std::env::temp_dir().join(format!("chromium-crypto-key-cache-{}", std::process::id()));
+        let cache = KeyCache::new(dir.clone(), CacheSecret::Passphrase("passphrase".into()));
+        let id = KeyCache::entry_id("Chrome", Path::new("/home/u/.config/google-chrome"));
+        let decrypter =
+            Decrypter::from_key_material(vec![SecretBytes::new(b"secret".to_vec())]).unwrap();
+
+        cache
+            .store(&id, b"fp", &decrypter)
+            .await
+            .unwrap();
+        let loaded = cache
+            .load(&id, b"fp")
+            .await
+            .unwrap();
+        assert_eq!(loaded.as_ref(), Some(&decrypter));
+        // the temporary file was renamed over the entry
+        assert_eq!(
+            std::fs::read_dir(&dir)
+                .unwrap()
+                .count(),
+            1
+        );
+
+        let wrong = KeyCache::new(dir.clone(), CacheSecret::Passphrase("other".into()));
+        assert!(
+            wrong
+                .load(&id, b"fp")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        cache
+            .store(&id, b"fp", &decrypter)
+            .await
+            .unwrap();
+        assert!(
+            cache
+                .load(&id, b"changed")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        // mismatched entry was removed
+        assert!(
+            !dir.join(format!("{id}.key"))
+                .exists()
+        );
+
+        let expired = cache.clone().ttl(Duration::ZERO);
+        cache
+            .store(&id, b"fp", &decrypter)
+            .await
+            .unwrap();
+        assert!(
+            expired
+                .load(&id, b"fp")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -17,11 +17,98 @@ cfg_select! {
 }
 
 pub mod error;
+#[cfg(feature = "key-cache")]
+pub mod key_cache;
+pub mod secret;
 
-#[derive(Clone, Copy)]
-#[derive(Debug)]
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
-pub enum Which {
-    Cookie,
-    Login,
+#[cfg(feature = "key-cache")]
+pub use key_cache::{CacheSecret, KeyCache};
+pub use secret::{SecretBytes, SecretString};
+
+impl Decrypter {
+    /// Encrypt the value of a `Cookies` row.
+    ///
+    /// Since version 24 of `Cookies` the plaintext starts with the SHA-256 of `host_key`,
+    /// pass `None` for older databases.
+    pub fn encrypt_cookie(&self, value: &str, host_key: Option<&str>) -> error::Result<Vec<u8>> {
+        use sha2::{Digest, Sha256};
+
+        let mut plaintext = zeroize::Zeroizing::new(Vec::with_capacity(32 + value.len()));
+        if let Some(host_key) = host_key {
+            plaintext.extend_from_slice(&Sha256::digest(host_key));
+        }
+        plaintext.extend_from_slice(value.as_bytes());
+        self.encrypt_raw(&plaintext)
+    }
+
+    /// Decrypt the value of a `Cookies` row, the counterpart of [`Decrypter::encrypt_cookie`].
+    ///
+    /// With `host_key`, the plaintext must start with its SHA-256, which is stripped,
+    /// otherwise it fails with [`CryptoError::HostKeyDigest`](error::CryptoError::HostKeyDigest).
+    /// An empty `ciphertext`, a cookie stored in plaintext, decrypts to an empty string.
+    pub fn decrypt_cookie(
+        &self,
+        ciphertext: &mut [u8],
+        host_key: Option<&str>,
+    ) -> error::Result<SecretString> {
+        use sha2::{Digest, Sha256};
+        use snafu::{ResultExt, ensure};
+
+        if ciphertext.is_empty() {
+            return Ok(SecretString::default());
+        }
+        let plaintext = self.decrypt_raw(ciphertext)?;
+        let mut value = plaintext.expose_secret();
+        if let Some(host_key) = host_key {
+            let digest = value.split_off(..32);
+            ensure!(
+                digest.is_some_and(|v| v == Sha256::digest(host_key).as_slice()),
+                error::HostKeyDigestSnafu { host_key }
+            );
+        }
+        str::from_utf8(value)
+            .map(SecretString::from)
+            .context(error::Utf8Snafu)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_round_trip() {
+        let crypto = Decrypter::default();
+
+        let mut value = crypto
+            .encrypt_cookie("session", None)
+            .unwrap();
+        let plain = crypto.decrypt(&mut value).unwrap();
+        assert_eq!(plain.expose_secret(), "session");
+
+        let mut value = crypto
+            .encrypt_cookie("session", Some(".a.com"))
+            .unwrap();
+        let plain = crypto
+            .decrypt_cookie(&mut value.clone(), Some(".a.com"))
+            .unwrap();
+        assert_eq!(plain.expose_secret(), "session");
+        assert!(matches!(
+            crypto.decrypt_cookie(&mut value, Some(".b.com")),
+            Err(error::CryptoError::HostKeyDigest { .. })
+        ));
+        let mut value = crypto
+            .encrypt_cookie("session", None)
+            .unwrap();
+        let plain = crypto
+            .decrypt_cookie(&mut value, None)
+            .unwrap();
+        assert_eq!(plain.expose_secret(), "session");
+
+        let mut value = crypto
+            .encrypt_raw(b"hunter2")
+            .unwrap();
+        let plain = crypto.decrypt(&mut value).unwrap();
+        assert_eq!(plain.expose_secret(), "hunter2");
+    }
 }
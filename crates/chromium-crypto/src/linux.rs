@@ -1,20 +1,26 @@
-use std::{str, sync::LazyLock};
+use std::{
+    collections::HashMap,
+    str,
+    sync::{Arc, LazyLock, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
-use aes::cipher::{BlockDecryptMut, KeyIvInit, block_padding};
+use aes::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit, block_padding};
 use pbkdf2::pbkdf2_hmac;
 use secret_service::{EncryptionType, SecretService};
 use snafu::ResultExt;
-use tinyufo::TinyUfo;
+use zeroize::{Zeroize, Zeroizing};
 
 use crate::{
-    Which,
     error::{self, Result, Utf8Snafu},
+    secret::{SecretBytes, SecretString},
 };
 
 // https://source.chromium.org/chromium/chromium/src/+/main:components/os_crypt/sync/os_crypt_linux.cc;l=32
 /// Key size required for 128 bit AES.
 // const K_DERIVED_KEY_SIZE_IN_BITS: u32 = 128;
 type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
+type Aes128CbcEnc = cbc::Encryptor<aes::Aes128>;
 
 // https://source.chromium.org/chromium/chromium/src/+/main:components/os_crypt/sync/os_crypt_linux.cc;l=50
 /// The UMA metric name for whether the false was decryptable with an empty key.
@@ -25,19 +31,93 @@ type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
 #[derive(Default)]
 #[derive(PartialEq, Eq)]
 pub struct Decrypter {
-    pass_v11: &'static [u8],
+    pass_v11: Arc<SecretBytes>,
 }
 
 impl Decrypter {
-    pub const fn pass_v11(&self) -> &[u8] {
-        self.pass_v11
+    pub fn pass_v11(&self) -> &[u8] {
+        self.pass_v11.expose_secret()
+    }
+
+    #[cfg(feature = "key-cache")]
+    pub(crate) fn key_material(&self) -> Vec<&[u8]> {
+        vec![self.pass_v11.expose_secret()]
+    }
+
+    #[cfg(feature = "key-cache")]
+    pub(crate) fn from_key_material(keys: Vec<SecretBytes>) -> Option<Self> {
+        let [pass_v11] = <[SecretBytes; 1]>::try_from(keys).ok()?;
+        Some(Self { pass_v11: Arc::new(pass_v11) })
     }
 }
 
-static CACHE_PASSWD: LazyLock<TinyUfo<&str, &'static [u8]>> =
-    LazyLock::new(|| TinyUfo::new_compact(10, 10));
+struct CachedPass {
+    pass: Arc<SecretBytes>,
+    inserted: Instant,
+}
+
+/// Keyring passwords, keyed by the item label
+static CACHE_PASSWD: LazyLock<Mutex<HashMap<String, CachedPass>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+/// `None` means the cached passwords live until evicted
+static CACHE_TTL: Mutex<Option<Duration>> = Mutex::new(None);
 
 impl Decrypter {
+    /// How long a keyring password stays in the process wide cache.
+    ///
+    /// `None` (the default) keeps it until [`Decrypter::evict_cache`] or [`Decrypter::clear_cache`].
+    pub fn set_cache_ttl(ttl: Option<Duration>) {
+        *CACHE_TTL
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = ttl;
+    }
+
+    /// Drop the cached keyring password of `safe_storage`, return whether it was cached
+    pub fn evict_cache(safe_storage: &str) -> bool {
+        CACHE_PASSWD
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(safe_storage)
+            .is_some()
+    }
+
+    /// Drop all cached keyring passwords
+    pub fn clear_cache() {
+        CACHE_PASSWD
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    fn cache_get(safe_storage: &str) -> Option<Arc<SecretBytes>> {
+        let ttl = *CACHE_TTL
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut cache = CACHE_PASSWD
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(ttl) = ttl {
+            cache.retain(|_, v| v.inserted.elapsed() < ttl);
+        }
+        cache
+            .get(safe_storage)
+            .map(|v| Arc::clone(&v.pass))
+    }
+
+    fn cache_put(label: String, pass: Vec<u8>) {
+        CACHE_PASSWD
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(
+                label,
+                CachedPass {
+                    pass: Arc::new(SecretBytes::new(pass)),
+                    inserted: Instant::now(),
+                },
+            );
+    }
+
     /// `safe_storage` example: Brave Safe Storage
     pub async fn build<F, N>(safe_storage: &str, need: N) -> Result<Self>
     where
@@ -46,16 +126,16 @@ impl Decrypter {
     {
         let pass_v11 = Self::get_pass(safe_storage, need)
             .await
-            .unwrap_or(Self::PASSWORD_V10);
+            .unwrap_or_else(|_| Arc::new(SecretBytes::new(Self::PASSWORD_V10.to_vec())));
         Ok(Self { pass_v11 })
     }
 
-    async fn get_pass<F, N>(safe_storage: &str, need: N) -> Result<&'static [u8]>
+    async fn get_pass<F, N>(safe_storage: &str, need: N) -> Result<Arc<SecretBytes>>
     where
         N: Into<Option<F>> + Send,
         F: Fn(&str) -> bool + Send,
     {
-        if let Some(v) = CACHE_PASSWD.get(&safe_storage) {
+        if let Some(v) = Self::cache_get(safe_storage) {
             return Ok(v);
         }
 
@@ -98,65 +178,114 @@ impl Decrypter {
                     continue;
                 };
 
-                let s = s.leak();
-                CACHE_PASSWD.put(&label, s, 1);
+                Self::cache_put(label, s);
             }
             else if label == safe_storage {
                 let Ok(s) = item.get_secret().await
                 else {
                     continue;
                 };
-                CACHE_PASSWD.put(&label, s.leak(), 1);
+                Self::cache_put(label, s);
             }
         }
 
-        if let Some(v) = CACHE_PASSWD.get(&safe_storage) {
+        if let Some(v) = Self::cache_get(safe_storage) {
             return Ok(v);
         }
 
-        Ok(Self::PASSWORD_V10)
+        Ok(Arc::new(SecretBytes::new(Self::PASSWORD_V10.to_vec())))
     }
 
-    // https://source.chromium.org/chromium/chromium/src/+/main:components/os_crypt/sync/os_crypt_linux.cc;l=72
-    pub fn decrypt(&self, ciphertext: &mut [u8], which: Which) -> Result<String> {
-        let (pass, prefix_len) = if ciphertext.starts_with(Self::K_OBFUSCATION_PREFIX_V11) {
-            (self.pass_v11, Self::K_OBFUSCATION_PREFIX_V11.len())
+    /// The password and the prefix length, `None` when `ciphertext` is not encrypted
+    fn pass_of(&self, ciphertext: &[u8]) -> Option<(&[u8], usize)> {
+        if ciphertext.starts_with(Self::K_OBFUSCATION_PREFIX_V11) {
+            Some((
+                self.pass_v11.expose_secret(),
+                Self::K_OBFUSCATION_PREFIX_V11.len(),
+            ))
         }
         else if ciphertext.starts_with(Self::K_OBFUSCATION_PREFIX_V10) {
-            (Self::PASSWORD_V10, Self::K_OBFUSCATION_PREFIX_V10.len())
+            Some((Self::PASSWORD_V10, Self::K_OBFUSCATION_PREFIX_V10.len()))
         }
         else {
-            return Ok(String::from_utf8_lossy(ciphertext).to_string());
-        };
+            None
+        }
+    }
+
+    fn key(pass: &[u8]) -> Zeroizing<[u8; 16]> {
+        let mut key = Zeroizing::new([0_u8; 16]);
+        pbkdf2_hmac::<sha1::Sha1>(
+            pass,
+            Self::K_SALT,
+            Self::K_ENCRYPTION_ITERATIONS,
+            key.as_mut_slice(),
+        );
+        key
+    }
 
-        let mut key = [0_u8; 16];
+    fn cipher(pass: &[u8]) -> Aes128CbcDec {
         let iv = [b' '; Self::K_IVBLOCK_SIZE_AES128];
+        Aes128CbcDec::new(Self::key(pass).as_slice().into(), &iv.into())
+    }
+
+    // https://source.chromium.org/chromium/chromium/src/+/main:components/os_crypt/sync/os_crypt_linux.cc
+    /// Encrypt like `OSCrypt`, `v11` with the keyring password, `v10` when there is none
+    #[expect(
+        clippy::unnecessary_wraps,
+        reason = "AES-GCM encryption on Windows can fail"
+    )]
+    pub fn encrypt_raw(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let (prefix, pass) = if self.pass_v11() == Self::PASSWORD_V10 {
+            (Self::K_OBFUSCATION_PREFIX_V10, Self::PASSWORD_V10)
+        }
+        else {
+            (Self::K_OBFUSCATION_PREFIX_V11, self.pass_v11())
+        };
+        let iv = [b' '; Self::K_IVBLOCK_SIZE_AES128];
+
+        let mut res = prefix.to_vec();
+        res.extend(
+            Aes128CbcEnc::new(Self::key(pass).as_slice().into(), &iv.into())
+                .encrypt_padded_vec_mut::<block_padding::Pkcs7>(plaintext),
+        );
+        Ok(res)
+    }
 
-        pbkdf2_hmac::<sha1::Sha1>(pass, Self::K_SALT, Self::K_ENCRYPTION_ITERATIONS, &mut key);
-        let decrypter = Aes128CbcDec::new(&key.into(), &iv.into());
+    /// Decrypt without interpreting the plaintext, e.g. OAuth tokens
+    pub fn decrypt_raw(&self, ciphertext: &mut [u8]) -> Result<SecretBytes> {
+        let Some((pass, prefix_len)) = self.pass_of(ciphertext)
+        else {
+            return Ok(SecretBytes::new(ciphertext.to_vec()));
+        };
 
-        decrypter
+        let res = Self::cipher(pass)
+            .decrypt_padded_mut::<block_padding::Pkcs7>(&mut ciphertext[prefix_len..])
+            .map(|res| SecretBytes::new(res.to_vec()))
+            .context(error::UnpaddingSnafu);
+        // The plaintext was written into the caller's buffer
+        ciphertext.zeroize();
+
+        res
+    }
+
+    // https://source.chromium.org/chromium/chromium/src/+/main:components/os_crypt/sync/os_crypt_linux.cc;l=72
+    /// The plaintext as is, cookie values go through [`Self::decrypt_cookie`]
+    pub fn decrypt(&self, ciphertext: &mut [u8]) -> Result<SecretString> {
+        let Some((pass, prefix_len)) = self.pass_of(ciphertext)
+        else {
+            return Ok(String::from_utf8_lossy(ciphertext)
+                .to_string()
+                .into());
+        };
+
+        let res = Self::cipher(pass)
             .decrypt_padded_mut::<block_padding::Pkcs7>(&mut ciphertext[prefix_len..])
             .context(error::UnpaddingSnafu)
-            .map(|res| {
-                match which {
-                    Which::Cookie => res.get(32..).map_or_else(
-                        || {
-                            std::hint::cold_path();
-                            str::from_utf8(res)
-                        },
-                        |slice| {
-                            str::from_utf8(slice).or_else(|_| {
-                                std::hint::cold_path();
-                                str::from_utf8(res)
-                            })
-                        },
-                    ),
-                    Which::Login => str::from_utf8(res),
-                }
-                .map(ToOwned::to_owned) // Lazily alloc memory
-            })?
-            .context(Utf8Snafu)
+            .map(|res| str::from_utf8(res).map(SecretString::from)); // Lazily alloc memory
+        // The plaintext was written into the caller's buffer
+        ciphertext.zeroize();
+
+        res?.context(Utf8Snafu)
     }
 }
 
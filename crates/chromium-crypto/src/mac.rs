@@ -1,25 +1,27 @@
-use std::{borrow::ToOwned, convert::Into, str};
+use std::{convert::Into, str};
 
-use aes::cipher::{BlockDecryptMut, KeyIvInit, block_padding};
+use aes::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit, block_padding};
 use pbkdf2::pbkdf2_hmac;
 use snafu::ResultExt;
+use zeroize::{Zeroize, Zeroizing};
 
 use crate::{
-    Which,
     error::{self, Result, Utf8Snafu},
+    secret::{SecretBytes, SecretString},
 };
 
 // https://source.chromium.org/chromium/chromium/src/+/main:components/os_crypt/sync/os_crypt_mac.mm;l=35
 /// Key size required for 128 bit AES.
 // const K_DERIVED_KEY_SIZE_IN_BITS: u32 = 128;
 type Aes128CbcDec = cbc::Decryptor<aes::Aes128>;
+type Aes128CbcEnc = cbc::Encryptor<aes::Aes128>;
 
 #[derive(Clone)]
 #[derive(Debug)]
 #[derive(Default)]
 #[derive(PartialEq, Eq)]
 pub struct Decrypter {
-    pass_v10: Vec<u8>,
+    pass_v10: SecretBytes,
 }
 
 impl Decrypter {
@@ -39,12 +41,23 @@ impl Decrypter {
 }
 
 impl Decrypter {
+    #[cfg(feature = "key-cache")]
+    pub(crate) fn key_material(&self) -> Vec<&[u8]> {
+        vec![self.pass_v10.expose_secret()]
+    }
+
+    #[cfg(feature = "key-cache")]
+    pub(crate) fn from_key_material(keys: Vec<SecretBytes>) -> Option<Self> {
+        let [pass_v10] = <[SecretBytes; 1]>::try_from(keys).ok()?;
+        Some(Self { pass_v10 })
+    }
+
     pub async fn build(safe_storage: &str, safe_name: &str) -> Result<Self> {
         let pass_v10 = Self::get_pass(safe_storage, safe_name).await?;
         Ok(Self { pass_v10 })
     }
 
-    async fn get_pass(safe_storage: &str, safe_name: &str) -> Result<Vec<u8>> {
+    async fn get_pass(safe_storage: &str, safe_name: &str) -> Result<SecretBytes> {
         // # Safety
         //
         // Already `.await` in the function.
@@ -59,51 +72,78 @@ impl Decrypter {
                 keyring::Entry::new(safe_storage, safe_name).context(error::KeyringSnafu)?;
             entry
                 .get_secret()
+                .map(SecretBytes::new)
                 .context(error::KeyringSnafu)
         })
         .await
         .context(error::TaskSnafu)?
     }
 
-    pub fn decrypt(&self, ciphertext: &mut [u8], which: Which) -> Result<String> {
+    fn key(&self) -> Zeroizing<[u8; 16]> {
+        let mut key = Zeroizing::new([0_u8; 16]);
+        pbkdf2_hmac::<sha1::Sha1>(
+            self.pass_v10.expose_secret(),
+            Self::K_SALT,
+            Self::K_ENCRYPTION_ITERATIONS,
+            key.as_mut_slice(),
+        );
+        key
+    }
+
+    fn cipher(&self) -> Aes128CbcDec {
+        let iv = [b' '; 16];
+        Aes128CbcDec::new(self.key().as_slice().into(), &iv.into())
+    }
+
+    // https://source.chromium.org/chromium/chromium/src/+/main:components/os_crypt/sync/os_crypt_mac.mm
+    /// Encrypt like `OSCrypt`, the result starts with `v10`
+    #[expect(clippy::unnecessary_wraps, reason = "AES-GCM encryption on Windows can fail")]
+    pub fn encrypt_raw(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let iv = [b' '; 16];
+
+        let mut res = Self::K_ENCRYPTION_VERSION_PREFIX.to_vec();
+        res.extend(
+            Aes128CbcEnc::new(self.key().as_slice().into(), &iv.into())
+                .encrypt_padded_vec_mut::<block_padding::Pkcs7>(plaintext),
+        );
+        Ok(res)
+    }
+
+    /// Decrypt without interpreting the plaintext, e.g. OAuth tokens
+    pub fn decrypt_raw(&self, ciphertext: &mut [u8]) -> Result<SecretBytes> {
         if !ciphertext.starts_with(Self::K_ENCRYPTION_VERSION_PREFIX) {
-            return Ok(String::from_utf8_lossy(ciphertext).to_string());
+            return Ok(SecretBytes::new(ciphertext.to_vec()));
         }
         let prefix_len = Self::K_ENCRYPTION_VERSION_PREFIX.len();
 
-        let mut key = [0_u8; 16];
-        let iv = [b' '; 16];
+        let res = self
+            .cipher()
+            .decrypt_padded_mut::<block_padding::Pkcs7>(&mut ciphertext[prefix_len..])
+            .map(|res| SecretBytes::new(res.to_vec()))
+            .context(error::UnpaddingSnafu);
+        // The plaintext was written into the caller's buffer
+        ciphertext.zeroize();
 
-        pbkdf2_hmac::<sha1::Sha1>(
-            &self.pass_v10,
-            Self::K_SALT,
-            Self::K_ENCRYPTION_ITERATIONS,
-            &mut key,
-        );
+        res
+    }
 
-        let decrypter = Aes128CbcDec::new(&key.into(), &iv.into());
+    /// The plaintext as is, cookie values go through [`Self::decrypt_cookie`]
+    pub fn decrypt(&self, ciphertext: &mut [u8]) -> Result<SecretString> {
+        if !ciphertext.starts_with(Self::K_ENCRYPTION_VERSION_PREFIX) {
+            return Ok(String::from_utf8_lossy(ciphertext)
+                .to_string()
+                .into());
+        }
+        let prefix_len = Self::K_ENCRYPTION_VERSION_PREFIX.len();
 
-        decrypter
+        let res = self
+            .cipher()
             .decrypt_padded_mut::<block_padding::Pkcs7>(&mut ciphertext[prefix_len..])
             .context(error::UnpaddingSnafu)
-            .map(|res| {
-                match which {
-                    Which::Cookie => res.get(32..).map_or_else(
-                        || {
-                            std::hint::cold_path();
-                            str::from_utf8(res)
-                        },
-                        |slice| {
-                            str::from_utf8(slice).or_else(|_| {
-                                std::hint::cold_path();
-                                str::from_utf8(res)
-                            })
-                        },
-                    ),
-                    Which::Login => str::from_utf8(res),
-                }
-                .map(ToOwned::to_owned) // Lazily alloc memory
-            })?
-            .context(Utf8Snafu)
+            .map(|res| str::from_utf8(res).map(SecretString::from)); // Lazily alloc memory
+        // The plaintext was written into the caller's buffer
+        ciphertext.zeroize();
+
+        res?.context(Utf8Snafu)
     }
 }
@@ -0,0 +1,176 @@
+//! Wrappers for key material and decrypted plaintext.
+//!
+//! Both types zeroize their buffer on drop and never print their content with [`Debug`].
+
+use std::fmt::Debug;
+
+use zeroize::{Zeroize, Zeroizing};
+
+/// Key material, e.g. a keyring password or a Chromium master key.
+///
+/// With the `mlock` feature the buffer is locked into memory so it is not written to swap.
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+pub struct SecretBytes(Zeroizing<Vec<u8>>);
+
+impl SecretBytes {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        let secret = Self(Zeroizing::new(bytes));
+        #[cfg(feature = "mlock")]
+        secret.lock();
+        secret
+    }
+
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+
+    #[cfg(feature = "mlock")]
+    fn lock(&self) {
+        if self.0.is_empty() {
+            return;
+        }
+        #[cfg(unix)]
+        let _res = unsafe { libc::mlock(self.0.as_ptr().cast(), self.0.len()) };
+        #[cfg(windows)]
+        let _res = unsafe {
+            windows::Win32::System::Memory::VirtualLock(self.0.as_ptr().cast(), self.0.len())
+        };
+        #[cfg(feature = "tracing")]
+        {
+            #[cfg(unix)]
+            let failed = _res != 0;
+            #[cfg(windows)]
+            let failed = _res.is_err();
+            if failed {
+                tracing::debug!("lock secret memory failed");
+            }
+        }
+    }
+
+    #[cfg(feature = "mlock")]
+    fn unlock(&self) {
+        if self.0.is_empty() {
+            return;
+        }
+        #[cfg(unix)]
+        unsafe {
+            libc::munlock(self.0.as_ptr().cast(), self.0.len())
+        };
+        #[cfg(windows)]
+        unsafe {
+            _ = windows::Win32::System::Memory::VirtualUnlock(self.0.as_ptr().cast(), self.0.len())
+        };
+    }
+}
+
+impl Clone for SecretBytes {
+    fn clone(&self) -> Self {
+        Self::new(self.0.to_vec())
+    }
+}
+
+#[cfg(feature = "mlock")]
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+        self.unlock();
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Debug for SecretBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretBytes([REDACTED])")
+    }
+}
+
+/// Decrypted plaintext, e.g. a password or a cookie value.
+///
+/// Use [`SecretString::expose_secret`] to read it.
+/// With the `serde` feature it is serialized as a plain string, so exports still contain the value.
+#[derive(Clone)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub const fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Take the plaintext out, the caller is responsible for it now
+    pub fn into_unprotected(mut self) -> String {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretString([REDACTED])")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SecretString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SecretString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_redacted() {
+        let s = SecretString::from("hunter2");
+        assert_eq!(format!("{s:?}"), "SecretString([REDACTED])");
+        assert_eq!(s.expose_secret(), "hunter2");
+
+        let b = SecretBytes::new(b"peanuts".to_vec());
+        assert_eq!(format!("{b:?}"), "SecretBytes([REDACTED])");
+        assert!(!format!("{:?}", Some(b)).contains("peanuts"));
+    }
+}
@@ -3,7 +3,10 @@ pub mod local_state;
 
 use std::{ffi::c_void, fmt::Display, path::Path, ptr, slice, str};
 
-use aes_gcm::{Aes256Gcm, KeyInit, aead::Aead};
+use aes_gcm::{
+    Aes256Gcm, KeyInit,
+    aead::{Aead, AeadCore, OsRng},
+};
 use base64::{Engine, prelude::BASE64_STANDARD};
 use chacha20poly1305::ChaCha20Poly1305;
 use local_state::LocalState;
@@ -25,9 +28,11 @@ use winnow::{
     token::take,
 };
 
+use zeroize::Zeroizing;
+
 use crate::{
-    Which,
     error::{self, Result, Utf8Snafu},
+    secret::{SecretBytes, SecretString},
     win::impersonate::ImpersonateGuard,
 };
 
@@ -36,8 +41,8 @@ use crate::{
 #[derive(Default)]
 #[derive(PartialEq, Eq)]
 pub struct Decrypter {
-    pass_v20: Option<Vec<u8>>,
-    pass_v10: Vec<u8>,
+    pass_v20: Option<SecretBytes>,
+    pass_v10: SecretBytes,
 }
 
 impl Decrypter {
@@ -66,14 +71,37 @@ impl Decrypter {
         let (pass_v20, pass_v10) = Self::get_pass(key_path).await?;
         Ok(Self { pass_v20, pass_v10 })
     }
+
+    /// v10 key first, then the optional v20 key
+    #[cfg(feature = "key-cache")]
+    pub(crate) fn key_material(&self) -> Vec<&[u8]> {
+        let mut keys = vec![self.pass_v10.expose_secret()];
+        keys.extend(
+            self.pass_v20
+                .as_ref()
+                .map(SecretBytes::expose_secret),
+        );
+        keys
+    }
+
+    #[cfg(feature = "key-cache")]
+    pub(crate) fn from_key_material(keys: Vec<SecretBytes>) -> Option<Self> {
+        let mut keys = keys.into_iter();
+        let pass_v10 = keys.next()?;
+        let pass_v20 = keys.next();
+        if keys.next().is_some() {
+            return None;
+        }
+        Some(Self { pass_v20, pass_v10 })
+    }
     async fn get_pass<A: AsRef<Path> + Send + Sync>(
         key_path: A,
-    ) -> Result<(Option<Vec<u8>>, Vec<u8>)> {
+    ) -> Result<(Option<SecretBytes>, SecretBytes)> {
         let string_str = fs::read_to_string(&key_path)
             .await
             .with_context(|_| error::IoSnafu { path: key_path.as_ref().to_owned() })?;
 
-        let key = spawn_blocking(move || -> Result<(Option<Vec<u8>>, Vec<u8>)> {
+        let key = spawn_blocking(move || -> Result<(Option<SecretBytes>, SecretBytes)> {
             let local_state: LocalState =
                 serde_json::from_str(&string_str).context(error::SerdeSnafu)?;
             let v10 = Self::pass_v10(&local_state.os_crypt.encrypted_key)?;
@@ -94,15 +122,15 @@ impl Decrypter {
         Ok(key)
     }
 
-    fn pass_v10(key_v10: &str) -> Result<Vec<u8>> {
+    fn pass_v10(key_v10: &str) -> Result<SecretBytes> {
         let encrypted_key = BASE64_STANDARD
             .decode(key_v10)
             .context(error::Base64Snafu)?;
         let mut key = encrypted_key[Self::K_DPAPIKEY_PREFIX.len()..].to_vec();
-        decrypt_with_dpapi(&mut key)
+        decrypt_with_dpapi(&mut key).map(SecretBytes::new)
     }
 
-    fn pass_v20(encrypted_key_v20: String) -> Result<Vec<u8>> {
+    fn pass_v20(encrypted_key_v20: String) -> Result<SecretBytes> {
         let mut encrypted_key_v20 = BASE64_STANDARD
             .decode(encrypted_key_v20)
             .context(error::Base64Snafu)?;
@@ -114,60 +142,108 @@ impl Decrypter {
         let key = &mut encrypted_key_v20[Self::K_CRYPT_APP_BOUND_KEY_PREFIX.len()..];
         let (mut key, pid, sys_handle) = {
             let (guard, pid) = ImpersonateGuard::start(None, None)?;
-            let key = decrypt_with_dpapi(key)?;
+            let key = Zeroizing::new(decrypt_with_dpapi(key)?);
             (key, pid, guard.stop_impe_ret_sys_handle()?)
         };
-        let key_blob = decrypt_with_dpapi(&mut key)?;
+        let key_blob = Zeroizing::new(decrypt_with_dpapi(&mut key)?);
         let key_data = KeyData::parse(&mut key_blob.as_slice())
             .map_err(|e| error::ContextSnafu { render: e }.build())?;
-        derive_v20_master_key(key_data, Some(pid), Some(sys_handle))
+        derive_v20_master_key(key_data, Some(pid), Some(sys_handle)).map(SecretBytes::new)
+    }
+
+    // https://source.chromium.org/chromium/chromium/src/+/main:components/os_crypt/sync/os_crypt_win.cc
+    /// Encrypt like `OSCrypt` with the `v10` key, prefix and nonce then the ciphertext.
+    ///
+    /// The app bound `v20` key is never used, Chromium still reads `v10` values.
+    pub fn encrypt_raw(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = Aes256Gcm::new(self.pass_v10.expose_secret().into())
+            .encrypt(&nonce, plaintext)
+            .context(error::AesGcmSnafu)?;
+
+        let mut res = Self::K_ENCRYPTION_VERSION_PREFIX.to_vec();
+        res.extend_from_slice(&nonce);
+        res.extend(ciphertext);
+        Ok(res)
+    }
+
+    /// Decrypt without interpreting the plaintext, e.g. OAuth tokens
+    pub fn decrypt_raw(&self, ciphertext: &mut [u8]) -> Result<SecretBytes> {
+        let (pass, prefix_len) = if let Some(pass_v20) = &self.pass_v20
+            && ciphertext.starts_with(Self::K_APP_BOUND_DATA_PREFIX)
+        {
+            (pass_v20.expose_secret(), Self::K_APP_BOUND_DATA_PREFIX.len())
+        }
+        else if ciphertext.starts_with(Self::K_ENCRYPTION_VERSION_PREFIX) {
+            (
+                self.pass_v10.expose_secret(),
+                Self::K_ENCRYPTION_VERSION_PREFIX.len(),
+            )
+        }
+        else {
+            return decrypt_with_dpapi(ciphertext).map(SecretBytes::new);
+        };
+        let nonce_len = Self::K_NONCE_LENGTH;
+
+        let (nonce, raw_ciphertext) = ciphertext
+            .get(prefix_len..)
+            .and_then(|v| v.split_at_checked(nonce_len))
+            .ok_or_else(|| error::TooShortSnafu.build())?;
+
+        Aes256Gcm::new(pass.into())
+            .decrypt(nonce.into(), raw_ciphertext)
+            .map(SecretBytes::new)
+            .context(error::AesGcmSnafu)
     }
 
     // https://source.chromium.org/chromium/chromium/src/+/main:components/os_crypt/sync/os_crypt_win.cc;l=213
-    pub fn decrypt(&self, ciphertext: &mut [u8], which: Which) -> Result<String> {
+    /// The plaintext as is, cookie values go through [`Self::decrypt_cookie`]
+    pub fn decrypt(&self, ciphertext: &mut [u8]) -> Result<SecretString> {
         let (pass, prefix_len) = if let Some(pass_v20) = &self.pass_v20
             && ciphertext.starts_with(Self::K_APP_BOUND_DATA_PREFIX)
         {
-            (pass_v20.as_slice(), Self::K_APP_BOUND_DATA_PREFIX.len())
+            (pass_v20.expose_secret(), Self::K_APP_BOUND_DATA_PREFIX.len())
         }
         else if ciphertext.starts_with(Self::K_ENCRYPTION_VERSION_PREFIX) {
             (
-                self.pass_v10.as_slice(),
+                self.pass_v10.expose_secret(),
                 Self::K_ENCRYPTION_VERSION_PREFIX.len(),
             )
         }
         else {
-            return Ok(String::from_utf8_lossy(&decrypt_with_dpapi(ciphertext)?).to_string());
+            let plain = Zeroizing::new(decrypt_with_dpapi(ciphertext)?);
+            return Ok(String::from_utf8_lossy(&plain)
+                .to_string()
+                .into());
         };
         let nonce_len = Self::K_NONCE_LENGTH;
 
-        let nonce = &ciphertext[prefix_len..][..nonce_len];
-        let raw_ciphertext = &ciphertext[nonce_len + prefix_len..];
+        let (nonce, raw_ciphertext) = ciphertext
+            .get(prefix_len..)
+            .and_then(|v| v.split_at_checked(nonce_len))
+            .ok_or_else(|| error::TooShortSnafu.build())?;
 
         let cipher = Aes256Gcm::new(pass.into());
 
         cipher
             .decrypt(nonce.into(), raw_ciphertext)
             .context(error::AesGcmSnafu)
-            .map(|res| match which {
-                Which::Cookie => match res.get(32..) {
-                    Some(slice) => str::from_utf8(slice)
-                        .map(ToOwned::to_owned)
-                        .or_else(|_| {
-                            std::hint::cold_path();
-                            String::from_utf8(res).map_err(|e| e.utf8_error())
-                        }),
-                    None => {
-                        std::hint::cold_path();
-                        String::from_utf8(res).map_err(|e| e.utf8_error())
-                    },
-                },
-                Which::Login => String::from_utf8(res).map_err(|e| e.utf8_error()),
-            })?
+            .map(secret_from_utf8)?
             .context(Utf8Snafu)
     }
 }
 
+/// Avoid leaving a copy of the plaintext behind when it is not valid UTF-8
+fn secret_from_utf8(bytes: Vec<u8>) -> std::result::Result<SecretString, str::Utf8Error> {
+    String::from_utf8(bytes)
+        .map(SecretString::from)
+        .map_err(|e| {
+            let err = e.utf8_error();
+            drop(Zeroizing::new(e.into_bytes()));
+            err
+        })
+}
+
 #[derive(Clone, Copy)]
 enum KeyData<'k> {
     One {
@@ -318,7 +394,7 @@ fn derive_v20_master_key(
             let xor_key = b"\xCC\xF8\xA1\xCE\xC5\x66\x05\xB8\x51\x75\x52\xBA\x1A\x2D\x06\x1C\x03\xA2\x9E\x90\x27\x4F\xB2\xFC\xF5\x9B\xA4\xB7\x5C\x39\x23\x90";
             let mut plain_aes_key = {
                 let (guard, _pid) = ImpersonateGuard::start(pid, sys_handle)?;
-                let key = decrypt_with_cng(enctypted_aes_key)?;
+                let key = Zeroizing::new(decrypt_with_cng(enctypted_aes_key)?);
                 guard.close_sys_handle()?;
                 key
             };
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::CACHE_PATH;
 use crate::chromium::{
@@ -21,6 +21,28 @@ pub trait ChromiumPath {
     const LOGIN_DATA: &str = "Default/Login Data";
     /// Another login data (sqlite3)
     const LOGIN_DATA_FOR_ACCOUNT: &str = "Default/Login Data For Account";
+    /// Suffix for browsing history path (sqlite3 database)
+    const HISTORY: &str = "Default/History";
+    /// Suffix for bookmarks path (json)
+    const BOOKMARKS: &str = "Default/Bookmarks";
+    /// Suffix for autofill and payment data path (sqlite3 database)
+    const WEB_DATA: &str = "Default/Web Data";
+    /// Suffix for `localStorage` path (LevelDB directory)
+    const LOCAL_STORAGE: &str = "Default/Local Storage/leveldb";
+    /// Suffix for IndexedDB path, a `*.indexeddb.leveldb` directory per origin
+    const INDEXED_DB: &str = "Default/IndexedDB";
+    /// Suffix for the session directory, `Session_*` and `Tabs_*` SNSS files
+    const SESSIONS: &str = "Default/Sessions";
+    /// Suffix for the HTTP disk cache directory, simple cache or blockfile
+    const DISK_CACHE: &str = "Default/Cache/Cache_Data";
+    /// Suffix for profile preferences path (json)
+    const PREFERENCES: &str = "Default/Preferences";
+    /// Suffix for preferences protected by MACs, holding extension settings on Windows and macOS (json)
+    const SECURE_PREFERENCES: &str = "Default/Secure Preferences";
+    /// Suffix for installed extensions path, `<id>/<version>/manifest.json`
+    const EXTENSIONS: &str = "Default/Extensions";
+    /// Suffix for browser wide state path (json)
+    const LOCAL_STATE: &str = "Local State";
     #[cfg(target_os = "windows")]
     /// Suffix for decryption key path (json)
     const KEY: &str = "Local State";
@@ -82,6 +104,125 @@ pub trait ChromiumPath {
 
         cache.into()
     }
+
+    /// History file (sqlite3 database)
+    fn history(mut base: PathBuf) -> PathBuf {
+        push_exact!(base, Self::HISTORY);
+        base
+    }
+    /// Copy the History file to a location to avoid conflicts with the browser over access to it.
+    fn history_temp() -> Option<PathBuf> {
+        push_temp!(cache, Self::HISTORY);
+
+        cache.into()
+    }
+
+    /// Bookmarks file (json), it is replaced atomically so can be read in place
+    fn bookmarks(mut base: PathBuf) -> PathBuf {
+        push_exact!(base, Self::BOOKMARKS);
+        base
+    }
+
+    /// Web Data file (sqlite3 database)
+    fn web_data(mut base: PathBuf) -> PathBuf {
+        push_exact!(base, Self::WEB_DATA);
+        base
+    }
+    /// Copy the Web Data file to a location to avoid conflicts with the browser over access to it.
+    fn web_data_temp() -> Option<PathBuf> {
+        push_temp!(cache, Self::WEB_DATA);
+
+        cache.into()
+    }
+
+    /// Local Storage directory (LevelDB)
+    fn local_storage(mut base: PathBuf) -> PathBuf {
+        push_exact!(base, Self::LOCAL_STORAGE);
+        base
+    }
+    /// Copy the Local Storage directory to a location to avoid conflicts with the browser over access to it.
+    fn local_storage_temp() -> Option<PathBuf> {
+        push_temp!(cache, Self::LOCAL_STORAGE);
+
+        cache.into()
+    }
+
+    /// IndexedDB directory, a LevelDB per origin
+    fn indexed_db(mut base: PathBuf) -> PathBuf {
+        push_exact!(base, Self::INDEXED_DB);
+        base
+    }
+    /// Copy the IndexedDB directory to a location to avoid conflicts with the browser over access to it.
+    fn indexed_db_temp() -> Option<PathBuf> {
+        push_temp!(cache, Self::INDEXED_DB);
+
+        cache.into()
+    }
+
+    /// Sessions directory (SNSS files)
+    fn sessions(mut base: PathBuf) -> PathBuf {
+        push_exact!(base, Self::SESSIONS);
+        base
+    }
+
+    /// HTTP disk cache directory. Like `GetUserCacheDirectory`, when the base is in the config dir
+    /// the cache is in the same place of the cache dir, e.g. `~/.config/google-chrome` to `~/.cache/google-chrome`
+    fn disk_cache(base: PathBuf) -> PathBuf {
+        let mut base = dirs::config_dir()
+            .zip(dirs::cache_dir())
+            .filter(|(config, cache)| config != cache)
+            .and_then(|(config, cache)| Some(cache.join(base.strip_prefix(config).ok()?)))
+            .unwrap_or(base);
+        push_exact!(base, Self::DISK_CACHE);
+        base
+    }
+    /// Copy the disk cache directory to a location to avoid conflicts with the browser over access to it.
+    fn disk_cache_temp() -> Option<PathBuf> {
+        push_temp!(cache, Self::DISK_CACHE);
+
+        cache.into()
+    }
+
+    /// Preferences file (json)
+    fn preferences(mut base: PathBuf) -> PathBuf {
+        push_exact!(base, Self::PREFERENCES);
+        base
+    }
+
+    /// Secure Preferences file (json)
+    fn secure_preferences(mut base: PathBuf) -> PathBuf {
+        push_exact!(base, Self::SECURE_PREFERENCES);
+        base
+    }
+
+    /// Extensions directory
+    fn extensions(mut base: PathBuf) -> PathBuf {
+        push_exact!(base, Self::EXTENSIONS);
+        base
+    }
+
+    /// Local State file (json)
+    fn local_state(mut base: PathBuf) -> PathBuf {
+        push_exact!(base, Self::LOCAL_STATE);
+        base
+    }
+
+    /// Exists while the browser has the user data dir open, `SingletonLock` or `lockfile` on Windows
+    fn profile_lock(mut base: PathBuf) -> PathBuf {
+        #[cfg(not(target_os = "windows"))]
+        base.push("SingletonLock");
+        #[cfg(target_os = "windows")]
+        base.push("lockfile");
+        base
+    }
+
+    /// The profile directory name, the key of `profile.info_cache` in `Local State`
+    fn profile_name() -> Option<&'static str> {
+        Path::new(Self::PREFERENCES)
+            .parent()?
+            .to_str()
+            .filter(|v| !v.is_empty())
+    }
 }
 
 /// Register a Chromium based browser info
@@ -90,7 +231,9 @@ pub trait ChromiumPath {
 /// - `platform`
 /// - `browser`: Generate a struct
 /// - `base: <path>`: A browser all data location relative to home dir.
-/// - `cookies: <path>`, `login_data: <path>`, `login_data_fa: <path>`: Relative to base dir. (optional)
+/// - `cookies: <path>`, `login_data: <path>`, `login_data_fa: <path>`, `history: <path>`, `bookmarks: <path>`,
+///   `web_data: <path>`, `local_storage: <path>`, `indexed_db: <path>`, `sessions: <path>`, `disk_cache: <path>`, `preferences: <path>`,
+///   `secure_preferences: <path>`, `extensions: <path>`: Relative to base dir. (optional)
 /// - `key: <path>`: Relative to profile dir. Require on windows.
 /// - `safe_name: <name>`: Require on linux and macos
 ///
@@ -129,6 +272,16 @@ macro_rules! chromium {
         $(, cookies: $cookies:literal)?
         $(, login_data: $login_data:literal)?
         $(, login_data_fa: $login_data_fa:literal)?
+        $(, history: $history:literal)?
+        $(, bookmarks: $bookmarks:literal)?
+        $(, web_data: $web_data:literal)?
+        $(, local_storage: $local_storage:literal)?
+        $(, indexed_db: $indexed_db:literal)?
+        $(, sessions: $sessions:literal)?
+        $(, disk_cache: $disk_cache:literal)?
+        $(, preferences: $preferences:literal)?
+        $(, secure_preferences: $secure_preferences:literal)?
+        $(, extensions: $extensions:literal)?
         $(, key: $key:literal)?
         $(, safe_name: $safe_name:literal)?
     ) => {
@@ -147,6 +300,16 @@ macro_rules! chromium {
             $(const COOKIES: &str = $cookies;)?
             $(const LOGIN_DATA: &str = $login_data;)?
             $(const LOGIN_DATA_FOR_ACCOUNT: &str = $login_data_fa;)?
+            $(const HISTORY: &str = $history;)?
+            $(const BOOKMARKS: &str = $bookmarks;)?
+            $(const WEB_DATA: &str = $web_data;)?
+            $(const LOCAL_STORAGE: &str = $local_storage;)?
+            $(const INDEXED_DB: &str = $indexed_db;)?
+            $(const SESSIONS: &str = $sessions;)?
+            $(const DISK_CACHE: &str = $disk_cache;)?
+            $(const PREFERENCES: &str = $preferences;)?
+            $(const SECURE_PREFERENCES: &str = $secure_preferences;)?
+            $(const EXTENSIONS: &str = $extensions;)?
             $(const KEY: &str = $key;)?
             $(
                 const SAFE_STORAGE: &str = concat!($safe_name, " Safe Storage");
@@ -192,7 +355,7 @@ chromium!("macos", Chromium, base: "Library/Application Support/Chromium"
 chromium!("macos", CocCoc  , base: "Library/Application Support/CocCoc/Browser"             , safe_name: "CocCoc"        );
 chromium!("macos", Edge    , base: "Library/Application Support/Microsoft Edge"             , safe_name: "Microsoft Edge");
 chromium!("macos", Opera   , base: "Library/Application Support/com.operasoftware.Opera"    , safe_name: "Opera"         );
-chromium!("macos", OperaGX , base: "Library/Application Support/com.operasoftware.OperaGX"  , cookies: "Cookies", login_data: "Login Data", safe_name: "Opera");
+chromium!("macos", OperaGX , base: "Library/Application Support/com.operasoftware.OperaGX"  , cookies: "Cookies", login_data: "Login Data", history: "History", bookmarks: "Bookmarks", web_data: "Web Data", local_storage: "Local Storage/leveldb", indexed_db: "IndexedDB", sessions: "Sessions", disk_cache: "Cache/Cache_Data", preferences: "Preferences", secure_preferences: "Secure Preferences", extensions: "Extensions", safe_name: "Opera");
 chromium!("macos", Vivaldi , base: "Library/Application Support/Vivaldi"                    , safe_name: "Vivaldi"       );
 chromium!("macos", Yandex  , base: "Library/Application Support/Yandex/YandexBrowser"       , login_data: "Default/Ya Passman Data", login_data_fa: "Default/Ya Passman Data", safe_name: "Yandex");
 
@@ -203,7 +366,7 @@ chromium!("windows", Chromium, base: r"AppData\Local\Chromium\User Data"
 chromium!("windows", CocCoc  , base: r"AppData\Local\CocCoc\Browser\User Data"             );
 chromium!("windows", Edge    , base: r"AppData\Local\Microsoft\Edge\User Data"             );
 chromium!("windows", Opera   , base: r"AppData\Roaming\Opera Software\Opera Stable"        );
-chromium!("windows", OperaGX , base: r"AppData\Roaming\Opera Software\Opera GX Stable"     , cookies: r"Network\Cookies", login_data: r"Login Data", login_data_fa: r"Login Data For Account");
+chromium!("windows", OperaGX , base: r"AppData\Roaming\Opera Software\Opera GX Stable"     , cookies: r"Network\Cookies", login_data: r"Login Data", login_data_fa: r"Login Data For Account", history: "History", bookmarks: "Bookmarks", web_data: "Web Data", local_storage: r"Local Storage\leveldb", indexed_db: "IndexedDB", sessions: "Sessions", disk_cache: r"Cache\Cache_Data", preferences: "Preferences", secure_preferences: "Secure Preferences", extensions: "Extensions");
 chromium!("windows", Vivaldi , base: r"AppData\Local\Vivaldi\User Data"                    );
 chromium!("windows", Yandex  , base: r"AppData\Local\Yandex\YandexBrowser\User Data"       , login_data: r"Default\Ya Passman Data");
 
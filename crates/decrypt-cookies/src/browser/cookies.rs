@@ -28,14 +28,175 @@ impl Display for LeetCodeCookies {
     }
 }
 
+/// The partition of a CHIPS cookie, the top level site it was set under.
+///
+/// <https://developer.mozilla.org/en-US/docs/Web/Privacy/Guides/Privacy_sandbox/Partitioned_cookies>
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ffi", repr(C))]
+pub struct PartitionKey {
+    /// Like `https://example.com`
+    pub top_level_site: String,
+    /// Set in a frame with a cross-site ancestor
+    pub cross_site_ancestor: bool,
+}
+
+impl PartitionKey {
+    pub fn new<S: Into<String>>(top_level_site: S) -> Self {
+        Self {
+            top_level_site: top_level_site.into(),
+            cross_site_ancestor: false,
+        }
+    }
+
+    /// From `top_frame_site_key` and `has_cross_site_ancestor` of Chromium `Cookies`,
+    /// `None` when `top_frame_site_key` is empty
+    pub fn from_chromium(
+        top_frame_site_key: &str,
+        has_cross_site_ancestor: Option<bool>,
+    ) -> Option<Self> {
+        if top_frame_site_key.is_empty() {
+            return None;
+        }
+        Some(Self {
+            top_level_site: top_frame_site_key.to_owned(),
+            cross_site_ancestor: has_cross_site_ancestor.unwrap_or_default(),
+        })
+    }
+
+    /// From the `partitionKey` of Firefox `originAttributes`, like `^partitionKey=%28https%2Cexample.com%29`.
+    ///
+    /// The key is `(scheme,host[,port][,f])`, `f` for a cross-site ancestor
+    pub fn from_origin_attributes(origin_attributes: &str) -> Option<Self> {
+        let value = origin_attributes
+            .trim_start_matches('^')
+            .split('&')
+            .find_map(|v| v.strip_prefix("partitionKey="))?;
+        let value = percent_decode(value);
+        if value.is_empty() {
+            return None;
+        }
+        let Some(inner) = value
+            .strip_prefix('(')
+            .and_then(|v| v.strip_suffix(')'))
+        else {
+            return Some(Self::new(format!("https://{value}")));
+        };
+
+        let mut parts = inner.split(',');
+        let scheme = parts.next()?;
+        let host = parts.next()?;
+        let mut top_level_site = format!("{scheme}://{host}");
+        let mut cross_site_ancestor = false;
+        for part in parts {
+            if part == "f" {
+                cross_site_ancestor = true;
+            }
+            else {
+                top_level_site.push(':');
+                top_level_site.push_str(part);
+            }
+        }
+        Some(Self { top_level_site, cross_site_ancestor })
+    }
+
+    /// Whether a page of `top_level_site`, a site or a url, is in this partition:
+    /// the same scheme and the same host or a subdomain of it, ports are ignored
+    pub fn matches(&self, top_level_site: &str) -> bool {
+        let (scheme, host) = split_site(&self.top_level_site);
+        let (other_scheme, other_host) = split_site(top_level_site);
+        scheme.eq_ignore_ascii_case(other_scheme)
+            && (other_host.eq_ignore_ascii_case(host)
+                || other_host
+                    .to_ascii_lowercase()
+                    .ends_with(&format!(".{}", host.to_ascii_lowercase())))
+    }
+}
+
+impl Display for PartitionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.top_level_site.fmt(f)
+    }
+}
+
+/// The scheme and the host of a site or url, `https` when there is no scheme
+fn split_site(site: &str) -> (&str, &str) {
+    let (scheme, rest) = site
+        .split_once("://")
+        .unwrap_or(("https", site));
+    let authority = rest
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, v)| v);
+    let host = if host.starts_with('[') {
+        host.split_inclusive(']')
+            .next()
+            .unwrap_or(host)
+    }
+    else {
+        host.split(':')
+            .next()
+            .unwrap_or(host)
+    };
+    (scheme, host.trim_end_matches('.'))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = value
+                .get(i + 1..i + 3)
+                .and_then(|v| u8::from_str_radix(v, 16).ok())
+        {
+            out.push(byte);
+            i += 3;
+        }
+        else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Add the cookies sent under `top_level_site` to `jar`, see [`CookiesInfo::in_partition`].
+///
+/// The jar doesn't know about partitions, so it should only serve requests under that site
+#[cfg(feature = "reqwest")]
+pub fn jar_extend<'a, C, I>(jar: &reqwest::cookie::Jar, cks: I, top_level_site: Option<&str>)
+where
+    C: CookiesInfo + 'a,
+    I: IntoIterator<Item = &'a C>,
+{
+    for cookie in cks
+        .into_iter()
+        .filter(|v| v.in_partition(top_level_site))
+    {
+        let set_cookie = cookie.set_cookie_header();
+        if let Ok(url) = reqwest::Url::parse(&cookie.url()) {
+            jar.add_cookie_str(&set_cookie, &url);
+        }
+    }
+}
+
 pub trait CookiesInfo {
     fn csv_header<D: Display>(sep: D) -> String {
-        format!("domain{sep}name{sep}path{sep}value{sep}creation{sep}expires{sep}is_secure{sep}is_http_only")
+        format!(
+            "domain{sep}name{sep}path{sep}value{sep}creation{sep}expires{sep}is_secure{sep}is_http_only{sep}same_site{sep}partition_key"
+        )
     }
 
     fn to_csv<D: Display>(&self, sep: D) -> String {
         format!(
-            "{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}",
+            "{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}",
             self.domain(),
             self.name(),
             self.path(),
@@ -44,9 +205,24 @@ pub trait CookiesInfo {
             self.expires().unwrap_or_default(),
             self.is_secure(),
             self.is_http_only(),
+            self.same_site(),
+            self.partition_key()
+                .map(|v| v.top_level_site.as_str())
+                .unwrap_or_default(),
         )
     }
 
+    /// Whether the cookie is sent to requests under the top level site `top_level_site`:
+    /// unpartitioned cookies always are, partitioned ones only under their own site,
+    /// so `None` leaves out every partitioned cookie
+    fn in_partition(&self, top_level_site: Option<&str>) -> bool {
+        match (self.partition_key(), top_level_site) {
+            (None, _) => true,
+            (Some(key), Some(site)) => key.matches(site),
+            (Some(_), None) => false,
+        }
+    }
+
     /// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Set-Cookie>
     fn set_cookie_header(&self) -> String {
         let mut properties = vec![
@@ -65,7 +241,15 @@ pub trait CookiesInfo {
         if self.is_http_only() {
             properties.push("HttpOnly".to_owned());
         }
-        properties.push(format!("SameSite={}", self.same_site()));
+        if matches!(
+            self.same_site(),
+            SameSite::None | SameSite::Lax | SameSite::Strict
+        ) {
+            properties.push(format!("SameSite={}", self.same_site()));
+        }
+        if self.partition_key().is_some() {
+            properties.push("Partitioned".to_owned());
+        }
 
         properties.join("; ")
     }
@@ -84,6 +268,8 @@ pub trait CookiesInfo {
     fn same_site(&self) -> SameSite;
     fn creation(&self) -> Option<DateTime<Utc>>;
     fn expires(&self) -> Option<DateTime<Utc>>;
+    /// The CHIPS partition, `None` for an unpartitioned cookie
+    fn partition_key(&self) -> Option<&PartitionKey>;
 }
 
 #[derive(Clone, Copy)]
@@ -92,19 +278,52 @@ pub trait CookiesInfo {
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SameSite {
+    /// The site didn't send `SameSite`, browsers treat it as `Lax`
+    Unspecified,
     #[default]
-    None = 0,
-    Lax = 1,
-    Strict = 2,
+    None,
+    Lax,
+    Strict,
+    Other(i32),
+}
+
+impl SameSite {
+    /// `nsICookie::SAMESITE_UNSET` of Firefox
+    const FIREFOX_UNSET: i32 = 256;
+
+    /// From `sameSite`/`rawSameSite` of Firefox `moz_cookies`
+    pub const fn from_firefox(value: i32) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Lax,
+            2 => Self::Strict,
+            Self::FIREFOX_UNSET => Self::Unspecified,
+            v => Self::Other(v),
+        }
+    }
 }
 
+/// From `samesite` of Chromium `Cookies`, `CookieSameSite`
 impl From<i32> for SameSite {
     fn from(value: i32) -> Self {
-        #[expect(clippy::wildcard_in_or_patterns, reason = "this is more clear")]
         match value {
+            -1 => Self::Unspecified,
+            0 => Self::None,
             1 => Self::Lax,
             2 => Self::Strict,
-            0 | _ => Self::None,
+            v => Self::Other(v),
+        }
+    }
+}
+
+impl From<SameSite> for i32 {
+    fn from(value: SameSite) -> Self {
+        match value {
+            SameSite::Unspecified => -1,
+            SameSite::None => 0,
+            SameSite::Lax => 1,
+            SameSite::Strict => 2,
+            SameSite::Other(v) => v,
         }
     }
 }
@@ -120,19 +339,69 @@ impl From<binary_cookies::cookie::SameSite> for SameSite {
     }
 }
 
-impl From<Option<i32>> for SameSite {
-    fn from(value: Option<i32>) -> Self {
-        value.unwrap_or_default().into()
-    }
-}
-
 impl Display for SameSite {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Unspecified => "Unspecified",
             Self::None => "None",
             Self::Lax => "Lax",
             Self::Strict => "Strict",
+            Self::Other(v) => return v.fmt(f),
         }
         .fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partition_key() {
+        assert_eq!(PartitionKey::from_chromium("", Some(true)), None);
+        let key = PartitionKey::from_chromium("https://example.com", Some(true)).unwrap();
+        assert!(key.cross_site_ancestor);
+        assert!(key.matches("https://example.com"));
+        assert!(key.matches("https://www.example.com/a?b"));
+        assert!(!key.matches("http://example.com"));
+        assert!(!key.matches("https://notexample.com"));
+
+        assert_eq!(PartitionKey::from_origin_attributes(""), None);
+        assert_eq!(
+            PartitionKey::from_origin_attributes("^userContextId=1"),
+            None
+        );
+        assert_eq!(
+            PartitionKey::from_origin_attributes(
+                "^userContextId=1&partitionKey=%28https%2Cexample.com%29"
+            ),
+            Some(PartitionKey::new("https://example.com"))
+        );
+        assert_eq!(
+            PartitionKey::from_origin_attributes("^partitionKey=%28http%2Clocalhost%2C8080%2Cf%29"),
+            Some(PartitionKey {
+                top_level_site: "http://localhost:8080".to_owned(),
+                cross_site_ancestor: true,
+            })
+        );
+        assert_eq!(
+            PartitionKey::from_origin_attributes("^partitionKey=example.com"),
+            Some(PartitionKey::new("https://example.com"))
+        );
+        assert!(PartitionKey::new("http://localhost:8080").matches("http://localhost:3000/"));
+    }
+
+    #[test]
+    fn same_site() {
+        assert_eq!(SameSite::from(-1), SameSite::Unspecified);
+        assert_eq!(SameSite::from(2), SameSite::Strict);
+        assert_eq!(SameSite::from_firefox(0), SameSite::None);
+        assert_eq!(SameSite::from_firefox(256), SameSite::Unspecified);
+        assert_eq!(SameSite::from(7), SameSite::Other(7));
+        assert_eq!(SameSite::from_firefox(3), SameSite::Other(3));
+        for v in [-1, 0, 1, 2, 7] {
+            assert_eq!(i32::from(SameSite::from(v)), v);
+        }
+        assert_eq!(SameSite::Other(7).to_string(), "7");
+    }
+}
@@ -48,3 +48,12 @@ pub use firefox::*;
 
 #[cfg(any(feature = "chromium", feature = "firefox"))]
 const CACHE_PATH: &str = "decrypt-cookies";
+
+/// Default dir for [`chromium_crypto::KeyCache`] entries
+#[cfg(feature = "key-cache")]
+pub fn key_cache_dir() -> Option<std::path::PathBuf> {
+    let mut cache = dirs::cache_dir()?;
+    cache.push(CACHE_PATH);
+    cache.push("keys");
+    Some(cache)
+}
@@ -0,0 +1,134 @@
+use std::{fmt::Display, sync::Arc};
+
+use snafu::ResultExt;
+
+use super::{MissingSnafu, Result, UnavailableSnafu, builder::ChromiumBuilderError};
+
+/// A file of the profile that the getter reads
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+pub enum ArtifactKind {
+    /// `Cookies`
+    Cookies,
+    /// `Login Data`
+    Logins,
+    /// `Login Data For Account`
+    LoginsForAccount,
+    /// `Local State` on Windows, the keyring/keychain entry on Linux/macOS
+    Key,
+}
+
+impl Display for ArtifactKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Cookies => "Cookies",
+            Self::Logins => "Login Data",
+            Self::LoginsForAccount => "Login Data For Account",
+            Self::Key => "Key",
+        })
+    }
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub enum ArtifactStatus {
+    Available,
+    /// The profile does not have it
+    #[default]
+    Missing,
+    /// It exists but can't be copied or opened
+    Failed(Arc<ChromiumBuilderError>),
+}
+
+impl ArtifactStatus {
+    pub const fn is_available(&self) -> bool {
+        matches!(self, Self::Available)
+    }
+}
+
+/// What [`ChromiumBuilder::build`](super::builder::ChromiumBuilder::build) could load
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[non_exhaustive]
+pub struct ProfileArtifacts {
+    pub cookies: ArtifactStatus,
+    pub logins: ArtifactStatus,
+    pub logins_for_account: ArtifactStatus,
+    pub key: ArtifactStatus,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub(crate) enum Artifact<T> {
+    Available(T),
+    #[default]
+    Missing,
+    Failed(Arc<ChromiumBuilderError>),
+}
+
+impl<T> Artifact<T> {
+    /// `exists`: whether the source file exists, decides between missing and failed
+    pub(crate) fn new(res: super::builder::Result<T>, exists: bool) -> Self {
+        match res {
+            Ok(v) => Self::Available(v),
+            Err(_) if !exists => Self::Missing,
+            Err(e) => Self::Failed(Arc::new(e)),
+        }
+    }
+
+    pub(crate) fn get(&self, kind: ArtifactKind) -> Result<&T> {
+        match self {
+            Self::Available(v) => Ok(v),
+            Self::Missing => MissingSnafu { artifact: kind }.fail(),
+            Self::Failed(e) => Err(Arc::clone(e)).context(UnavailableSnafu { artifact: kind }),
+        }
+    }
+
+    pub(crate) const fn as_option(&self) -> Option<&T> {
+        match self {
+            Self::Available(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn status(&self) -> ArtifactStatus {
+        match self {
+            Self::Available(_) => ArtifactStatus::Available,
+            Self::Missing => ArtifactStatus::Missing,
+            Self::Failed(e) => ArtifactStatus::Failed(Arc::clone(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chromium::{ChromiumError, builder::HomeSnafu};
+
+    #[test]
+    fn missing_and_failed() {
+        let missing = Artifact::<()>::new(HomeSnafu.fail(), false);
+        assert!(matches!(
+            missing.get(ArtifactKind::Cookies),
+            Err(ChromiumError::Missing { artifact: ArtifactKind::Cookies, .. })
+        ));
+
+        let failed = Artifact::<()>::new(HomeSnafu.fail(), true);
+        assert!(matches!(failed.status(), ArtifactStatus::Failed(_)));
+        assert!(matches!(
+            failed.get(ArtifactKind::Logins),
+            Err(ChromiumError::Unavailable { artifact: ArtifactKind::Logins, .. })
+        ));
+
+        assert!(
+            Artifact::new(Ok(1), false)
+                .status()
+                .is_available()
+        );
+    }
+}
@@ -4,16 +4,38 @@ use std::{
     fmt::{Debug, Display},
     marker::PhantomData,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use chromium_crypto::Decrypter;
+#[cfg(feature = "key-cache")]
+use chromium_crypto::KeyCache;
+use leveldb_reader::{LevelDb, LevelDbError};
 use snafu::{Location, OptionExt, ResultExt, Snafu, ensure};
 use tokio::{fs, join};
 
-use super::{ChromiumCookieGetter, ChromiumGetter, ChromiumLoginGetter};
+use super::{
+    ChromiumCookieGetter, ChromiumCookieWriter, ChromiumGetter, ChromiumHistoryGetter,
+    ChromiumIndexedDbGetter, ChromiumLocalStorageGetter, ChromiumLoginGetter,
+    ChromiumLoginImporter, ChromiumWebDataGetter,
+    artifact::{Artifact, ArtifactKind},
+};
 use crate::{
     browser::ChromiumPath,
-    chromium::items::{cookie::cookie_dao::CookiesQuery, passwd::login_data_dao::LoginDataQuery},
+    chromium::items::{
+        account::{self, ProfileAccount},
+        bookmark::Bookmarks,
+        cache::{CacheError, DiskCache},
+        cookie::cookie_dao::CookiesQuery,
+        extension::{self, Extension},
+        history::history_dao::HistoryQuery,
+        indexed_db::IndexedDb,
+        local_storage,
+        passwd::login_data_dao::LoginDataQuery,
+        preferences::Preferences,
+        session::{Session, Sessions, SnssError},
+        web_data::web_data_dao::WebDataQuery,
+    },
 };
 
 // TODO: add browser name in error
@@ -56,6 +78,32 @@ The browser is not installed or started with `--user-data-dir` arg
         #[snafu(implicit)]
         location: Location,
     },
+    #[snafu(display("{source}, path: {}\n@:{location}",path.display()))]
+    Json {
+        source: serde_json::Error,
+        path: PathBuf,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{source}\n@:{location}"))]
+    LevelDb {
+        source: LevelDbError,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{source}\n@:{location}"))]
+    Cache {
+        source: CacheError,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{source}, path: {}\n@:{location}",path.display()))]
+    Snss {
+        source: SnssError,
+        path: PathBuf,
+        #[snafu(implicit)]
+        location: Location,
+    },
     #[snafu(display("{source}\n@:{location}"))]
     TokioJoin {
         source: tokio::task::JoinError,
@@ -101,12 +149,52 @@ where
     Ok(())
 }
 
+/// Copy the files of a directory, replacing an older copy. `LOCK` is skipped
+async fn copy_dir<A, A0>(from: A, to: A0) -> Result<()>
+where
+    A: AsRef<Path> + Send,
+    A0: AsRef<Path> + Send,
+{
+    let (from, to) = (from.as_ref(), to.as_ref());
+    if fs::try_exists(to)
+        .await
+        .unwrap_or_default()
+    {
+        fs::remove_dir_all(to)
+            .await
+            .with_context(|_| IoSnafu { path: to.to_owned() })?;
+    }
+
+    let mut entries = fs::read_dir(from)
+        .await
+        .with_context(|_| IoSnafu { path: from.to_owned() })?;
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .with_context(|_| IoSnafu { path: from.to_owned() })?
+    {
+        let is_dir = entry
+            .file_type()
+            .await
+            .is_ok_and(|v| v.is_dir());
+        if is_dir || entry.file_name() == "LOCK" {
+            continue;
+        }
+        copy(entry.path(), to.join(entry.file_name())).await?;
+    }
+
+    Ok(())
+}
+
 #[derive(Clone)]
 #[derive(Debug)]
 #[derive(Default)]
 #[derive(PartialEq, Eq)]
 pub struct ChromiumBuilder<T: ChromiumPath> {
     pub(crate) base: Option<PathBuf>,
+    #[cfg(feature = "key-cache")]
+    pub(crate) key_cache: Option<KeyCache>,
+    pub(crate) crypto: Option<Arc<Decrypter>>,
     pub(crate) __browser: PhantomData<T>,
 }
 
@@ -120,6 +208,9 @@ impl<B: ChromiumPath> ChromiumBuilder<B> {
     pub const fn new() -> Self {
         Self {
             base: None,
+            #[cfg(feature = "key-cache")]
+            key_cache: None,
+            crypto: None,
             __browser: PhantomData::<B>,
         }
     }
@@ -128,14 +219,37 @@ impl<B: ChromiumPath> ChromiumBuilder<B> {
     pub const fn with_user_data_dir(base: PathBuf) -> Self {
         Self {
             base: Some(base),
+            #[cfg(feature = "key-cache")]
+            key_cache: None,
+            crypto: None,
             __browser: PhantomData::<B>,
         }
     }
+
+    /// Reuse the derived keys across runs, see [`KeyCache`]
+    #[cfg(feature = "key-cache")]
+    #[must_use]
+    pub fn key_cache(mut self, cache: KeyCache) -> Self {
+        self.key_cache = Some(cache);
+        self
+    }
+
+    /// Reuse the key of `getter` instead of deriving it again,
+    /// no effect when its key is unavailable
+    #[must_use]
+    pub fn crypto_of(mut self, getter: &ChromiumGetter<B>) -> Self {
+        self.crypto = getter
+            .crypto
+            .get(ArtifactKind::Key)
+            .ok()
+            .map(Arc::clone);
+        self
+    }
 }
 
 impl<B: ChromiumPath + Send + Sync> ChromiumBuilder<B> {
-    fn ensure_base(self) -> Result<PathBuf> {
-        let base = if let Some(base) = self.base {
+    fn ensure_base(&self) -> Result<PathBuf> {
+        let base = if let Some(base) = self.base.clone() {
             base
         }
         else {
@@ -150,6 +264,10 @@ impl<B: ChromiumPath + Send + Sync> ChromiumBuilder<B> {
         Ok(base)
     }
 
+    /// Only fails when the profile dir doesn't exist.
+    /// The state of each file is in [`ChromiumGetter::artifacts`],
+    /// calls that need an unavailable file return [`ChromiumError::Missing`](super::ChromiumError::Missing)
+    /// or [`ChromiumError::Unavailable`](super::ChromiumError::Unavailable).
     #[cfg_attr(
         feature = "tracing",
         tracing::instrument(name = "Chromium build", skip(self), fields(browser), level = "debug")
@@ -164,21 +282,28 @@ impl<B: ChromiumPath + Send + Sync> ChromiumBuilder<B> {
             tracing::debug!(base = %base.display());
         };
 
-        let crypto = Self::gen_crypto(&base);
+        let crypto = self.gen_crypto(&base);
 
-        let (crypto, cookies_query, logins) = join!(
+        let (crypto, cookies_query, login_data_query, lfa) = join!(
             crypto,
             Self::cache_cookies(base.clone()),
-            Self::cache_login(base.clone())
+            Self::cache_login_data(base.clone()),
+            Self::cache_login_data_for_account(base.clone())
         );
 
-        let (login_data_query, lfa) = logins?;
+        #[cfg(target_os = "windows")]
+        let key_exists = B::key(base.clone()).exists();
+        #[cfg(not(target_os = "windows"))]
+        let key_exists = true;
 
         Ok(ChromiumGetter {
-            cookies_query: cookies_query?,
-            login_data_query,
-            login_data_for_account_query: lfa,
-            crypto: crypto?,
+            cookies_query: Artifact::new(cookies_query, B::cookies(base.clone()).exists()),
+            login_data_query: Artifact::new(login_data_query, B::login_data(base.clone()).exists()),
+            login_data_for_account_query: Artifact::new(
+                lfa,
+                B::login_data_for_account(base).exists(),
+            ),
+            crypto: Artifact::new(crypto, key_exists),
             __browser,
         })
     }
@@ -202,7 +327,7 @@ impl<B: ChromiumPath + Send + Sync> ChromiumBuilder<B> {
             tracing::debug!(base = %base.display());
         };
 
-        let (crypto, logins) = join!(Self::gen_crypto(&base), Self::cache_login(base.clone()));
+        let (crypto, logins) = join!(self.gen_crypto(&base), Self::cache_login(base.clone()));
 
         let (login_data_query, lfa) = logins?;
 
@@ -233,7 +358,7 @@ impl<B: ChromiumPath + Send + Sync> ChromiumBuilder<B> {
             tracing::debug!(base = %base.display());
         };
 
-        let crypto = Self::gen_crypto(&base);
+        let crypto = self.gen_crypto(&base);
 
         let (crypto, cookies_query) = join!(crypto, Self::cache_cookies(base.clone()));
 
@@ -244,11 +369,446 @@ impl<B: ChromiumPath + Send + Sync> ChromiumBuilder<B> {
         })
     }
 
+    /// Write cookies into the profile, see [`ChromiumCookieWriter::write_cookies`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium CookieWriter build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_cookie_writer(self) -> Result<ChromiumCookieWriter<B>> {
+        let __browser = self.__browser;
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        let crypto = self.gen_crypto(&base).await?;
+
+        Ok(ChromiumCookieWriter { base, crypto, __browser })
+    }
+
+    /// Import logins into the profile, see [`ChromiumLoginImporter::import_logins`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium LoginImporter build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_login_importer(self) -> Result<ChromiumLoginImporter<B>> {
+        let __browser = self.__browser;
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        let crypto = self.gen_crypto(&base);
+
+        let (crypto, login_data_query) = join!(crypto, Self::cache_login_data(base.clone()));
+
+        Ok(ChromiumLoginImporter {
+            base,
+            login_data_query: login_data_query?,
+            crypto: crypto?,
+            __browser,
+        })
+    }
+
+    /// The `History` database doesn't need the key
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium History build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_history(self) -> Result<ChromiumHistoryGetter<B>> {
+        let __browser = self.__browser;
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        Ok(ChromiumHistoryGetter {
+            history_query: Self::cache_history(base).await?,
+            __browser,
+        })
+    }
+
+    /// The `Web Data` database, card numbers and IBANs need the key
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium Web Data build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_web_data(self) -> Result<ChromiumWebDataGetter<B>> {
+        let __browser = self.__browser;
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        let crypto = self.gen_crypto(&base);
+
+        let (crypto, web_data_query, accounts) = join!(
+            crypto,
+            Self::cache_web_data(base.clone()),
+            Self::load_accounts(base.clone())
+        );
+
+        Ok(ChromiumWebDataGetter {
+            web_data_query: web_data_query?,
+            crypto: crypto?,
+            accounts,
+            __browser,
+        })
+    }
+
+    /// Read `localStorage` of every origin, no decryption needed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium Local Storage build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_local_storage(self) -> Result<ChromiumLocalStorageGetter<B>> {
+        let __browser = self.__browser;
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        let dir = Self::cache_local_storage(base).await?;
+        let items = tokio::task::spawn_blocking(move || {
+            LevelDb::open(dir).map(|db| local_storage::items(&db))
+        })
+        .await
+        .context(TokioJoinSnafu)?
+        .context(LevelDbSnafu)?;
+
+        Ok(ChromiumLocalStorageGetter { items, __browser })
+    }
+
+    /// Copy the IndexedDB of every origin, they are read on demand by [`GetIndexedDb`](super::GetIndexedDb)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium IndexedDB build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_indexed_db(self) -> Result<ChromiumIndexedDbGetter<B>> {
+        let __browser = self.__browser;
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        Ok(ChromiumIndexedDbGetter {
+            databases: Self::cache_indexed_db(base).await?,
+            __browser,
+        })
+    }
+
+    /// Parse the `Bookmarks` file, export it with [`Bookmarks::to_netscape_html`]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium Bookmarks build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_bookmarks(self) -> Result<Bookmarks> {
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        let path = B::bookmarks(base);
+        let json = fs::read(&path)
+            .await
+            .with_context(|_| IoSnafu { path: path.clone() })?;
+        Bookmarks::from_json(&json).context(JsonSnafu { path })
+    }
+
+    /// Parse the newest `Sessions/Session_*` and `Sessions/Tabs_*` files,
+    /// before Chromium 90 they are `Current Session` and `Current Tabs` in the profile
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium Sessions build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_sessions(self) -> Result<Sessions> {
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        let sessions = B::sessions(base);
+        let (session, tabs) = if fs::try_exists(&sessions)
+            .await
+            .unwrap_or_default()
+        {
+            let mut session = None;
+            let mut tabs = None;
+            let mut entries = fs::read_dir(&sessions)
+                .await
+                .with_context(|_| IoSnafu { path: sessions.clone() })?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .with_context(|_| IoSnafu { path: sessions.clone() })?
+            {
+                let name = entry.file_name();
+                let Some(name) = name.to_str()
+                else {
+                    continue;
+                };
+                let (newest, stamp) = if let Some(v) = name.strip_prefix("Session_") {
+                    (&mut session, v)
+                }
+                else if let Some(v) = name.strip_prefix("Tabs_") {
+                    (&mut tabs, v)
+                }
+                else {
+                    continue;
+                };
+                // Named by the creation time
+                let Ok(stamp) = stamp.parse::<i64>()
+                else {
+                    continue;
+                };
+                if newest
+                    .as_ref()
+                    .is_none_or(|(v, _)| *v < stamp)
+                {
+                    *newest = Some((stamp, entry.path()));
+                }
+            }
+            (session.map(|(_, v)| v), tabs.map(|(_, v)| v))
+        }
+        else {
+            let profile = sessions
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+            let (session, tabs) = (
+                profile.join("Current Session"),
+                profile.join("Current Tabs"),
+            );
+            let (has_session, has_tabs) = join!(fs::try_exists(&session), fs::try_exists(&tabs));
+            (
+                has_session
+                    .unwrap_or_default()
+                    .then_some(session),
+                has_tabs
+                    .unwrap_or_default()
+                    .then_some(tabs),
+            )
+        };
+
+        let mut res = Sessions::default();
+        if let Some(path) = session {
+            let data = fs::read(&path)
+                .await
+                .with_context(|_| IoSnafu { path: path.clone() })?;
+            res.session = Some(Session::from_session_snss(&data).context(SnssSnafu { path })?);
+        }
+        if let Some(path) = tabs {
+            let data = fs::read(&path)
+                .await
+                .with_context(|_| IoSnafu { path: path.clone() })?;
+            res.tabs = Some(Session::from_tabs_snss(&data).context(SnssSnafu { path })?);
+        }
+        Ok(res)
+    }
+
+    /// Read the HTTP disk cache, urls, response headers and bodies of cached responses
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium Disk Cache build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_disk_cache(self) -> Result<DiskCache> {
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        let dir = Self::cache_disk_cache(base).await?;
+        tokio::task::spawn_blocking(move || DiskCache::open(&dir))
+            .await
+            .context(TokioJoinSnafu)?
+            .context(CacheSnafu)
+    }
+
+    /// Read the installed extensions from `Preferences`, `Secure Preferences` and their manifests
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium Extensions build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_extensions(self) -> Result<Vec<Extension>> {
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        let path = B::preferences(base.clone());
+        let preferences = fs::read(&path)
+            .await
+            .with_context(|_| IoSnafu { path: path.clone() })?;
+        let secure_preferences = fs::read(B::secure_preferences(base.clone()))
+            .await
+            .ok();
+        let extensions_dir = B::extensions(base);
+
+        tokio::task::spawn_blocking(move || {
+            extension::from_preferences(
+                &preferences,
+                secure_preferences.as_deref(),
+                &extensions_dir,
+            )
+        })
+        .await
+        .context(TokioJoinSnafu)?
+        .context(JsonSnafu { path })
+    }
+
+    /// Read `Preferences` and `Secure Preferences`, see [`Preferences::verify_macs`] to detect tampering
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "Chromium Preferences build",
+            skip(self),
+            fields(browser),
+            level = "debug"
+        )
+    )]
+    pub async fn build_preferences(self) -> Result<Preferences> {
+        let base = self.ensure_base()?;
+
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Span::current().record("browser", B::NAME);
+            tracing::debug!(base = %base.display());
+        };
+
+        let path = B::preferences(base.clone());
+        let preferences = fs::read(&path)
+            .await
+            .with_context(|_| IoSnafu { path: path.clone() })?;
+        let secure_path = B::secure_preferences(base);
+        let secure_preferences = match fs::read(&secure_path).await {
+            Ok(v) => Some(v),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e).context(IoSnafu { path: secure_path }),
+        };
+
+        Preferences::from_json(&preferences, secure_preferences.as_deref())
+            .context(JsonSnafu { path })
+    }
+
     #[cfg_attr(
-        not(target_os = "windows"),
+        all(not(target_os = "windows"), not(feature = "key-cache")),
         expect(unused_variables, reason = "for windows")
     )]
-    async fn gen_crypto(base: &Path) -> Result<Decrypter> {
+    async fn gen_crypto(&self, base: &Path) -> Result<Arc<Decrypter>> {
+        if let Some(crypto) = &self.crypto {
+            return Ok(Arc::clone(crypto));
+        }
+
+        #[cfg(target_os = "windows")]
+        let key_path = Self::cache_key(base.to_owned()).await?;
+
+        #[cfg(feature = "key-cache")]
+        let entry = if let Some(key_cache) = &self.key_cache
+            && let Some(fingerprint) = Self::key_fingerprint(
+                #[cfg(target_os = "windows")]
+                &key_path,
+            )
+            .await
+        {
+            let id = KeyCache::entry_id(B::NAME, base);
+            match key_cache
+                .load(&id, &fingerprint)
+                .await
+            {
+                Ok(Some(crypto)) => return Ok(Arc::new(crypto)),
+                Ok(None) => {},
+                Err(e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %e, "load key cache failed");
+                    _ = e;
+                },
+            }
+            Some((key_cache, id, fingerprint))
+        }
+        else {
+            None
+        };
+
         #[cfg(target_os = "linux")]
         let crypto = Decrypter::build(B::SAFE_STORAGE, crate::browser::need_safe_storage);
 
@@ -256,43 +816,170 @@ impl<B: ChromiumPath + Send + Sync> ChromiumBuilder<B> {
         let crypto = Decrypter::build(B::SAFE_STORAGE, B::SAFE_NAME);
 
         #[cfg(target_os = "windows")]
-        let crypto = {
-            let key_path = Self::cache_key(base.to_owned()).await?;
-            Decrypter::build(key_path)
-        };
+        let crypto = Decrypter::build(key_path);
 
-        crypto
+        let crypto = crypto
             .await
-            .context(DecrypterSnafu)
+            .context(DecrypterSnafu)?;
+
+        #[cfg(feature = "key-cache")]
+        if let Some((key_cache, id, fingerprint)) = entry
+            && let Err(e) = key_cache
+                .store(&id, &fingerprint, &crypto)
+                .await
+        {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %e, "store key cache failed");
+            _ = e;
+        }
+
+        Ok(Arc::new(crypto))
+    }
+
+    /// Identify the key source, `None` skips the cache
+    #[cfg(feature = "key-cache")]
+    async fn key_fingerprint(#[cfg(target_os = "windows")] key_path: &Path) -> Option<Vec<u8>> {
+        #[cfg(target_os = "linux")]
+        let fingerprint = KeyCache::keyring_fingerprint(B::SAFE_STORAGE)
+            .await
+            .map(Option::unwrap_or_default);
+
+        // The keychain item has no modification time, only the TTL applies
+        #[cfg(target_os = "macos")]
+        let fingerprint = Ok::<_, chromium_crypto::key_cache::KeyCacheError>(Vec::new());
+
+        #[cfg(target_os = "windows")]
+        let fingerprint = KeyCache::local_state_fingerprint(key_path).await;
+
+        #[cfg(feature = "tracing")]
+        if let Err(e) = &fingerprint {
+            tracing::debug!(error = %e, "skip key cache");
+        }
+
+        fingerprint.ok()
     }
 
     /// return login and login for account
     async fn cache_login(base: PathBuf) -> Result<(LoginDataQuery, Option<LoginDataQuery>)> {
-        let login_data = B::login_data(base.clone());
+        let (lg, lfa) = join!(
+            Self::cache_login_data(base.clone()),
+            Self::cache_login_data_for_account(base)
+        );
+
+        Ok((lg?, lfa.ok()))
+    }
+
+    async fn cache_login_data(base: PathBuf) -> Result<LoginDataQuery> {
+        let login_data = B::login_data(base);
         let login_data_temp = B::login_data_temp().context(HomeSnafu)?;
 
+        copy(&login_data, &login_data_temp).await?;
+        LoginDataQuery::new(login_data_temp)
+            .await
+            .context(DbSnafu)
+    }
+
+    async fn cache_login_data_for_account(base: PathBuf) -> Result<LoginDataQuery> {
         let login_data_for_account = B::login_data_for_account(base);
         let login_data_for_account_temp = B::login_data_for_account_temp().context(HomeSnafu)?;
 
-        let (lg, lfac) = join!(
-            copy(&login_data, &login_data_temp),
-            copy(&login_data_for_account, &login_data_for_account_temp)
-        );
-        lg?;
+        copy(&login_data_for_account, &login_data_for_account_temp).await?;
+        LoginDataQuery::new(login_data_for_account_temp)
+            .await
+            .context(DbSnafu)
+    }
 
-        Ok((
-            LoginDataQuery::new(login_data_temp)
-                .await
-                .context(DbSnafu)?,
-            if lfac.is_ok() {
-                LoginDataQuery::new(login_data_for_account_temp)
-                    .await
-                    .ok()
-            }
-            else {
-                None
+    /// Accounts of `Preferences`, and the primary one of `Local State`, unreadable files are skipped
+    async fn load_accounts(base: PathBuf) -> Vec<ProfileAccount> {
+        let mut accounts = match fs::read(B::preferences(base.clone())).await {
+            Ok(json) => account::from_preferences(&json).unwrap_or_else(|_e| {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(error = %_e, "parse Preferences failed");
+                vec![]
+            }),
+            Err(_e) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(error = %_e, "read Preferences failed");
+                vec![]
             },
-        ))
+        };
+
+        if let Some(profile) = B::profile_name()
+            && let Ok(json) = fs::read(B::local_state(base)).await
+            && let Ok(Some(primary)) = account::from_local_state(&json, profile)
+            && !accounts
+                .iter()
+                .any(|a| a.account_id == primary.account_id)
+        {
+            accounts.push(primary);
+        }
+        accounts
+    }
+
+    async fn cache_web_data(base: PathBuf) -> Result<WebDataQuery> {
+        let web_data = B::web_data(base);
+        let web_data_temp = B::web_data_temp().context(HomeSnafu)?;
+
+        copy(&web_data, &web_data_temp).await?;
+        WebDataQuery::new(web_data_temp)
+            .await
+            .context(DbSnafu)
+    }
+
+    async fn cache_local_storage(base: PathBuf) -> Result<PathBuf> {
+        let local_storage = B::local_storage(base);
+        let local_storage_temp = B::local_storage_temp().context(HomeSnafu)?;
+
+        copy_dir(&local_storage, &local_storage_temp).await?;
+        Ok(local_storage_temp)
+    }
+
+    async fn cache_disk_cache(base: PathBuf) -> Result<PathBuf> {
+        let disk_cache = B::disk_cache(base);
+        let disk_cache_temp = B::disk_cache_temp().context(HomeSnafu)?;
+
+        copy_dir(&disk_cache, &disk_cache_temp).await?;
+        Ok(disk_cache_temp)
+    }
+
+    /// The origins and their copied directories
+    async fn cache_indexed_db(base: PathBuf) -> Result<Vec<(String, PathBuf)>> {
+        let indexed_db = B::indexed_db(base);
+        let indexed_db_temp = B::indexed_db_temp().context(HomeSnafu)?;
+
+        let mut entries = fs::read_dir(&indexed_db)
+            .await
+            .with_context(|_| IoSnafu { path: indexed_db.clone() })?;
+        let mut res = vec![];
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .with_context(|_| IoSnafu { path: indexed_db.clone() })?
+        {
+            let name = entry.file_name();
+            let Some(origin) = name
+                .to_str()
+                .and_then(IndexedDb::origin_of_dir)
+            else {
+                continue;
+            };
+            let temp = indexed_db_temp.join(&name);
+            copy_dir(entry.path(), &temp).await?;
+            res.push((origin, temp));
+        }
+        res.sort_unstable();
+
+        Ok(res)
+    }
+
+    async fn cache_history(base: PathBuf) -> Result<HistoryQuery> {
+        let history = B::history(base);
+        let history_temp = B::history_temp().context(HomeSnafu)?;
+
+        copy(&history, &history_temp).await?;
+        HistoryQuery::new(history_temp)
+            .await
+            .context(DbSnafu)
     }
 
     async fn cache_cookies(base: PathBuf) -> Result<CookiesQuery> {
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// An account signed in to a profile, from `Preferences` or `Local State`
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProfileAccount {
+    /// The gaia id, older profiles use the email
+    pub account_id: String,
+    pub email: String,
+    pub full_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Preferences {
+    #[serde(default)]
+    account_info: Vec<AccountInfo>,
+}
+
+#[derive(Deserialize)]
+struct AccountInfo {
+    account_id: String,
+    #[serde(default)]
+    email: String,
+    #[serde(default)]
+    full_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LocalState {
+    #[serde(default)]
+    profile: ProfileState,
+}
+
+#[derive(Deserialize)]
+#[derive(Default)]
+struct ProfileState {
+    #[serde(default)]
+    info_cache: HashMap<String, InfoCache>,
+}
+
+#[derive(Deserialize)]
+struct InfoCache {
+    #[serde(default)]
+    user_name: String,
+    #[serde(default)]
+    gaia_id: String,
+    #[serde(default)]
+    gaia_name: Option<String>,
+}
+
+/// `account_info` of `Preferences`
+pub fn from_preferences(json: &[u8]) -> serde_json::Result<Vec<ProfileAccount>> {
+    serde_json::from_slice(json).map(accounts)
+}
+
+/// `account_info` of parsed `Preferences`
+pub fn from_preferences_value(
+    value: &serde_json::Value,
+) -> serde_json::Result<Vec<ProfileAccount>> {
+    Preferences::deserialize(value).map(accounts)
+}
+
+fn accounts(prefs: Preferences) -> Vec<ProfileAccount> {
+    prefs
+        .account_info
+        .into_iter()
+        .map(|v| ProfileAccount {
+            account_id: v.account_id,
+            email: v.email,
+            full_name: v
+                .full_name
+                .filter(|v| !v.is_empty()),
+        })
+        .collect()
+}
+
+/// `profile.info_cache.<profile>` of `Local State`, the primary account of the profile
+pub fn from_local_state(json: &[u8], profile: &str) -> serde_json::Result<Option<ProfileAccount>> {
+    let mut state: LocalState = serde_json::from_slice(json)?;
+    Ok(state
+        .profile
+        .info_cache
+        .remove(profile)
+        .filter(|v| !v.user_name.is_empty())
+        .map(|v| ProfileAccount {
+            account_id: if v.gaia_id.is_empty() {
+                v.user_name.clone()
+            }
+            else {
+                v.gaia_id
+            },
+            email: v.user_name,
+            full_name: v
+                .gaia_name
+                .filter(|v| !v.is_empty()),
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accounts() {
+        let prefs = br#"{"account_info":[{"account_id":"1234","email":"a@gmail.com","full_name":"A","gaia":"1234"}]}"#;
+        let accounts = from_preferences(prefs).unwrap();
+        assert_eq!(accounts[0].account_id, "1234");
+        assert_eq!(accounts[0].email, "a@gmail.com");
+        assert!(
+            from_preferences(b"{}")
+                .unwrap()
+                .is_empty()
+        );
+
+        let state = br#"{"profile":{"info_cache":{"Default":{"user_name":"b@gmail.com","gaia_id":"5678","gaia_name":""}}}}"#;
+        let account = from_local_state(state, "Default")
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.account_id, "5678");
+        assert_eq!(account.full_name, None);
+        assert_eq!(from_local_state(state, "Profile 1").unwrap(), None);
+    }
+}
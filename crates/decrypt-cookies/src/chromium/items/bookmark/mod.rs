@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::I64ToChromiumDateTime;
+
+mod netscape;
+
+/// The `Bookmarks` file of a profile
+///
+/// <https://source.chromium.org/chromium/chromium/src/+/main:components/bookmarks/browser/bookmark_codec.cc>
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bookmarks {
+    pub version: i64,
+    /// MD5 of the tree, the browser recomputes it when it doesn't match
+    pub checksum: Option<String>,
+    pub bookmark_bar: BookmarkNode,
+    /// "Other bookmarks"
+    pub other: BookmarkNode,
+    /// "Mobile bookmarks"
+    pub synced: BookmarkNode,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BookmarkNode {
+    pub id: i64,
+    pub guid: String,
+    pub name: String,
+    pub date_added: Option<DateTime<Utc>>,
+    pub date_last_used: Option<DateTime<Utc>>,
+    pub meta_info: HashMap<String, String>,
+    pub kind: BookmarkKind,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BookmarkKind {
+    Url {
+        url: String,
+    },
+    Folder {
+        date_modified: Option<DateTime<Utc>>,
+        children: Vec<BookmarkNode>,
+    },
+}
+
+impl Bookmarks {
+    /// Parse the content of a `Bookmarks` file
+    pub fn from_json(json: &[u8]) -> serde_json::Result<Self> {
+        let raw: RawBookmarks = serde_json::from_slice(json)?;
+        Ok(Self {
+            version: raw.version,
+            checksum: raw.checksum,
+            bookmark_bar: raw.roots.bookmark_bar.into(),
+            other: raw.roots.other.into(),
+            synced: raw.roots.synced.into(),
+        })
+    }
+
+    pub const fn roots(&self) -> [&BookmarkNode; 3] {
+        [&self.bookmark_bar, &self.other, &self.synced]
+    }
+
+    /// All url bookmarks, depth first
+    pub fn urls(&self) -> Vec<&BookmarkNode> {
+        let mut res = vec![];
+        for root in self.roots() {
+            root.collect_urls(&mut res);
+        }
+        res
+    }
+}
+
+impl BookmarkNode {
+    pub const fn is_folder(&self) -> bool {
+        matches!(self.kind, BookmarkKind::Folder { .. })
+    }
+
+    pub fn url(&self) -> Option<&str> {
+        match &self.kind {
+            BookmarkKind::Url { url } => Some(url),
+            BookmarkKind::Folder { .. } => None,
+        }
+    }
+
+    /// Empty for urls
+    pub fn children(&self) -> &[Self] {
+        match &self.kind {
+            BookmarkKind::Url { .. } => &[],
+            BookmarkKind::Folder { children, .. } => children,
+        }
+    }
+
+    fn collect_urls<'a>(&'a self, res: &mut Vec<&'a Self>) {
+        if self.url().is_some() {
+            res.push(self);
+        }
+        for child in self.children() {
+            child.collect_urls(res);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawBookmarks {
+    #[serde(default)]
+    checksum: Option<String>,
+    roots: RawRoots,
+    #[serde(default)]
+    version: i64,
+}
+
+#[derive(Deserialize)]
+struct RawRoots {
+    bookmark_bar: RawNode,
+    other: RawNode,
+    synced: RawNode,
+}
+
+/// Numbers are stored as strings
+#[derive(Deserialize)]
+struct RawNode {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    guid: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    date_added: Option<String>,
+    #[serde(default)]
+    date_last_used: Option<String>,
+    #[serde(default)]
+    date_modified: Option<String>,
+    #[serde(default)]
+    meta_info: HashMap<String, String>,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    children: Vec<Self>,
+}
+
+/// `0` means never
+fn chromium_time(v: Option<&str>) -> Option<DateTime<Utc>> {
+    v.and_then(|v| v.parse::<i64>().ok())
+        .filter(|&v| v != 0)
+        .and_then(|v| v.micros_to_chromium_utc())
+}
+
+impl From<RawNode> for BookmarkNode {
+    fn from(v: RawNode) -> Self {
+        let kind = match (v.kind.as_str(), v.url) {
+            ("url", Some(url)) => BookmarkKind::Url { url },
+            _ => BookmarkKind::Folder {
+                date_modified: chromium_time(v.date_modified.as_deref()),
+                children: v
+                    .children
+                    .into_iter()
+                    .map(Self::from)
+                    .collect(),
+            },
+        };
+        Self {
+            id: v.id.parse().unwrap_or_default(),
+            guid: v.guid,
+            name: v.name,
+            date_added: chromium_time(v.date_added.as_deref()),
+            date_last_used: chromium_time(v.date_last_used.as_deref()),
+            meta_info: v.meta_info,
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOOKMARKS: &str = r#"{
+   "checksum": "b1a5ec6ed8f3c1a4b5ef9a4fd0d2f0c1",
+   "roots": {
+      "bookmark_bar": {
+         "children": [ {
+            "date_added": "13350000000000000",
+            "date_last_used": "0",
+            "guid": "8a1d2c56-6f2f-4b0e-a7a0-1f5a8b6f1a01",
+            "id": "5",
+            "meta_info": { "power_bookmark_meta": "" },
+            "name": "Rust <lang> & \"co\"",
+            "type": "url",
+            "url": "https://www.rust-lang.org/?a=1&b=2"
+         }, {
+            "children": [ {
+               "date_added": "13350000000000000",
+               "guid": "8a1d2c56-6f2f-4b0e-a7a0-1f5a8b6f1a03",
+               "id": "7",
+               "name": "docs",
+               "type": "url",
+               "url": "https://docs.rs/"
+            } ],
+            "date_added": "13350000000000000",
+            "date_modified": "13350000001000000",
+            "guid": "8a1d2c56-6f2f-4b0e-a7a0-1f5a8b6f1a02",
+            "id": "6",
+            "name": "Dev",
+            "type": "folder"
+         } ],
+         "date_added": "13340000000000000",
+         "date_modified": "0",
+         "guid": "0bc5d13f-2cba-5d74-951f-3f233fe6c908",
+         "id": "1",
+         "name": "Bookmarks bar",
+         "type": "folder"
+      },
+      "other": {
+         "children": [ {
+            "date_added": "13350000000000000",
+            "guid": "8a1d2c56-6f2f-4b0e-a7a0-1f5a8b6f1a04",
+            "id": "8",
+            "name": "crates",
+            "type": "url",
+            "url": "https://crates.io/"
+         } ],
+         "date_added": "13340000000000000",
+         "guid": "82b081ec-3dd3-529c-8475-ab6c344590dd",
+         "id": "2",
+         "name": "Other bookmarks",
+         "type": "folder"
+      },
+      "synced": {
+         "children": [ ],
+         "date_added": "13340000000000000",
+         "guid": "4cf2e351-0e85-532b-bb37-df045d8f8d0f",
+         "id": "3",
+         "name": "Mobile bookmarks",
+         "type": "folder"
+      }
+   },
+   "version": 1
+}"#;
+
+    #[test]
+    fn parse_and_export() {
+        let bookmarks = Bookmarks::from_json(BOOKMARKS.as_bytes()).unwrap();
+
+        let urls: Vec<_> = bookmarks
+            .urls()
+            .into_iter()
+            .filter_map(BookmarkNode::url)
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://www.rust-lang.org/?a=1&b=2",
+                "https://docs.rs/",
+                "https://crates.io/"
+            ]
+        );
+        let rust = &bookmarks.bookmark_bar.children()[0];
+        assert_eq!(rust.id, 5);
+        assert!(rust.date_added.is_some());
+        assert_eq!(rust.date_last_used, None);
+        assert_eq!(rust.meta_info["power_bookmark_meta"], "");
+
+        let html = bookmarks.to_netscape_html();
+        assert!(html.starts_with("<!DOCTYPE NETSCAPE-Bookmark-file-1>"));
+        assert!(html.contains(r#"PERSONAL_TOOLBAR_FOLDER="true">Bookmarks bar</H3>"#));
+        assert!(html.contains(
+            r#"<DT><A HREF="https://www.rust-lang.org/?a=1&amp;b=2" ADD_DATE="1705526400">Rust &lt;lang&gt; &amp; &quot;co&quot;</A>"#
+        ));
+        assert!(
+            html.contains(r#"<DT><H3 ADD_DATE="1705526400" LAST_MODIFIED="1705526401">Dev</H3>"#)
+        );
+        // "Other bookmarks" are inlined, empty "Mobile bookmarks" are skipped
+        assert!(!html.contains("Other bookmarks"));
+        assert!(!html.contains("Mobile bookmarks"));
+        assert!(html.contains(r#"        <DT><A HREF="https://docs.rs/""#));
+        assert!(html.contains(r#"    <DT><A HREF="https://crates.io/""#));
+    }
+}
@@ -0,0 +1,107 @@
+//! Netscape bookmark file, what every browser imports
+//!
+//! Same layout as Chromium's exporter:
+//! <https://source.chromium.org/chromium/chromium/src/+/main:chrome/browser/bookmarks/bookmark_html_writer.cc>
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+
+use super::{BookmarkKind, BookmarkNode, Bookmarks};
+
+const HEADER: &str = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<!-- This is an automatically generated file.
+     It will be read and overwritten.
+     DO NOT EDIT! -->
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+"#;
+
+const INDENT: &str = "    ";
+
+impl Bookmarks {
+    /// Export to the Netscape bookmark HTML format.
+    ///
+    /// The bookmark bar becomes the toolbar folder, "Other bookmarks" are written at the top level
+    /// and "Mobile bookmarks" become a folder when not empty.
+    pub fn to_netscape_html(&self) -> String {
+        let mut out = String::from(HEADER);
+
+        write_folder(&mut out, &self.bookmark_bar, 1, true);
+        for child in self.other.children() {
+            write_node(&mut out, child, 1);
+        }
+        if !self.synced.children().is_empty() {
+            write_folder(&mut out, &self.synced, 1, false);
+        }
+
+        out.push_str("</DL><p>\n");
+        out
+    }
+}
+
+fn write_node(out: &mut String, node: &BookmarkNode, depth: usize) {
+    match &node.kind {
+        BookmarkKind::Url { url } => {
+            push_indent(out, depth);
+            _ = write!(out, r#"<DT><A HREF="{}""#, escape(url));
+            push_date(out, "ADD_DATE", node.date_added);
+            _ = writeln!(out, ">{}</A>", escape(&node.name));
+        },
+        BookmarkKind::Folder { .. } => write_folder(out, node, depth, false),
+    }
+}
+
+fn write_folder(out: &mut String, node: &BookmarkNode, depth: usize, toolbar: bool) {
+    let date_modified = match &node.kind {
+        BookmarkKind::Folder { date_modified, .. } => *date_modified,
+        BookmarkKind::Url { .. } => None,
+    };
+
+    push_indent(out, depth);
+    out.push_str("<DT><H3");
+    push_date(out, "ADD_DATE", node.date_added);
+    push_date(out, "LAST_MODIFIED", date_modified);
+    if toolbar {
+        out.push_str(r#" PERSONAL_TOOLBAR_FOLDER="true""#);
+    }
+    _ = writeln!(out, ">{}</H3>", escape(&node.name));
+
+    push_indent(out, depth);
+    out.push_str("<DL><p>\n");
+    for child in node.children() {
+        write_node(out, child, depth + 1);
+    }
+    push_indent(out, depth);
+    out.push_str("</DL><p>\n");
+}
+
+fn push_indent(out: &mut String, depth: usize) {
+    for _ in 0..depth {
+        out.push_str(INDENT);
+    }
+}
+
+/// Seconds since the unix epoch
+fn push_date(out: &mut String, attr: &str, date: Option<DateTime<Utc>>) {
+    if let Some(date) = date {
+        _ = write!(out, r#" {attr}="{}""#, date.timestamp());
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&#39;"),
+            c => res.push(c),
+        }
+    }
+    res
+}
@@ -0,0 +1,323 @@
+//! The blockfile cache, `index` is a hash table of addresses into the `data_N` block files,
+//! streams too large for a block file are in `f_N` files
+//!
+//! <https://source.chromium.org/chromium/chromium/src/+/main:net/disk_cache/blockfile/disk_format.h>
+//! <https://source.chromium.org/chromium/chromium/src/+/main:net/disk_cache/blockfile/addr.h>
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use snafu::{OptionExt, ResultExt, ensure};
+
+use super::{CacheEntry, CorruptedSnafu, IoSnafu, Result};
+use crate::chromium::items::{
+    I64ToChromiumDateTime,
+    pickle::{i64_at, u32_at},
+};
+
+const INDEX_MAGIC: u32 = 0xC103_CAC3;
+const BLOCK_MAGIC: u32 = 0xC104_CAC3;
+/// `IndexHeader` with its `LruData`, the table follows
+const INDEX_HEADER_SIZE: usize = 368;
+const DEFAULT_TABLE_LEN: usize = 0x10000;
+const BLOCK_HEADER_SIZE: usize = 8192;
+/// `EntryStore` fills one 256 bytes block, a long key spans more
+const KEY_OFFSET: usize = 96;
+
+pub fn is_index(data: &[u8]) -> bool {
+    u32_at(data, 0) == Some(INDEX_MAGIC)
+}
+
+/// `CacheAddr`, initialized bit, file type, then the file and block or the external file
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, Hash)]
+struct Addr(u32);
+
+impl Addr {
+    const INITIALIZED: u32 = 0x8000_0000;
+
+    const fn is_initialized(self) -> bool {
+        self.0 & Self::INITIALIZED != 0
+    }
+
+    const fn file_type(self) -> u32 {
+        (self.0 >> 28) & 0x7
+    }
+
+    const fn block_size(self) -> Option<usize> {
+        match self.file_type() {
+            1 => Some(36),
+            2 => Some(256),
+            3 => Some(1024),
+            4 => Some(4096),
+            5 => Some(8),
+            6 => Some(104),
+            7 => Some(48),
+            _ => None,
+        }
+    }
+}
+
+fn read_block_file(path: &Path) -> Result<Vec<u8>> {
+    let data = std::fs::read(path).with_context(|_| IoSnafu { path: path.to_owned() })?;
+    ensure!(
+        u32_at(&data, 0) == Some(BLOCK_MAGIC),
+        CorruptedSnafu {
+            reason: "bad block file magic",
+            path
+        }
+    );
+    Ok(data)
+}
+
+struct Reader<'a> {
+    dir: &'a Path,
+    /// `data_N` by `N`, `None` when it is missing or broken
+    block_files: HashMap<u32, Option<Vec<u8>>>,
+}
+
+impl Reader<'_> {
+    /// A missing or broken file is read once, the entries in it fail
+    fn block_file(&mut self, number: u32) -> Result<&[u8]> {
+        let dir = self.dir;
+        self.block_files
+            .entry(number)
+            .or_insert_with(|| {
+                read_block_file(&dir.join(format!("data_{number}")))
+                    .inspect_err(|_e| {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(error = %_e, "read cache block file failed");
+                    })
+                    .ok()
+            })
+            .as_deref()
+            .with_context(|| CorruptedSnafu {
+                reason: "missing or broken block file",
+                path: dir.join(format!("data_{number}")),
+            })
+    }
+
+    /// `len` bytes at `addr`, `None` when it points outside the file
+    fn read(&mut self, addr: Addr, len: usize) -> Result<Option<Vec<u8>>> {
+        if !addr.is_initialized() {
+            return Ok(None);
+        }
+        let Some(block_size) = addr.block_size()
+        else {
+            let path = self
+                .dir
+                .join(format!("f_{:06x}", addr.0 & 0x0FFF_FFFF));
+            let data = match std::fs::read(&path) {
+                Ok(v) => v,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => return Err(e).context(IoSnafu { path }),
+            };
+            return Ok(data.get(..len).map(<[u8]>::to_vec));
+        };
+
+        let start = (addr.0 & 0xFFFF) as usize * block_size + BLOCK_HEADER_SIZE;
+        let data = self.block_file((addr.0 >> 16) & 0xFF)?;
+        Ok(start
+            .checked_add(len)
+            .and_then(|end| data.get(start..end))
+            .map(<[u8]>::to_vec))
+    }
+
+    /// An `EntryStore`, `None` when broken
+    fn entry(&mut self, store: &[u8]) -> Result<Option<CacheEntry>> {
+        let Some(key_len) = u32_at(store, 32).map(|v| v as usize)
+        else {
+            return Ok(None);
+        };
+        let long_key = Addr(u32_at(store, 36).unwrap_or_default());
+        let key = if long_key.is_initialized() {
+            self.read(long_key, key_len)?
+        }
+        else {
+            store
+                .get(KEY_OFFSET..KEY_OFFSET + key_len)
+                .map(<[u8]>::to_vec)
+        };
+        let Some(key) = key
+        else {
+            return Ok(None);
+        };
+
+        let mut entry = CacheEntry {
+            key: String::from_utf8_lossy(&key).into_owned(),
+            creation_time: i64_at(store, 24).and_then(|v| v.micros_to_chromium_utc()),
+            ..Default::default()
+        };
+
+        // `RankingsNode`, last used then last modified
+        let rankings = Addr(u32_at(store, 8).unwrap_or_default());
+        if let Some(node) = self.read(rankings, 8)? {
+            entry.last_used = i64_at(&node, 0).and_then(|v| v.micros_to_chromium_utc());
+        }
+
+        let stream = |i: usize| {
+            Some((
+                Addr(u32_at(store, 56 + i * 4)?),
+                u32_at(store, 40 + i * 4)? as usize,
+            ))
+        };
+        if let Some((addr, len)) = stream(0)
+            && let Some(data) = self.read(addr, len)?
+        {
+            _ = entry.read_response_info(&data);
+        }
+        if let Some((addr, len)) = stream(1)
+            && let Some(data) = self.read(addr, len)?
+        {
+            entry.body = data;
+        }
+        Ok(Some(entry))
+    }
+}
+
+pub fn entries(dir: &Path) -> Result<Vec<CacheEntry>> {
+    let path = dir.join("index");
+    let index = std::fs::read(&path).with_context(|_| IoSnafu { path: path.clone() })?;
+    ensure!(
+        is_index(&index),
+        CorruptedSnafu { reason: "bad index magic", path }
+    );
+    let table_len = match u32_at(&index, 28) {
+        Some(0) | None => DEFAULT_TABLE_LEN,
+        Some(v) => v as usize,
+    };
+    let table = index
+        .get(INDEX_HEADER_SIZE..)
+        .unwrap_or_default();
+    let (table, _) = table.as_chunks::<4>();
+
+    let mut reader = Reader { dir, block_files: HashMap::new() };
+    let mut seen = HashSet::new();
+    let mut res = vec![];
+    for &head in table.iter().take(table_len) {
+        // Entries with the same bucket are chained by `next`
+        let mut addr = Addr(u32::from_le_bytes(head));
+        while addr.is_initialized() && seen.insert(addr) {
+            let blocks = ((addr.0 >> 24) & 0x3) as usize + 1;
+            // A broken entry is skipped, a broken store ends the chain
+            let store = match reader.read(addr, blocks * 256) {
+                Ok(Some(v)) => v,
+                Ok(None) => break,
+                Err(_e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_e, "skip cache entry");
+                    break;
+                },
+            };
+            match reader.entry(&store) {
+                Ok(Some(entry)) => res.push(entry),
+                Ok(None) => {},
+                Err(_e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_e, "skip cache entry");
+                },
+            }
+            addr = Addr(u32_at(&store, 4).unwrap_or_default());
+        }
+    }
+    res.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::tests::response_info, *};
+
+    fn block_file(blocks: &[(usize, Vec<u8>)], block_size: usize) -> Vec<u8> {
+        let mut res = vec![0; BLOCK_HEADER_SIZE];
+        res[..4].copy_from_slice(&BLOCK_MAGIC.to_le_bytes());
+        for (start, data) in blocks {
+            let offset = BLOCK_HEADER_SIZE + start * block_size;
+            if res.len() < offset + data.len() {
+                res.resize(offset + data.len(), 0);
+            }
+            res[offset..offset + data.len()].copy_from_slice(data);
+        }
+        res
+    }
+
+    fn entry_store(next: u32, rankings: u32, key: &str, streams: [(u32, usize); 2]) -> Vec<u8> {
+        let mut res = vec![0; 256];
+        res[4..8].copy_from_slice(&next.to_le_bytes());
+        res[8..12].copy_from_slice(&rankings.to_le_bytes());
+        res[24..32].copy_from_slice(&13_340_000_000_000_000_i64.to_le_bytes());
+        res[32..36].copy_from_slice(&(key.len() as u32).to_le_bytes());
+        for (i, (addr, len)) in streams.into_iter().enumerate() {
+            res[40 + i * 4..44 + i * 4].copy_from_slice(&(len as u32).to_le_bytes());
+            res[56 + i * 4..60 + i * 4].copy_from_slice(&addr.to_le_bytes());
+        }
+        res[KEY_OFFSET..KEY_OFFSET + key.len()].copy_from_slice(key.as_bytes());
+        res
+    }
+
+    #[test]
+    fn read() {
+        let dir = std::env::temp_dir().join(format!(
+            "decrypt-cookies-blockfile-test-{}",
+            std::process::id()
+        ));
+        _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let info = response_info(
+            13_340_000_000_000_000,
+            "HTTP/1.1 200 OK\nSet-Cookie: a=1\n\n",
+        );
+        // entries in data_1 (256), a rankings node in data_0 (36), headers in data_2 (1k),
+        // the body in f_000001
+        let entry = |block: u32| 0xA001_0000 | block;
+        let first = entry_store(
+            entry(1),
+            0x9000_0000,
+            "https://a.com/",
+            [(0xB002_0000, info.len()), (0x8000_0001, 5)],
+        );
+        let second = entry_store(0, 0, "https://b.com/", [(0, 0), (0, 0)]);
+        let mut rankings = vec![0; 36];
+        rankings[..8].copy_from_slice(&13_340_000_000_000_001_i64.to_le_bytes());
+
+        std::fs::write(dir.join("data_0"), block_file(&[(0, rankings)], 36)).unwrap();
+        std::fs::write(
+            dir.join("data_1"),
+            block_file(&[(0, first), (1, second)], 256),
+        )
+        .unwrap();
+        std::fs::write(dir.join("data_2"), block_file(&[(0, info)], 1024)).unwrap();
+        std::fs::write(dir.join("f_000001"), b"hello").unwrap();
+
+        let mut index = vec![0; INDEX_HEADER_SIZE + 4 * 16];
+        index[..4].copy_from_slice(&INDEX_MAGIC.to_le_bytes());
+        index[28..32].copy_from_slice(&16_u32.to_le_bytes());
+        index[INDEX_HEADER_SIZE + 12..INDEX_HEADER_SIZE + 16]
+            .copy_from_slice(&entry(0).to_le_bytes());
+        // data_3 has a bad magic, data_4 is missing
+        std::fs::write(dir.join("data_3"), vec![0; BLOCK_HEADER_SIZE + 256]).unwrap();
+        index[INDEX_HEADER_SIZE..INDEX_HEADER_SIZE + 4]
+            .copy_from_slice(&0xA003_0000_u32.to_le_bytes());
+        index[INDEX_HEADER_SIZE + 4..INDEX_HEADER_SIZE + 8]
+            .copy_from_slice(&0xA004_0000_u32.to_le_bytes());
+        std::fs::write(dir.join("index"), index).unwrap();
+
+        let entries = entries(&dir).unwrap();
+        _ = std::fs::remove_dir_all(&dir);
+
+        let [a, b] = entries.as_slice()
+        else {
+            panic!("{entries:?}");
+        };
+        assert_eq!(a.url(), "https://a.com/");
+        assert_eq!(a.body, b"hello");
+        assert_eq!(a.set_cookies().collect::<Vec<_>>(), ["a=1"]);
+        assert_eq!(a.last_used, 13_340_000_000_000_001.micros_to_chromium_utc());
+        assert_eq!(b.url(), "https://b.com/");
+        assert!(b.body.is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use snafu::{Location, ResultExt, Snafu};
+
+use super::{I64ToChromiumDateTime, match_origin_host, pickle::Pickle};
+
+mod blockfile;
+mod simple;
+
+#[derive(Debug)]
+#[derive(Snafu)]
+#[snafu(visibility(pub))]
+#[non_exhaustive]
+pub enum CacheError {
+    #[snafu(display("{source}, path: {}\n@:{location}", path.display()))]
+    Io {
+        source: std::io::Error,
+        path: PathBuf,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{reason}, path: {}\n@:{location}", path.display()))]
+    Corrupted {
+        reason: &'static str,
+        path: PathBuf,
+        #[snafu(implicit)]
+        location: Location,
+    },
+}
+
+type Result<T> = std::result::Result<T, CacheError>;
+
+/// The HTTP disk cache of a profile, `Cache/Cache_Data`
+///
+/// <https://source.chromium.org/chromium/chromium/src/+/main:net/disk_cache/>
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiskCache {
+    pub format: CacheFormat,
+    pub entries: Vec<CacheEntry>,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CacheFormat {
+    /// A file per entry, `<hash>_0`, the default on Linux, macOS and Android
+    Simple,
+    /// `index` and `data_N` block files with `f_N` for large streams, the default on Windows
+    Blockfile,
+}
+
+/// A cached response
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CacheEntry {
+    /// The url, with a partitioned cache prefixed by `_dk_` and the top frame and frame sites
+    pub key: String,
+    /// When the request was sent
+    pub request_time: Option<DateTime<Utc>>,
+    /// When the response headers were received
+    pub response_time: Option<DateTime<Utc>>,
+    /// Only in a blockfile cache
+    pub creation_time: Option<DateTime<Utc>>,
+    /// Only in a blockfile cache
+    pub last_used: Option<DateTime<Utc>>,
+    /// Like `HTTP/1.1 200 OK`
+    pub status_line: String,
+    /// In received order, names keep their case
+    pub headers: Vec<(String, String)>,
+    /// As received, still `Content-Encoding` encoded
+    pub body: Vec<u8>,
+}
+
+impl CacheEntry {
+    /// The url of the key
+    pub fn url(&self) -> &str {
+        let url = self
+            .key
+            .rsplit(' ')
+            .next()
+            .unwrap_or_default();
+        // `<upload data id>/<flags>/` of `HttpCache::GenerateCacheKey`
+        let mut parts = url.splitn(3, '/');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(id), Some(flags), Some(rest))
+                if [id, flags].iter().all(|v| {
+                    !v.is_empty()
+                        && v.bytes()
+                            .all(|v| v.is_ascii_digit())
+                }) =>
+            {
+                rest
+            },
+            _ => url,
+        }
+    }
+
+    /// The host of the url, without port
+    pub fn host(&self) -> &str {
+        let url = self.url();
+        let authority = url
+            .split_once("://")
+            .map_or(url, |(_, v)| v);
+        let authority = authority
+            .split(['/', '?', '#'])
+            .next()
+            .unwrap_or_default();
+        let host = authority
+            .rsplit_once('@')
+            .map_or(authority, |(_, v)| v);
+        host.rsplit_once(':')
+            .map_or(host, |(v, _)| v)
+    }
+
+    /// The host or a subdomain of it
+    pub fn match_host(&self, host: &str) -> bool {
+        match_origin_host(self.host(), host)
+    }
+
+    /// Like `200`
+    pub fn status(&self) -> Option<u16> {
+        self.status_line
+            .split(' ')
+            .nth(1)?
+            .parse()
+            .ok()
+    }
+
+    /// The first value of a header, the name is case insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// All values of a header, the name is case insensitive
+    pub fn header_values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.headers
+            .iter()
+            .filter(move |(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// The `Set-Cookie` headers of the response
+    pub fn set_cookies(&self) -> impl Iterator<Item = &str> {
+        self.header_values("set-cookie")
+    }
+
+    /// Fill the fields of a `net::HttpResponseInfo`
+    ///
+    /// <https://source.chromium.org/chromium/chromium/src/+/main:net/http/http_response_info.cc>
+    fn read_response_info(&mut self, data: &[u8]) -> Option<()> {
+        const HAS_EXTRA_FLAGS: u32 = 1 << 31;
+        const HAS_ORIGINAL_RESPONSE_TIME: u32 = 1 << 0;
+
+        let mut pickle = Pickle::new(data)?;
+        let flags = pickle.u32()?;
+        let extra_flags = if flags & HAS_EXTRA_FLAGS == 0 {
+            0
+        }
+        else {
+            pickle.u32()?
+        };
+        self.request_time = pickle
+            .i64()?
+            .micros_to_chromium_utc();
+        self.response_time = pickle
+            .i64()?
+            .micros_to_chromium_utc();
+        if extra_flags & HAS_ORIGINAL_RESPONSE_TIME != 0 {
+            pickle.i64()?;
+        }
+
+        // The status line and the headers, each ends with `\0`
+        let raw = pickle.string()?;
+        let mut lines = raw
+            .split('\0')
+            .take_while(|v| !v.is_empty());
+        lines
+            .next()?
+            .clone_into(&mut self.status_line);
+        self.headers = lines
+            .filter_map(|v| {
+                let (name, value) = v.split_once(':')?;
+                Some((name.trim().to_owned(), value.trim().to_owned()))
+            })
+            .collect();
+        Some(())
+    }
+}
+
+impl DiskCache {
+    /// Read a `Cache_Data` directory, broken entries are skipped
+    pub fn open(dir: &Path) -> Result<Self> {
+        let index = dir.join("index");
+        let is_blockfile = match std::fs::read(&index) {
+            Ok(v) => blockfile::is_index(&v),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => false,
+            Err(e) => return Err(e).context(IoSnafu { path: index }),
+        };
+        if is_blockfile {
+            Ok(Self {
+                format: CacheFormat::Blockfile,
+                entries: blockfile::entries(dir)?,
+            })
+        }
+        else {
+            Ok(Self {
+                format: CacheFormat::Simple,
+                entries: simple::entries(dir)?,
+            })
+        }
+    }
+
+    /// Entries of the host or its subdomains
+    pub fn by_host<'a>(&'a self, host: &'a str) -> impl Iterator<Item = &'a CacheEntry> {
+        self.entries
+            .iter()
+            .filter(move |v| v.match_host(host))
+    }
+
+    /// `(url, Set-Cookie)` of all responses
+    pub fn set_cookies(&self) -> Vec<(&str, &str)> {
+        self.entries
+            .iter()
+            .flat_map(|entry| {
+                entry
+                    .set_cookies()
+                    .map(|v| (entry.url(), v))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{super::pickle::tests::PickleWriter, *};
+
+    /// A `net::HttpResponseInfo` without extras
+    pub fn response_info(request_time: i64, headers: &str) -> Vec<u8> {
+        PickleWriter::default()
+            .int(3)
+            .i64(request_time)
+            .i64(request_time + 1)
+            .string(&headers.replace('\n', "\0"))
+            .finish()
+    }
+
+    #[test]
+    fn entry() {
+        let mut entry = CacheEntry {
+            key: "1/0/_dk_https://a.com https://a.com https://cdn.a.com:8443/x.js?v=1".to_owned(),
+            ..Default::default()
+        };
+        entry
+            .read_response_info(&response_info(
+                13_340_000_000_000_000,
+                "HTTP/1.1 200 OK\nSet-Cookie: a=1\nContent-Type: text/javascript\nset-cookie: \
+                 b=2\n\n",
+            ))
+            .unwrap();
+
+        assert_eq!(entry.url(), "https://cdn.a.com:8443/x.js?v=1");
+        assert_eq!(entry.host(), "cdn.a.com");
+        assert!(entry.match_host("a.com"));
+        assert_eq!(entry.status(), Some(200));
+        assert_eq!(entry.header("content-type"), Some("text/javascript"));
+        assert_eq!(
+            entry
+                .set_cookies()
+                .collect::<Vec<_>>(),
+            ["a=1", "b=2"]
+        );
+        assert_eq!(
+            entry.request_time,
+            13_340_000_000_000_000.micros_to_chromium_utc()
+        );
+
+        entry.key = "1/0/https://b.com/".to_owned();
+        assert_eq!(entry.url(), "https://b.com/");
+        entry.key = "https://b.com/1/2/".to_owned();
+        assert_eq!(entry.url(), "https://b.com/1/2/");
+    }
+}
@@ -0,0 +1,135 @@
+//! The simple cache, `<hash>_0` holds the key, the body (stream 1) and the response info (stream 0)
+//!
+//! <https://source.chromium.org/chromium/chromium/src/+/main:net/disk_cache/simple/simple_entry_format.h>
+
+use std::path::Path;
+
+use snafu::ResultExt;
+
+use super::{CacheEntry, IoSnafu, Result};
+use crate::chromium::items::pickle::{u32_at, u64_at};
+
+const INITIAL_MAGIC: u64 = 0xFCFB_6D1B_A772_5C30;
+const FINAL_MAGIC: u64 = 0xF4FA_6F45_970D_41D8;
+/// `SimpleFileHeader`, magic, version, key length, key hash
+const HEADER_SIZE: usize = 24;
+/// `SimpleFileEOF`, magic, flags, crc32, stream size
+const EOF_SIZE: usize = 24;
+/// The SHA-256 of the key follows stream 0
+const FLAG_HAS_KEY_SHA256: u32 = 2;
+
+/// The key and streams 0 and 1 of a `<hash>_0` file
+pub fn read_entry(data: &[u8]) -> Option<(&[u8], &[u8], &[u8])> {
+    if u64_at(data, 0)? != INITIAL_MAGIC {
+        return None;
+    }
+    let key_end = HEADER_SIZE.checked_add(u32_at(data, 12)? as usize)?;
+
+    let eof0 = data.len().checked_sub(EOF_SIZE)?;
+    if u64_at(data, eof0)? != FINAL_MAGIC {
+        return None;
+    }
+    let sha = if u32_at(data, eof0 + 8)? & FLAG_HAS_KEY_SHA256 == 0 {
+        0
+    }
+    else {
+        32
+    };
+    let stream0_end = eof0.checked_sub(sha)?;
+    let stream0_start = stream0_end.checked_sub(u32_at(data, eof0 + 16)? as usize)?;
+
+    let eof1 = stream0_start.checked_sub(EOF_SIZE)?;
+    if u64_at(data, eof1)? != FINAL_MAGIC {
+        return None;
+    }
+
+    Some((
+        data.get(HEADER_SIZE..key_end)?,
+        data.get(stream0_start..stream0_end)?,
+        data.get(key_end..eof1)?,
+    ))
+}
+
+/// Entry files are named by 16 hex digits of the key hash
+fn is_entry_file(name: &str) -> bool {
+    name.strip_suffix("_0")
+        .is_some_and(|v| {
+            v.len() == 16
+                && v.bytes()
+                    .all(|v| v.is_ascii_hexdigit())
+        })
+}
+
+pub fn entries(dir: &Path) -> Result<Vec<CacheEntry>> {
+    let read_dir = std::fs::read_dir(dir).with_context(|_| IoSnafu { path: dir.to_owned() })?;
+
+    let mut res = vec![];
+    for entry in read_dir {
+        let entry = entry.with_context(|_| IoSnafu { path: dir.to_owned() })?;
+        if !entry
+            .file_name()
+            .to_str()
+            .is_some_and(is_entry_file)
+        {
+            continue;
+        }
+        let path = entry.path();
+        let data = std::fs::read(&path).with_context(|_| IoSnafu { path })?;
+        let Some((key, stream0, stream1)) = read_entry(&data)
+        else {
+            continue;
+        };
+
+        let mut entry = CacheEntry {
+            key: String::from_utf8_lossy(key).into_owned(),
+            body: stream1.to_vec(),
+            ..Default::default()
+        };
+        _ = entry.read_response_info(stream0);
+        res.push(entry);
+    }
+    res.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(res)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn eof(flags: u32, size: usize) -> Vec<u8> {
+        let mut res = FINAL_MAGIC.to_le_bytes().to_vec();
+        res.extend_from_slice(&flags.to_le_bytes());
+        res.extend_from_slice(&0_u32.to_le_bytes());
+        res.extend_from_slice(&(size as u32).to_le_bytes());
+        res.extend_from_slice(&[0; 4]);
+        res
+    }
+
+    pub fn build_entry(key: &str, stream0: &[u8], stream1: &[u8]) -> Vec<u8> {
+        let mut res = INITIAL_MAGIC
+            .to_le_bytes()
+            .to_vec();
+        res.extend_from_slice(&5_u32.to_le_bytes());
+        res.extend_from_slice(&(key.len() as u32).to_le_bytes());
+        res.extend_from_slice(&[0; 8]);
+        res.extend_from_slice(key.as_bytes());
+        res.extend_from_slice(stream1);
+        res.extend(eof(1, stream1.len()));
+        res.extend_from_slice(stream0);
+        res.extend_from_slice(&[0xAA; 32]);
+        res.extend(eof(1 | FLAG_HAS_KEY_SHA256, stream0.len()));
+        res
+    }
+
+    #[test]
+    fn entry() {
+        let data = build_entry("1/0/https://a.com/", b"headers", b"<html>");
+        assert_eq!(
+            read_entry(&data),
+            Some((&b"1/0/https://a.com/"[..], &b"headers"[..], &b"<html>"[..]))
+        );
+        assert_eq!(read_entry(&data[..data.len() - 1]), None);
+        assert!(is_entry_file("0123456789abcdef_0"));
+        assert!(!is_entry_file("0123456789abcdef_1"));
+    }
+}
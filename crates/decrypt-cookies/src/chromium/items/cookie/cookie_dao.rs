@@ -1,14 +1,15 @@
 use std::path::Path;
 
-use sea_orm::{
-    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, sea_query::IntoCondition,
-};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, QueryFilter, sea_query::IntoCondition};
 
-use super::cookie_entities::{
-    cookies::{self, Model},
-    prelude::*,
+use super::{
+    cookie_entities::cookies::{self, Model},
+    cookie_writer::HOST_KEY_DIGEST_VERSION,
+};
+use crate::utils::{
+    connect_db,
+    schema::{Schema, meta_version},
 };
-use crate::utils::connect_db;
 
 type Result<T> = std::result::Result<T, DbErr>;
 
@@ -17,12 +18,21 @@ type Result<T> = std::result::Result<T, DbErr>;
 #[derive(Default)]
 pub struct CookiesQuery {
     conn: DatabaseConnection,
+    version: i64,
+    schema: Schema<cookies::Entity>,
 }
 
 impl CookiesQuery {
     pub async fn new<P: AsRef<Path> + Send>(path: P) -> Result<Self> {
         let db = connect_db(&path).await?;
-        Ok(Self { conn: db })
+        let version = meta_version(&db).await?;
+        let schema = Schema::read(&db).await?;
+        Ok(Self { conn: db, version, schema })
+    }
+
+    /// Whether the plaintext of `encrypted_value` starts with the SHA-256 of `host_key`
+    pub const fn host_key_digest(&self) -> bool {
+        self.version >= HOST_KEY_DIGEST_VERSION
     }
 
     /// get raw Cookies
@@ -30,7 +40,8 @@ impl CookiesQuery {
     where
         F: IntoCondition + Send,
     {
-        Cookies::find()
+        self.schema
+            .find()
             .filter(filter)
             .all(&self.conn)
             .await
@@ -38,7 +49,8 @@ impl CookiesQuery {
 
     /// get raw Cookies
     pub async fn cookies_by_host(&self, host: &str) -> Result<Vec<Model>> {
-        Cookies::find()
+        self.schema
+            .find()
             .filter(cookies::Column::HostKey.contains(host))
             .all(&self.conn)
             .await
@@ -46,8 +58,47 @@ impl CookiesQuery {
 
     /// get raw Cookies
     pub async fn cookies_all(&self) -> Result<Vec<Model>> {
-        Cookies::find()
+        self.schema
+            .find()
             .all(&self.conn)
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{browser::cookies::CookiesInfo, chromium::ChromiumCookie, utils::schema::fixture};
+
+    #[tokio::test]
+    async fn version_matrix() {
+        for version in [18, 21, 24] {
+            let name = format!("chromium_cookies_v{version}");
+            let query = CookiesQuery::new(fixture(&name).await)
+                .await
+                .unwrap();
+            assert_eq!(query.version, version, "{name}");
+            assert_eq!(query.host_key_digest(), version >= 24, "{name}");
+
+            let rows = query
+                .cookies_by_host("a.com")
+                .await
+                .unwrap();
+            let row = &rows[0];
+            assert_eq!(row.value, name[name.len() - 3..], "{name}");
+            assert_eq!(row.source_port, 443, "{name}");
+            let since = |v: i64| version >= v;
+            assert_eq!(row.last_update_utc != 0, since(21), "{name}");
+            assert_eq!(row.source_type, since(24).then_some(1), "{name}");
+            assert_eq!(
+                row.has_cross_site_ancestor,
+                since(24).then_some(1),
+                "{name}"
+            );
+
+            let cookie = ChromiumCookie::from(row.clone());
+            assert_eq!(cookie.in_partition(None), !since(24), "{name}");
+            assert!(cookie.in_partition(Some("https://www.b.com")), "{name}");
+        }
+    }
+}
@@ -30,8 +30,10 @@ pub struct Model {
     pub source_scheme: i32,
     pub source_port: i32,
     pub last_update_utc: i64,
-    // pub source_type:             i32,
-    // pub has_cross_site_ancestor: i32,
+    /// Since version 22
+    pub source_type: Option<i32>,
+    /// Since version 23
+    pub has_cross_site_ancestor: Option<i32>,
     // pub is_edgelegacycookie: i32,
     // pub browser_provenance:      i32,
 }
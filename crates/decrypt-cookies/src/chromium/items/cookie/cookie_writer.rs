@@ -0,0 +1,260 @@
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbErr, IdenStatic, ModelTrait, Statement, TransactionTrait,
+};
+
+use super::{CookiePriority, SourceScheme, cookie_entities::cookies};
+use crate::{
+    browser::cookies::{PartitionKey, SameSite},
+    chromium::items::{ChromiumDateTimeToI64, insert::Insert},
+    utils::{connect_db_rw, schema::meta_version},
+};
+
+type Result<T> = std::result::Result<T, DbErr>;
+
+/// `meta.version` since which the plaintext of `encrypted_value` starts with SHA-256 of `host_key`
+pub const HOST_KEY_DIGEST_VERSION: i64 = 24;
+
+// https://source.chromium.org/chromium/chromium/src/+/main:net/cookies/cookie_constants.h
+/// `CookieSourceType::kUnknown`
+const SOURCE_TYPE_UNKNOWN: i32 = 0;
+
+/// A cookie to write into `Cookies`, like a `Set-Cookie` header
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub struct SetCookie {
+    host_key: String,
+    name: String,
+    value: String,
+    path: String,
+    expires: Option<DateTime<Utc>>,
+    is_secure: bool,
+    is_httponly: bool,
+    same_site: SameSite,
+    source_port: Option<i32>,
+    partition_key: Option<PartitionKey>,
+}
+
+impl SetCookie {
+    /// A session cookie of path `/`, a leading `.` in `host_key` makes it a domain cookie
+    pub fn new<H, N, V>(host_key: H, name: N, value: V) -> Self
+    where
+        H: Into<String>,
+        N: Into<String>,
+        V: Into<String>,
+    {
+        Self {
+            host_key: host_key.into(),
+            name: name.into(),
+            value: value.into(),
+            path: "/".to_owned(),
+            expires: None,
+            is_secure: false,
+            is_httponly: false,
+            same_site: SameSite::Unspecified,
+            source_port: None,
+            partition_key: None,
+        }
+    }
+
+    #[must_use]
+    pub fn path<P: Into<String>>(mut self, path: P) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Persistent until `expires`
+    #[must_use]
+    pub const fn expires(mut self, expires: DateTime<Utc>) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    #[must_use]
+    pub const fn secure(mut self, secure: bool) -> Self {
+        self.is_secure = secure;
+        self
+    }
+
+    #[must_use]
+    pub const fn http_only(mut self, http_only: bool) -> Self {
+        self.is_httponly = http_only;
+        self
+    }
+
+    /// `Unspecified` by default, like a header without `SameSite`
+    #[must_use]
+    pub const fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = same_site;
+        self
+    }
+
+    /// Default to 443 for secure cookies, 80 otherwise
+    #[must_use]
+    pub const fn source_port(mut self, port: i32) -> Self {
+        self.source_port = Some(port);
+        self
+    }
+
+    /// A CHIPS cookie of the top level site of `partition_key`, it should be secure as well
+    #[must_use]
+    pub fn partitioned(mut self, partition_key: PartitionKey) -> Self {
+        self.partition_key = Some(partition_key);
+        self
+    }
+
+    pub fn host_key(&self) -> &str {
+        &self.host_key
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The row, `value` is left empty and `encrypted_value` holds the ciphertext
+    pub(crate) fn into_model(self, encrypted_value: Vec<u8>, now: DateTime<Utc>) -> cookies::Model {
+        let now = now.to_chromium_micros();
+        let (source_scheme, port) = if self.is_secure {
+            (SourceScheme::Secure, 443)
+        }
+        else {
+            (SourceScheme::NonSecure, 80)
+        };
+        let (top_frame_site_key, has_cross_site_ancestor) = self
+            .partition_key
+            .map_or_else(Default::default, |v| {
+                (v.top_level_site, v.cross_site_ancestor)
+            });
+        cookies::Model {
+            creation_utc: now,
+            host_key: self.host_key,
+            top_frame_site_key,
+            name: self.name,
+            value: String::new(),
+            encrypted_value,
+            path: self.path,
+            expires_utc: self
+                .expires
+                .as_ref()
+                .map_or(0, ChromiumDateTimeToI64::to_chromium_micros),
+            is_secure: self.is_secure.into(),
+            is_httponly: self.is_httponly.into(),
+            last_access_utc: now,
+            has_expires: self.expires.is_some().into(),
+            is_persistent: self.expires.is_some().into(),
+            priority: CookiePriority::Medium.into(),
+            samesite: self.same_site.into(),
+            source_scheme: source_scheme.into(),
+            source_port: self.source_port.unwrap_or(port),
+            last_update_utc: now,
+            source_type: Some(SOURCE_TYPE_UNKNOWN),
+            has_cross_site_ancestor: Some(has_cross_site_ancestor.into()),
+        }
+    }
+}
+
+/// Writes a `Cookies` database in place, the browser must not be running
+#[derive(Debug)]
+pub struct CookiesWriter {
+    conn: DatabaseConnection,
+}
+
+impl CookiesWriter {
+    pub async fn new<P: AsRef<Path> + Send>(path: P) -> Result<Self> {
+        let conn = connect_db_rw(&path).await?;
+        Ok(Self { conn })
+    }
+
+    /// `meta.version`, 0 when it is missing
+    pub async fn version(&self) -> Result<i64> {
+        meta_version(&self.conn).await
+    }
+
+    /// Replace the cookies with the same host, partition, name and path, in one transaction
+    pub async fn upsert(&self, rows: Vec<cookies::Model>) -> Result<()> {
+        let backend = self.conn.get_database_backend();
+        let insert = Insert::<cookies::Entity>::prepare(&self.conn, &[]).await?;
+        let keys: Vec<_> = [
+            cookies::Column::HostKey,
+            cookies::Column::TopFrameSiteKey,
+            cookies::Column::Name,
+            cookies::Column::Path,
+        ]
+        .into_iter()
+        .filter(|&v| insert.has(v))
+        .collect();
+        let delete = format!(
+            "DELETE FROM cookies WHERE {}",
+            keys.iter()
+                .map(|v| format!("{} = ?", v.as_str()))
+                .collect::<Vec<_>>()
+                .join(" AND ")
+        );
+
+        let txn = self.conn.begin().await?;
+        for row in rows {
+            txn.execute(Statement::from_sql_and_values(
+                backend,
+                &delete,
+                keys.iter().map(|&v| row.get(v)),
+            ))
+            .await?;
+            txn.execute(insert.statement(backend, &row))
+                .await?;
+        }
+        txn.commit().await
+    }
+
+    pub async fn close(self) -> Result<()> {
+        self.conn.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::chromium::items::I64ToChromiumDateTime;
+
+    #[test]
+    fn into_model() {
+        let now = Utc
+            .timestamp_opt(1_700_000_000, 0)
+            .unwrap();
+        let expires = now + chrono::Duration::days(1);
+        let model = SetCookie::new(".a.com", "sid", "secret")
+            .secure(true)
+            .same_site(SameSite::Lax)
+            .expires(expires)
+            .into_model(b"v10".to_vec(), now);
+
+        assert_eq!(
+            model
+                .creation_utc
+                .micros_to_chromium_utc(),
+            Some(now)
+        );
+        assert_eq!(
+            model
+                .expires_utc
+                .micros_to_chromium_utc(),
+            Some(expires)
+        );
+        assert_eq!(model.value, "");
+        assert_eq!((model.has_expires, model.is_persistent), (1, 1));
+        assert_eq!(
+            (model.source_scheme, model.source_port),
+            (SourceScheme::Secure.into(), 443)
+        );
+        assert_eq!(model.samesite, 1);
+
+        let session = SetCookie::new("a.com", "t", "v").into_model(vec![], now);
+        assert_eq!((session.expires_utc, session.has_expires), (0, 0));
+        assert_eq!(session.source_port, 80);
+        assert_eq!(session.samesite, -1);
+    }
+}
@@ -1,11 +1,165 @@
+use std::sync::{Arc, OnceLock};
+
+use chromium_crypto::{Decrypter, SecretString, error::CryptoError};
 use chrono::prelude::*;
 
 use self::cookie_entities::cookies;
 use super::I64ToChromiumDateTime;
-use crate::browser::cookies::{CookiesInfo, SameSite};
+use crate::browser::cookies::{CookiesInfo, PartitionKey, SameSite};
 
 pub mod cookie_dao;
 pub mod cookie_entities;
+pub mod cookie_writer;
+
+// https://source.chromium.org/chromium/chromium/src/+/main:net/cookies/cookie_constants.h
+/// `CookiePriority`, which cookies are evicted first
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CookiePriority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Other(i32),
+}
+
+impl From<i32> for CookiePriority {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Low,
+            1 => Self::Medium,
+            2 => Self::High,
+            v => Self::Other(v),
+        }
+    }
+}
+
+impl From<CookiePriority> for i32 {
+    fn from(value: CookiePriority) -> Self {
+        match value {
+            CookiePriority::Low => 0,
+            CookiePriority::Medium => 1,
+            CookiePriority::High => 2,
+            CookiePriority::Other(v) => v,
+        }
+    }
+}
+
+/// `CookieSourceScheme`, whether the cookie was set over a secure scheme
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SourceScheme {
+    /// Set before Chromium kept track of it
+    #[default]
+    Unset,
+    NonSecure,
+    Secure,
+    Other(i32),
+}
+
+impl From<i32> for SourceScheme {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Unset,
+            1 => Self::NonSecure,
+            2 => Self::Secure,
+            v => Self::Other(v),
+        }
+    }
+}
+
+impl From<SourceScheme> for i32 {
+    fn from(value: SourceScheme) -> Self {
+        match value {
+            SourceScheme::Unset => 0,
+            SourceScheme::NonSecure => 1,
+            SourceScheme::Secure => 2,
+            SourceScheme::Other(v) => v,
+        }
+    }
+}
+
+/// The port the cookie was set from
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SourcePort {
+    /// `url::PORT_UNSPECIFIED`, set before Chromium kept track of it
+    #[default]
+    Unspecified,
+    /// `url::PORT_INVALID`
+    Invalid,
+    Port(u16),
+    Other(i32),
+}
+
+impl From<i32> for SourcePort {
+    fn from(value: i32) -> Self {
+        match value {
+            -1 => Self::Unspecified,
+            -2 => Self::Invalid,
+            v => u16::try_from(v).map_or(Self::Other(v), Self::Port),
+        }
+    }
+}
+
+impl From<SourcePort> for i32 {
+    fn from(value: SourcePort) -> Self {
+        match value {
+            SourcePort::Unspecified => -1,
+            SourcePort::Invalid => -2,
+            SourcePort::Port(v) => v.into(),
+            SourcePort::Other(v) => v,
+        }
+    }
+}
+
+/// Why the value of a cookie could not be decrypted
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecryptError {
+    /// The plaintext doesn't start with the SHA-256 of `host_key`,
+    /// the value was copied from another cookie
+    HostKeyDigest,
+    /// The message of any other error
+    Other(String),
+}
+
+impl From<&CryptoError> for DecryptError {
+    fn from(value: &CryptoError) -> Self {
+        match value {
+            CryptoError::HostKeyDigest { .. } => Self::HostKeyDigest,
+            e => Self::Other(e.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for DecryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HostKeyDigest => f.write_str("The value doesn't belong to the host key"),
+            Self::Other(v) => f.write_str(v),
+        }
+    }
+}
 
 #[derive(Clone)]
 #[derive(Debug)]
@@ -19,7 +173,10 @@ pub struct ChromiumCookie {
     pub top_frame_site_key: String,
     pub name: String,
     pub value: String,
-    pub decrypted_value: Option<String>,
+    pub decrypted_value: Option<SecretString>,
+    /// Set when `decrypted_value` is `None` because the decryption failed,
+    /// [`CookiesInfo::value`] falls back to the empty `value` then
+    pub decrypt_error: Option<DecryptError>,
     pub path: String,
     /// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Expires>
     pub expires_utc: Option<DateTime<Utc>>,
@@ -28,24 +185,27 @@ pub struct ChromiumCookie {
     pub last_access_utc: Option<DateTime<Utc>>,
     pub has_expires: bool,
     pub is_persistent: bool,
-    pub priority: i32,
+    pub priority: CookiePriority,
     pub same_site: SameSite,
-    pub source_scheme: i32,
-    pub source_port: i32,
+    pub source_scheme: SourceScheme,
+    pub source_port: SourcePort,
     pub last_update_utc: Option<DateTime<Utc>>,
+    /// `CookieSourceType`, how the cookie was set, since version 22 of `Cookies`
+    pub source_type: Option<i32>,
+    /// Set in a cross-site context, since version 23 of `Cookies`
+    pub has_cross_site_ancestor: Option<bool>,
+    /// The CHIPS partition from `top_frame_site_key`, `None` for an unpartitioned cookie
+    pub partition_key: Option<PartitionKey>,
 }
 
+/// Add the unpartitioned cookies to `jar`,
+/// see [`jar_extend`](crate::browser::cookies::jar_extend) for the partitioned ones
 #[cfg(feature = "reqwest")]
 pub fn jar_extend_chromium<'a, I>(jar: &reqwest::cookie::Jar, cks: I)
 where
     I: IntoIterator<Item = &'a ChromiumCookie>,
 {
-    for cookie in cks {
-        let set_cookie = cookie.set_cookie_header();
-        if let Ok(url) = reqwest::Url::parse(&cookie.url()) {
-            jar.add_cookie_str(&set_cookie, &url);
-        }
-    }
+    crate::browser::cookies::jar_extend(jar, cks, None);
 }
 
 #[cfg(feature = "reqwest")]
@@ -75,7 +235,7 @@ impl CookiesInfo for ChromiumCookie {
     fn value(&self) -> &str {
         self.decrypted_value
             .as_ref()
-            .unwrap_or(&self.value)
+            .map_or(&self.value, SecretString::expose_secret)
     }
     fn domain(&self) -> &str {
         &self.host_key
@@ -101,10 +261,157 @@ impl CookiesInfo for ChromiumCookie {
     fn expires(&self) -> Option<DateTime<Utc>> {
         self.expires_utc
     }
+
+    fn partition_key(&self) -> Option<&PartitionKey> {
+        self.partition_key.as_ref()
+    }
+}
+
+/// A [`ChromiumCookie`] that keeps the ciphertext and decrypts it on first access.
+///
+/// The decrypted value is cached, so the work is done at most once per cookie.
+#[derive(Clone)]
+pub struct LazyChromiumCookie {
+    cookie: ChromiumCookie,
+    encrypted_value: Vec<u8>,
+    crypto: Arc<Decrypter>,
+    host_key_digest: bool,
+    decrypted_value: OnceLock<Result<SecretString, DecryptError>>,
+}
+
+impl std::fmt::Debug for LazyChromiumCookie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyChromiumCookie")
+            .field("cookie", &self.cookie)
+            .field(
+                "decrypted",
+                &self
+                    .decrypted_value
+                    .get()
+                    .is_some(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl LazyChromiumCookie {
+    /// `host_key_digest`, see [`CookiesQuery::host_key_digest`](cookie_dao::CookiesQuery::host_key_digest)
+    pub(crate) fn new(
+        mut value: cookies::Model,
+        crypto: Arc<Decrypter>,
+        host_key_digest: bool,
+    ) -> Self {
+        let encrypted_value = std::mem::take(&mut value.encrypted_value);
+        Self {
+            cookie: ChromiumCookie::from(value),
+            encrypted_value,
+            crypto,
+            host_key_digest,
+            decrypted_value: OnceLock::new(),
+        }
+    }
+
+    /// The cookie without the decrypted value
+    pub const fn cookie(&self) -> &ChromiumCookie {
+        &self.cookie
+    }
+
+    pub fn encrypted_value(&self) -> &[u8] {
+        &self.encrypted_value
+    }
+
+    /// Decrypt the value if it has not been decrypted yet
+    pub fn decrypted_value(&self) -> Option<&str> {
+        self.try_decrypted_value().ok()
+    }
+
+    /// Like [`LazyChromiumCookie::decrypted_value`], with why it failed
+    pub fn try_decrypted_value(&self) -> Result<&str, &DecryptError> {
+        self.decrypted_value
+            .get_or_init(|| {
+                let mut buf = self.encrypted_value.clone();
+                self.crypto
+                    .decrypt_cookie(
+                        &mut buf,
+                        self.host_key_digest
+                            .then_some(self.cookie.host_key.as_str()),
+                    )
+                    .inspect_err(|_e| {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(error = %_e, name = self.cookie.name, "decrypt cookie failed");
+                    })
+                    .map_err(|e| DecryptError::from(&e))
+            })
+            .as_ref()
+            .map(SecretString::expose_secret)
+    }
+
+    /// Decrypt and convert to [`ChromiumCookie`]
+    pub fn into_cookie(self) -> ChromiumCookie {
+        self.decrypted_value();
+        let Self { mut cookie, decrypted_value, .. } = self;
+        match decrypted_value.into_inner() {
+            Some(Ok(v)) => cookie.decrypted_value = Some(v),
+            Some(Err(e)) => cookie.decrypt_error = Some(e),
+            None => {},
+        }
+        cookie
+    }
+}
+
+impl CookiesInfo for LazyChromiumCookie {
+    fn name(&self) -> &str {
+        &self.cookie.name
+    }
+    fn path(&self) -> &str {
+        &self.cookie.path
+    }
+    fn value(&self) -> &str {
+        self.decrypted_value()
+            .unwrap_or(&self.cookie.value)
+    }
+    fn domain(&self) -> &str {
+        &self.cookie.host_key
+    }
+    fn expiry(&self) -> Option<String> {
+        self.cookie.expiry()
+    }
+    fn is_secure(&self) -> bool {
+        self.cookie.is_secure
+    }
+    fn same_site(&self) -> SameSite {
+        self.cookie.same_site
+    }
+    fn is_http_only(&self) -> bool {
+        self.cookie.is_httponly
+    }
+
+    fn creation(&self) -> Option<DateTime<Utc>> {
+        self.cookie.creation_utc
+    }
+
+    fn expires(&self) -> Option<DateTime<Utc>> {
+        self.cookie.expires_utc
+    }
+
+    fn partition_key(&self) -> Option<&PartitionKey> {
+        self.cookie.partition_key.as_ref()
+    }
+}
+
+impl From<LazyChromiumCookie> for ChromiumCookie {
+    fn from(value: LazyChromiumCookie) -> Self {
+        value.into_cookie()
+    }
 }
 
 impl From<cookies::Model> for ChromiumCookie {
     fn from(value: cookies::Model) -> Self {
+        let has_cross_site_ancestor = value
+            .has_cross_site_ancestor
+            .map(|v| v != 0);
+        let partition_key =
+            PartitionKey::from_chromium(&value.top_frame_site_key, has_cross_site_ancestor);
         Self {
             creation_utc: value
                 .creation_utc
@@ -114,6 +421,7 @@ impl From<cookies::Model> for ChromiumCookie {
             name: value.name,
             value: value.value,
             decrypted_value: None,
+            decrypt_error: None,
             path: value.path,
             expires_utc: value
                 .expires_utc
@@ -125,13 +433,121 @@ impl From<cookies::Model> for ChromiumCookie {
                 .micros_to_chromium_utc(),
             has_expires: value.has_expires != 0,
             is_persistent: value.is_persistent != 0,
-            priority: value.priority,
+            priority: value.priority.into(),
             same_site: value.samesite.into(),
-            source_scheme: value.source_scheme,
-            source_port: value.source_port,
+            source_scheme: value.source_scheme.into(),
+            source_port: value.source_port.into(),
             last_update_utc: value
                 .last_update_utc
                 .micros_to_chromium_utc(),
+            source_type: value.source_type,
+            has_cross_site_ancestor,
+            partition_key,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> cookies::Model {
+        cookies::Model {
+            creation_utc: 0,
+            host_key: ".example.com".to_owned(),
+            top_frame_site_key: String::new(),
+            name: "sid".to_owned(),
+            value: String::new(),
+            encrypted_value: b"plain".to_vec(),
+            path: "/".to_owned(),
+            expires_utc: 0,
+            is_secure: 0,
+            is_httponly: 0,
+            last_access_utc: 0,
+            has_expires: 0,
+            is_persistent: 0,
+            priority: 1,
+            samesite: 0,
+            source_scheme: 2,
+            source_port: 443,
+            last_update_utc: 0,
+            source_type: None,
+            has_cross_site_ancestor: None,
         }
     }
+
+    #[test]
+    fn typed_columns() {
+        let mut model = model();
+        let cookie = ChromiumCookie::from(model.clone());
+        assert_eq!(cookie.priority, CookiePriority::Medium);
+        assert_eq!(cookie.same_site, SameSite::None);
+        assert_eq!(cookie.source_scheme, SourceScheme::Secure);
+        assert_eq!(cookie.source_port, SourcePort::Port(443));
+
+        model.priority = 7;
+        model.samesite = -1;
+        model.source_port = -1;
+        model.source_scheme = 9;
+        let cookie = ChromiumCookie::from(model);
+        assert_eq!(cookie.priority, CookiePriority::Other(7));
+        assert_eq!(i32::from(cookie.priority), 7);
+        assert_eq!(cookie.same_site, SameSite::Unspecified);
+        assert_eq!(cookie.source_port, SourcePort::Unspecified);
+        assert_eq!(i32::from(cookie.source_scheme), 9);
+        assert!(
+            !cookie
+                .set_cookie_header()
+                .contains("SameSite")
+        );
+        assert_eq!(SourcePort::from(70_000), SourcePort::Other(70_000));
+    }
+
+    #[test]
+    fn lazy_cookie_decrypt_once() {
+        let model = model();
+        let lazy = LazyChromiumCookie::new(model, Arc::default(), false);
+        assert!(
+            lazy.decrypted_value
+                .get()
+                .is_none()
+        );
+
+        let first = lazy.decrypted_value();
+        assert_eq!(first, Some("plain"));
+        assert!(std::ptr::eq(
+            first.unwrap(),
+            lazy.decrypted_value().unwrap()
+        ));
+
+        let cookie = lazy.into_cookie();
+        assert_eq!(
+            cookie
+                .decrypted_value
+                .as_ref()
+                .map(SecretString::expose_secret),
+            Some("plain")
+        );
+    }
+
+    #[test]
+    fn lazy_cookie_host_key_digest() {
+        let crypto = Arc::new(Decrypter::default());
+        let mut model = model();
+        model.encrypted_value = crypto
+            .encrypt_cookie("secret", Some(".example.com"))
+            .unwrap();
+        let lazy = LazyChromiumCookie::new(model.clone(), Arc::clone(&crypto), true);
+        assert_eq!(lazy.decrypted_value(), Some("secret"));
+
+        model.host_key = ".evil.com".to_owned();
+        let lazy = LazyChromiumCookie::new(model, crypto, true);
+        assert_eq!(lazy.decrypted_value(), None);
+        assert_eq!(
+            lazy.try_decrypted_value(),
+            Err(&DecryptError::HostKeyDigest)
+        );
+        let cookie = lazy.into_cookie();
+        assert_eq!(cookie.decrypt_error, Some(DecryptError::HostKeyDigest));
+    }
 }
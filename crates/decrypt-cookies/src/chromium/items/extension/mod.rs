@@ -0,0 +1,433 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::I64ToChromiumDateTime;
+
+/// An extension installed in a profile
+///
+/// <https://source.chromium.org/chromium/chromium/src/+/main:extensions/browser/extension_prefs.cc>
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Extension {
+    /// 32 letters `a`-`p`
+    pub id: String,
+    /// `__MSG_*__` names are resolved with the default locale
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub manifest_version: Option<i64>,
+    /// API permissions like `tabs` and `cookies`, declared or granted
+    pub permissions: Vec<String>,
+    /// Match patterns like `https://*/*` and `<all_urls>`, declared or granted
+    pub host_permissions: Vec<String>,
+    pub optional_permissions: Vec<String>,
+    pub enabled: bool,
+    /// `extensions::disable_reason::DisableReason` values, empty when enabled
+    pub disable_reasons: Vec<i64>,
+    pub location: ExtensionLocation,
+    pub from_webstore: bool,
+    /// Installed with the browser or by the OEM
+    pub installed_by_default: bool,
+    pub install_time: Option<DateTime<Utc>>,
+    pub update_url: Option<String>,
+    /// The directory of the unpacked version, `None` for component extensions
+    pub path: Option<PathBuf>,
+}
+
+/// `mojom::ManifestLocation`, where an extension was installed from
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExtensionLocation {
+    /// The web store or a crx opened by the user
+    Internal,
+    /// An `External Extensions` json file
+    ExternalPref,
+    /// The Windows registry
+    ExternalRegistry,
+    /// Loaded unpacked in developer mode
+    Unpacked,
+    /// Built into the browser
+    Component,
+    ExternalPrefDownload,
+    /// Force installed by enterprise policy
+    ExternalPolicyDownload,
+    /// `--load-extension`
+    CommandLine,
+    ExternalPolicy,
+    ExternalComponent,
+    Other(i32),
+}
+
+impl From<i32> for ExtensionLocation {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Internal,
+            2 => Self::ExternalPref,
+            3 => Self::ExternalRegistry,
+            4 => Self::Unpacked,
+            5 => Self::Component,
+            6 => Self::ExternalPrefDownload,
+            7 => Self::ExternalPolicyDownload,
+            8 => Self::CommandLine,
+            9 => Self::ExternalPolicy,
+            10 => Self::ExternalComponent,
+            v => Self::Other(v),
+        }
+    }
+}
+
+/// An entry of `extensions.settings`
+#[derive(Deserialize)]
+struct Settings {
+    #[serde(default)]
+    location: i32,
+    /// Gone since disable reasons tell it
+    state: Option<i32>,
+    #[serde(default)]
+    disable_reasons: DisableReasons,
+    #[serde(default)]
+    from_webstore: bool,
+    #[serde(default)]
+    was_installed_by_default: bool,
+    #[serde(default)]
+    was_installed_by_oem: bool,
+    /// Microseconds as a string
+    install_time: Option<String>,
+    /// Relative to `Extensions`, absolute when unpacked
+    path: Option<String>,
+    /// Component extensions keep the manifest here
+    manifest: Option<Manifest>,
+    #[serde(default)]
+    active_permissions: ActivePermissions,
+}
+
+/// A bit mask, or since M131 a list of the reasons
+#[derive(Deserialize)]
+#[derive(Default)]
+#[serde(untagged)]
+enum DisableReasons {
+    #[default]
+    None,
+    Mask(i64),
+    List(Vec<i64>),
+}
+
+impl DisableReasons {
+    fn into_vec(self) -> Vec<i64> {
+        match self {
+            Self::None => vec![],
+            Self::Mask(mask) => (0..63)
+                .map(|v| 1 << v)
+                .filter(|v| mask & v != 0)
+                .collect(),
+            Self::List(v) => v,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[derive(Default)]
+struct ActivePermissions {
+    #[serde(default)]
+    api: Vec<Value>,
+    #[serde(default)]
+    explicit_host: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[derive(Default)]
+struct Manifest {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    description: String,
+    manifest_version: Option<i64>,
+    default_locale: Option<String>,
+    /// Before manifest v3 host permissions are here too
+    #[serde(default)]
+    permissions: Vec<Value>,
+    #[serde(default)]
+    host_permissions: Vec<String>,
+    #[serde(default)]
+    optional_permissions: Vec<Value>,
+    update_url: Option<String>,
+}
+
+/// Permissions with arguments are objects like `{"socket": [..]}`
+fn permission_name(value: &Value) -> Option<&str> {
+    match value {
+        Value::String(v) => Some(v),
+        Value::Object(v) => v.keys().next().map(String::as_str),
+        _ => None,
+    }
+}
+
+fn is_host_pattern(permission: &str) -> bool {
+    permission == "<all_urls>" || permission.contains("://")
+}
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|v| v == value) {
+        list.push(value.to_owned());
+    }
+}
+
+/// `1.2.3_0` as numbers, to find the newest version directory
+fn version_key(name: &str) -> Vec<u64> {
+    name.split(['.', '_'])
+        .map(|v| v.parse().unwrap_or_default())
+        .collect()
+}
+
+/// `__MSG_appName__` from `_locales/<default_locale>/messages.json`, the message names are case insensitive
+fn localize(text: &mut String, messages: &HashMap<String, String>) {
+    if let Some(name) = text
+        .strip_prefix("__MSG_")
+        .and_then(|v| v.strip_suffix("__"))
+        && let Some(message) = messages.get(&name.to_ascii_lowercase())
+    {
+        message.clone_into(text);
+    }
+}
+
+fn read_messages(dir: &Path, locale: &str) -> HashMap<String, String> {
+    #[derive(Deserialize)]
+    struct Message {
+        #[serde(default)]
+        message: String,
+    }
+
+    let path = dir
+        .join("_locales")
+        .join(locale)
+        .join("messages.json");
+    std::fs::read(path)
+        .ok()
+        .and_then(|v| serde_json::from_slice::<HashMap<String, Message>>(strip_bom(&v)).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(k, v)| (k.to_ascii_lowercase(), v.message))
+        .collect()
+}
+
+fn strip_bom(data: &[u8]) -> &[u8] {
+    data.strip_prefix(b"\xEF\xBB\xBF")
+        .unwrap_or(data)
+}
+
+/// The directory of the installed version
+fn version_dir(id: &str, settings: &Settings, extensions_dir: &Path) -> Option<PathBuf> {
+    if let Some(path) = &settings.path {
+        let path = Path::new(path);
+        return Some(if path.is_absolute() {
+            path.to_owned()
+        }
+        else {
+            extensions_dir.join(path)
+        })
+        .filter(|v| v.is_dir());
+    }
+    std::fs::read_dir(extensions_dir.join(id))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|v| v.path())
+        .filter(|v| v.is_dir())
+        .max_by_key(|v| {
+            v.file_name()
+                .and_then(|v| v.to_str())
+                .map(version_key)
+        })
+}
+
+/// `extensions.settings` of a preferences file
+fn settings(json: &[u8]) -> serde_json::Result<Map<String, Value>> {
+    let mut prefs: Value = serde_json::from_slice(json)?;
+    Ok(
+        match prefs
+            .pointer_mut("/extensions/settings")
+            .map(Value::take)
+        {
+            Some(Value::Object(v)) => v,
+            _ => Map::new(),
+        },
+    )
+}
+
+impl Extension {
+    fn new(id: String, settings: Settings, extensions_dir: &Path) -> Self {
+        let path = version_dir(&id, &settings, extensions_dir);
+        let manifest = path
+            .as_ref()
+            .and_then(|v| std::fs::read(v.join("manifest.json")).ok())
+            .and_then(|v| serde_json::from_slice::<Manifest>(strip_bom(&v)).ok())
+            .or(settings.manifest)
+            .unwrap_or_default();
+
+        let mut name = manifest.name;
+        let mut description = manifest.description;
+        if let (Some(dir), Some(locale)) = (&path, &manifest.default_locale) {
+            let messages = read_messages(dir, locale);
+            localize(&mut name, &messages);
+            localize(&mut description, &messages);
+        }
+
+        let mut permissions = vec![];
+        let mut host_permissions = vec![];
+        let declared = manifest
+            .permissions
+            .iter()
+            .chain(&settings.active_permissions.api)
+            .filter_map(permission_name);
+        for permission in declared {
+            if is_host_pattern(permission) {
+                push_unique(&mut host_permissions, permission);
+            }
+            else {
+                push_unique(&mut permissions, permission);
+            }
+        }
+        for host in manifest
+            .host_permissions
+            .iter()
+            .chain(
+                &settings
+                    .active_permissions
+                    .explicit_host,
+            )
+        {
+            push_unique(&mut host_permissions, host);
+        }
+
+        let disable_reasons = settings.disable_reasons.into_vec();
+        Self {
+            id,
+            name,
+            version: manifest.version,
+            description,
+            manifest_version: manifest.manifest_version,
+            permissions,
+            host_permissions,
+            optional_permissions: manifest
+                .optional_permissions
+                .iter()
+                .filter_map(permission_name)
+                .map(ToOwned::to_owned)
+                .collect(),
+            enabled: disable_reasons.is_empty() && settings.state != Some(0),
+            disable_reasons,
+            location: settings.location.into(),
+            from_webstore: settings.from_webstore,
+            installed_by_default: settings.was_installed_by_default
+                || settings.was_installed_by_oem,
+            install_time: settings
+                .install_time
+                .and_then(|v| v.parse::<i64>().ok())
+                .and_then(|v| v.micros_to_chromium_utc()),
+            update_url: manifest.update_url,
+            path,
+        }
+    }
+}
+
+/// `extensions.settings` of `Preferences` merged with `Secure Preferences`,
+/// which holds them on Windows and macOS, and the manifests in `extensions_dir`
+pub fn from_preferences(
+    preferences: &[u8],
+    secure_preferences: Option<&[u8]>,
+    extensions_dir: &Path,
+) -> serde_json::Result<Vec<Extension>> {
+    let mut merged = settings(preferences)?;
+    if let Some(json) = secure_preferences {
+        for (id, secure) in settings(json)? {
+            match (merged.get_mut(&id), secure) {
+                (Some(Value::Object(own)), Value::Object(secure)) => own.extend(secure),
+                (_, secure) => _ = merged.insert(id, secure),
+            }
+        }
+    }
+
+    let mut res: Vec<_> = merged
+        .into_iter()
+        .filter_map(|(id, settings)| {
+            let settings = serde_json::from_value(settings).ok()?;
+            Some(Extension::new(id, settings, extensions_dir))
+        })
+        .collect();
+    res.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extensions() {
+        let dir = std::env::temp_dir().join("decrypt-cookies-extension-test");
+        _ = std::fs::remove_dir_all(&dir);
+        let id = "aapocclcgogkmnckokdopfmhonfmgoek";
+        let version = dir.join(id).join("1.10.0_0");
+        std::fs::create_dir_all(version.join("_locales/en")).unwrap();
+        std::fs::create_dir_all(dir.join(id).join("1.9.0_0")).unwrap();
+        std::fs::write(
+            version.join("manifest.json"),
+            br#"{"name":"__MSG_APP_NAME__","version":"1.10.0","manifest_version":2,"default_locale":"en",
+                "permissions":["tabs",{"socket":["tcp-connect"]},"https://*.a.com/*"],
+                "update_url":"https://clients2.google.com/service/update2/crx"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            version.join("_locales/en/messages.json"),
+            b"\xEF\xBB\xBF{\"app_name\":{\"message\":\"Slides\"}}",
+        )
+        .unwrap();
+
+        let prefs = format!(
+            r#"{{"extensions":{{"settings":{{"{id}":{{"location":1,"from_webstore":true,"install_time":"13340000000000000",
+                "active_permissions":{{"api":["tabs","cookies"],"explicit_host":["<all_urls>"]}}}},
+                "mhjfbmdgcfjbbpaeojofohoefgiehjai":{{"location":5,"manifest":{{"name":"PDF Viewer","version":"1"}}}}}}}}}}"#
+        );
+        let secure =
+            format!(r#"{{"extensions":{{"settings":{{"{id}":{{"disable_reasons":[1]}}}}}}}}"#);
+        let extensions = from_preferences(prefs.as_bytes(), Some(secure.as_bytes()), &dir).unwrap();
+        _ = std::fs::remove_dir_all(&dir);
+
+        let [slides, pdf] = extensions.as_slice()
+        else {
+            panic!("{extensions:?}");
+        };
+        assert_eq!(slides.name, "Slides");
+        assert_eq!(slides.version, "1.10.0");
+        assert_eq!(slides.permissions, ["tabs", "socket", "cookies"]);
+        assert_eq!(slides.host_permissions, ["https://*.a.com/*", "<all_urls>"]);
+        assert!(!slides.enabled);
+        assert_eq!(slides.disable_reasons, [1]);
+        assert_eq!(slides.location, ExtensionLocation::Internal);
+        assert_eq!(
+            slides.install_time,
+            13_340_000_000_000_000.micros_to_chromium_utc()
+        );
+        assert_eq!(slides.path, Some(version));
+
+        assert_eq!(pdf.name, "PDF Viewer");
+        assert_eq!(pdf.location, ExtensionLocation::Component);
+        assert!(pdf.enabled);
+        assert_eq!(pdf.path, None);
+
+        assert_eq!(DisableReasons::Mask(0b101).into_vec(), [1, 4]);
+    }
+}
@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    sea_query::IntoCondition,
+};
+
+use super::history_entities::{
+    downloads, downloads_url_chains, keyword_search_terms, prelude::*, urls, visits,
+};
+use crate::utils::connect_db;
+
+type Result<T> = std::result::Result<T, DbErr>;
+
+/// Stay below the sqlite limit of bound parameters
+const IDS_PER_QUERY: usize = 10_000;
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct HistoryQuery {
+    conn: DatabaseConnection,
+}
+
+impl HistoryQuery {
+    pub async fn new<P: AsRef<Path> + Send>(path: P) -> Result<Self> {
+        let db = connect_db(&path).await?;
+        Ok(Self { conn: db })
+    }
+
+    pub async fn urls_filter<F>(&self, filter: F) -> Result<Vec<urls::Model>>
+    where
+        F: IntoCondition + Send,
+    {
+        Urls::find()
+            .filter(filter)
+            .all(&self.conn)
+            .await
+    }
+
+    pub async fn urls_by_ids(&self, ids: &[i64]) -> Result<Vec<urls::Model>> {
+        let mut res = Vec::with_capacity(ids.len());
+        for ids in ids.chunks(IDS_PER_QUERY) {
+            let part = Urls::find()
+                .filter(urls::Column::Id.is_in(ids.iter().copied()))
+                .all(&self.conn)
+                .await?;
+            res.extend(part);
+        }
+        Ok(res)
+    }
+
+    pub async fn visits_filter<F>(&self, filter: F) -> Result<Vec<visits::Model>>
+    where
+        F: IntoCondition + Send,
+    {
+        Visits::find()
+            .filter(filter)
+            .order_by_asc(visits::Column::VisitTime)
+            .all(&self.conn)
+            .await
+    }
+
+    pub async fn visits_by_ids(&self, ids: &[i64]) -> Result<Vec<visits::Model>> {
+        let mut res = Vec::with_capacity(ids.len());
+        for ids in ids.chunks(IDS_PER_QUERY) {
+            let part = Visits::find()
+                .filter(visits::Column::Id.is_in(ids.iter().copied()))
+                .all(&self.conn)
+                .await?;
+            res.extend(part);
+        }
+        Ok(res)
+    }
+
+    /// The visits of the urls `url_ids`, ordered by visit time
+    pub async fn visits_by_url_ids(&self, url_ids: &[i64]) -> Result<Vec<visits::Model>> {
+        let mut res = Vec::with_capacity(url_ids.len());
+        for ids in url_ids.chunks(IDS_PER_QUERY) {
+            let part = Visits::find()
+                .filter(visits::Column::Url.is_in(ids.iter().copied()))
+                .all(&self.conn)
+                .await?;
+            res.extend(part);
+        }
+        res.sort_by_key(|v| v.visit_time);
+        Ok(res)
+    }
+
+    pub async fn downloads_filter<F>(&self, filter: F) -> Result<Vec<downloads::Model>>
+    where
+        F: IntoCondition + Send,
+    {
+        Downloads::find()
+            .filter(filter)
+            .order_by_asc(downloads::Column::StartTime)
+            .all(&self.conn)
+            .await
+    }
+
+    /// The redirect chains of the downloads, ordered by `chain_index`
+    pub async fn url_chains(&self, ids: &[i64]) -> Result<Vec<downloads_url_chains::Model>> {
+        let mut res = Vec::with_capacity(ids.len());
+        for ids in ids.chunks(IDS_PER_QUERY) {
+            let part = DownloadsUrlChains::find()
+                .filter(downloads_url_chains::Column::Id.is_in(ids.iter().copied()))
+                .order_by_asc(downloads_url_chains::Column::Id)
+                .order_by_asc(downloads_url_chains::Column::ChainIndex)
+                .all(&self.conn)
+                .await?;
+            res.extend(part);
+        }
+        Ok(res)
+    }
+
+    pub async fn keyword_search_terms_filter<F>(
+        &self,
+        filter: F,
+    ) -> Result<Vec<keyword_search_terms::Model>>
+    where
+        F: IntoCondition + Send,
+    {
+        KeywordSearchTerms::find()
+            .filter(filter)
+            .all(&self.conn)
+            .await
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use self::history_entities::{downloads, keyword_search_terms, urls, visits};
+use super::I64ToChromiumDateTime;
+
+pub mod history_dao;
+pub mod history_entities;
+
+/// A row of the `urls` table
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryUrl {
+    pub id: i64,
+    pub url: String,
+    pub title: String,
+    pub visit_count: i64,
+    pub typed_count: i64,
+    pub last_visit_time: Option<DateTime<Utc>>,
+    pub hidden: bool,
+}
+
+impl From<urls::Model> for HistoryUrl {
+    fn from(v: urls::Model) -> Self {
+        Self {
+            id: v.id,
+            url: v.url,
+            title: v.title,
+            visit_count: v.visit_count,
+            typed_count: v.typed_count,
+            last_visit_time: v
+                .last_visit_time
+                .micros_to_chromium_utc(),
+            hidden: v.hidden != 0,
+        }
+    }
+}
+
+/// The core type of a [`PageTransition`]
+///
+/// <https://source.chromium.org/chromium/chromium/src/+/main:ui/base/page_transition_types.h>
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransitionCore {
+    /// Clicked a link
+    Link,
+    /// Typed the URL in the omnibox
+    Typed,
+    /// Picked from the bookmarks or the new tab page
+    AutoBookmark,
+    /// Subframe navigation not requested by the user
+    AutoSubframe,
+    /// Subframe navigation requested by the user
+    ManualSubframe,
+    /// Picked a non-URL omnibox suggestion
+    Generated,
+    /// Toplevel navigation not requested by the user, e.g. command line
+    AutoToplevel,
+    FormSubmit,
+    Reload,
+    /// Keyword search from the omnibox
+    Keyword,
+    /// Visit generated for a keyword search
+    KeywordGenerated,
+    Other(u8),
+}
+
+impl From<u8> for TransitionCore {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Link,
+            1 => Self::Typed,
+            2 => Self::AutoBookmark,
+            3 => Self::AutoSubframe,
+            4 => Self::ManualSubframe,
+            5 => Self::Generated,
+            6 => Self::AutoToplevel,
+            7 => Self::FormSubmit,
+            8 => Self::Reload,
+            9 => Self::Keyword,
+            10 => Self::KeywordGenerated,
+            v => Self::Other(v),
+        }
+    }
+}
+
+/// The `transition` column of `visits`, a core type and qualifier bits
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageTransition(u32);
+
+impl PageTransition {
+    const CORE_MASK: u32 = 0xFF;
+
+    pub const BLOCKED: u32 = 0x0080_0000;
+    pub const FORWARD_BACK: u32 = 0x0100_0000;
+    pub const FROM_ADDRESS_BAR: u32 = 0x0200_0000;
+    pub const HOME_PAGE: u32 = 0x0400_0000;
+    pub const FROM_API: u32 = 0x0800_0000;
+    pub const CHAIN_START: u32 = 0x1000_0000;
+    pub const CHAIN_END: u32 = 0x2000_0000;
+    pub const CLIENT_REDIRECT: u32 = 0x4000_0000;
+    pub const SERVER_REDIRECT: u32 = 0x8000_0000;
+
+    pub const fn new(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+
+    pub fn core(self) -> TransitionCore {
+        TransitionCore::from((self.0 & Self::CORE_MASK) as u8)
+    }
+
+    /// Whether all bits of `qualifier` are set
+    pub const fn contains(self, qualifier: u32) -> bool {
+        self.0 & qualifier == qualifier
+    }
+
+    pub const fn is_redirect(self) -> bool {
+        self.0 & (Self::CLIENT_REDIRECT | Self::SERVER_REDIRECT) != 0
+    }
+}
+
+impl From<i64> for PageTransition {
+    fn from(value: i64) -> Self {
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "stored as a signed 32-bit int"
+        )]
+        Self(value as u32)
+    }
+}
+
+/// A row of the `visits` table with its URL
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryVisit {
+    pub id: i64,
+    pub url_id: i64,
+    pub url: Option<String>,
+    pub title: Option<String>,
+    pub visit_time: Option<DateTime<Utc>>,
+    /// The visit that referred to this one
+    pub from_visit: Option<i64>,
+    pub transition: PageTransition,
+    pub visit_duration: TimeDelta,
+    /// URLs of the referring visits, nearest first
+    pub referrer_chain: Vec<String>,
+}
+
+impl HistoryVisit {
+    /// `urls`: url id to url row, `visits`: visit id to visit row
+    pub(crate) fn new(
+        v: &visits::Model,
+        urls: &HashMap<i64, urls::Model>,
+        visits: &HashMap<i64, visits::Model>,
+    ) -> Self {
+        let url = urls.get(&v.url);
+        let referrer_chain = referrer_chain(v.from_visit, urls, visits);
+        Self {
+            id: v.id,
+            url_id: v.url,
+            url: url.map(|u| u.url.clone()),
+            title: url.map(|u| u.title.clone()),
+            visit_time: v
+                .visit_time
+                .micros_to_chromium_utc(),
+            from_visit: v.from_visit.filter(|&id| id != 0),
+            transition: PageTransition::from(v.transition),
+            visit_duration: TimeDelta::microseconds(v.visit_duration),
+            referrer_chain,
+        }
+    }
+}
+
+/// Follow `from_visit` until the chain ends, a visit is missing or a cycle is found
+fn referrer_chain(
+    mut from: Option<i64>,
+    urls: &HashMap<i64, urls::Model>,
+    visits: &HashMap<i64, visits::Model>,
+) -> Vec<String> {
+    let mut chain = vec![];
+    let mut seen = vec![];
+    while let Some(id) = from.filter(|&id| id != 0 && !seen.contains(&id))
+        && let Some(visit) = visits.get(&id)
+    {
+        seen.push(id);
+        if let Some(url) = urls.get(&visit.url) {
+            chain.push(url.url.clone());
+        }
+        from = visit.from_visit;
+    }
+    chain
+}
+
+/// <https://source.chromium.org/chromium/chromium/src/+/main:components/history/core/browser/download_constants.h>
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DownloadState {
+    InProgress,
+    Complete,
+    Cancelled,
+    Interrupted,
+    Other(i32),
+}
+
+impl From<i32> for DownloadState {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::InProgress,
+            1 => Self::Complete,
+            2 => Self::Cancelled,
+            // 3 is the legacy interrupted state
+            3 | 4 => Self::Interrupted,
+            v => Self::Other(v),
+        }
+    }
+}
+
+/// A row of the `downloads` table with its URL chain
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Download {
+    pub id: i64,
+    pub guid: String,
+    pub current_path: String,
+    pub target_path: String,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub last_access_time: Option<DateTime<Utc>>,
+    pub received_bytes: i64,
+    pub total_bytes: i64,
+    pub state: DownloadState,
+    pub danger_type: i32,
+    pub interrupt_reason: i32,
+    pub opened: bool,
+    pub referrer: String,
+    pub site_url: String,
+    pub tab_url: String,
+    pub tab_referrer_url: String,
+    pub mime_type: String,
+    pub original_mime_type: String,
+    /// `downloads_url_chains`, the last one is the URL the file came from
+    pub url_chain: Vec<String>,
+}
+
+impl From<downloads::Model> for Download {
+    fn from(v: downloads::Model) -> Self {
+        Self {
+            id: v.id,
+            guid: v.guid,
+            current_path: v.current_path,
+            target_path: v.target_path,
+            start_time: v
+                .start_time
+                .micros_to_chromium_utc(),
+            end_time: v.end_time.micros_to_chromium_utc(),
+            last_access_time: v
+                .last_access_time
+                .micros_to_chromium_utc(),
+            received_bytes: v.received_bytes,
+            total_bytes: v.total_bytes,
+            state: DownloadState::from(v.state),
+            danger_type: v.danger_type,
+            interrupt_reason: v.interrupt_reason,
+            opened: v.opened != 0,
+            referrer: v.referrer,
+            site_url: v.site_url,
+            tab_url: v.tab_url,
+            tab_referrer_url: v.tab_referrer_url,
+            mime_type: v.mime_type,
+            original_mime_type: v.original_mime_type,
+            url_chain: vec![],
+        }
+    }
+}
+
+/// A row of the `keyword_search_terms` table
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeywordSearchTerm {
+    pub keyword_id: i64,
+    pub url_id: i64,
+    pub url: Option<String>,
+    pub term: String,
+    pub normalized_term: String,
+}
+
+impl From<keyword_search_terms::Model> for KeywordSearchTerm {
+    fn from(v: keyword_search_terms::Model) -> Self {
+        Self {
+            keyword_id: v.keyword_id,
+            url_id: v.url_id,
+            url: None,
+            term: v.term,
+            normalized_term: v.normalized_term,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(id: i64, url: &str) -> (i64, urls::Model) {
+        (
+            id,
+            urls::Model {
+                id,
+                url: url.to_owned(),
+                title: String::new(),
+                visit_count: 1,
+                typed_count: 0,
+                last_visit_time: 0,
+                hidden: 0,
+            },
+        )
+    }
+
+    fn visit(id: i64, url: i64, from_visit: i64) -> (i64, visits::Model) {
+        (
+            id,
+            visits::Model {
+                id,
+                url,
+                visit_time: 0,
+                from_visit: Some(from_visit),
+                transition: 0,
+                segment_id: None,
+                visit_duration: 0,
+            },
+        )
+    }
+
+    #[test]
+    fn referrer_chain_stops_on_cycle() {
+        let urls = HashMap::from([url(1, "https://a.com"), url(2, "https://b.com")]);
+        let visits = HashMap::from([visit(1, 1, 2), visit(2, 2, 1), visit(3, 1, 2)]);
+
+        let v = HistoryVisit::new(&visits[&3], &urls, &visits);
+        assert_eq!(v.referrer_chain, ["https://b.com", "https://a.com"]);
+    }
+
+    #[test]
+    fn transition_qualifiers() {
+        let t = PageTransition::from(i64::from(0x8000_0001_u32 as i32));
+        assert_eq!(t.core(), TransitionCore::Typed);
+        assert!(t.is_redirect());
+        assert!(t.contains(PageTransition::SERVER_REDIRECT));
+        assert!(!t.contains(PageTransition::CHAIN_START));
+    }
+}
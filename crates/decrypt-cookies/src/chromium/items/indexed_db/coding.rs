@@ -0,0 +1,132 @@
+//! Key coding of Chromium's IndexedDB backing store
+//!
+//! <https://source.chromium.org/chromium/chromium/src/+/main:content/browser/indexed_db/indexed_db_leveldb_coding.cc>
+
+use super::IdbKey;
+
+pub fn varint(input: &mut &[u8]) -> Option<u64> {
+    let mut res = 0_u64;
+    for (i, &byte) in input.iter().enumerate().take(10) {
+        res |= u64::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            *input = &input[i + 1..];
+            return Some(res);
+        }
+    }
+    None
+}
+
+pub fn byte(input: &mut &[u8]) -> Option<u8> {
+    let (&res, rest) = input.split_first()?;
+    *input = rest;
+    Some(res)
+}
+
+/// Little endian, as many bytes as given
+pub fn int(input: &[u8]) -> Option<i64> {
+    (input.len() <= 8).then(|| {
+        input
+            .iter()
+            .rev()
+            .fold(0, |acc, &b| (acc << 8) | i64::from(b))
+    })
+}
+
+/// UTF-16BE filling the rest of the input
+pub fn string(input: &[u8]) -> String {
+    let (units, _) = input.as_chunks();
+    let units: Vec<u16> = units
+        .iter()
+        .map(|&v| u16::from_be_bytes(v))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Length in code units, then UTF-16BE
+pub fn string_with_length(input: &mut &[u8]) -> Option<String> {
+    let len = usize::try_from(varint(input)?)
+        .ok()?
+        .checked_mul(2)?;
+    let (res, rest) = input.split_at_checked(len)?;
+    *input = rest;
+    Some(string(res))
+}
+
+fn double(input: &mut &[u8]) -> Option<f64> {
+    let (res, rest) = input.split_first_chunk()?;
+    *input = rest;
+    Some(f64::from_le_bytes(*res))
+}
+
+/// Every key starts with the database, object store and index ids.
+/// The first byte holds the byte lengths of the ids.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub struct KeyPrefix {
+    pub database_id: i64,
+    pub object_store_id: i64,
+    pub index_id: i64,
+}
+
+impl KeyPrefix {
+    pub fn decode(input: &mut &[u8]) -> Option<Self> {
+        let first = byte(input)?;
+        let database_len = usize::from(((first >> 5) & 0x7) + 1);
+        let object_store_len = usize::from(((first >> 2) & 0x7) + 1);
+        let index_len = usize::from((first & 0x3) + 1);
+
+        let (database_id, rest) = input.split_at_checked(database_len)?;
+        let (object_store_id, rest) = rest.split_at_checked(object_store_len)?;
+        let (index_id, rest) = rest.split_at_checked(index_len)?;
+        *input = rest;
+
+        Some(Self {
+            database_id: int(database_id)?,
+            object_store_id: int(object_store_id)?,
+            index_id: int(index_id)?,
+        })
+    }
+}
+
+const KEY_NULL: u8 = 0;
+const KEY_STRING: u8 = 1;
+const KEY_DATE: u8 = 2;
+const KEY_NUMBER: u8 = 3;
+const KEY_ARRAY: u8 = 4;
+const KEY_MIN: u8 = 5;
+const KEY_BINARY: u8 = 6;
+
+/// Arrays nested deeper are rejected instead of overflowing the stack
+const MAX_KEY_DEPTH: usize = 256;
+
+pub fn idb_key(input: &mut &[u8]) -> Option<IdbKey> {
+    idb_key_nested(input, 0)
+}
+
+fn idb_key_nested(input: &mut &[u8], depth: usize) -> Option<IdbKey> {
+    if depth >= MAX_KEY_DEPTH {
+        return None;
+    }
+    Some(match byte(input)? {
+        KEY_NULL | KEY_MIN => IdbKey::None,
+        KEY_STRING => IdbKey::String(string_with_length(input)?),
+        KEY_DATE => IdbKey::Date(double(input)?),
+        KEY_NUMBER => IdbKey::Number(double(input)?),
+        KEY_ARRAY => {
+            let len = varint(input)?;
+            let mut res = vec![];
+            for _ in 0..len {
+                res.push(idb_key_nested(input, depth + 1)?);
+            }
+            IdbKey::Array(res)
+        },
+        KEY_BINARY => {
+            let len = usize::try_from(varint(input)?).ok()?;
+            let (res, rest) = input.split_at_checked(len)?;
+            *input = rest;
+            IdbKey::Binary(res.to_vec())
+        },
+        _ => return None,
+    })
+}
@@ -0,0 +1,605 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use leveldb_reader::LevelDb;
+use serde_json::{Map, Value};
+
+use self::coding::{KeyPrefix, byte, idb_key, int, string, string_with_length, varint};
+pub use self::v8::JsValue;
+use super::{match_origin_host, origin_host};
+
+mod coding;
+mod v8;
+
+/// The IndexedDB databases of an origin, from a `*.indexeddb.leveldb` directory
+///
+/// <https://source.chromium.org/chromium/chromium/src/+/main:content/browser/indexed_db/docs/leveldb_coding_scheme.md>
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexedDb {
+    /// Like `https://app.slack.com`
+    pub origin: String,
+    pub databases: Vec<IdbDatabase>,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdbDatabase {
+    pub id: i64,
+    pub name: String,
+    /// `-1` before the first upgrade
+    pub version: i64,
+    pub object_stores: Vec<IdbObjectStore>,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdbObjectStore {
+    pub id: i64,
+    pub name: String,
+    pub key_path: IdbKeyPath,
+    pub auto_increment: bool,
+    pub indexes: Vec<IdbIndex>,
+    /// Sorted by key
+    pub records: Vec<IdbRecord>,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdbIndex {
+    pub id: i64,
+    pub name: String,
+    pub key_path: IdbKeyPath,
+    pub unique: bool,
+    pub multi_entry: bool,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IdbKeyPath {
+    /// Out of line keys
+    #[default]
+    None,
+    String(String),
+    Array(Vec<String>),
+}
+
+/// Ordered as `Number < Date < String < Binary < Array`
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IdbKey {
+    None,
+    Number(f64),
+    /// Milliseconds since the unix epoch
+    Date(f64),
+    String(String),
+    Binary(Vec<u8>),
+    Array(Vec<Self>),
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdbRecord {
+    pub key: IdbKey,
+    pub value: IdbValue,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IdbValue {
+    Js(JsValue),
+    /// Large values are moved to a file of the `*.indexeddb.blob` directory
+    External {
+        size: u64,
+        offset: u64,
+    },
+    Undecodable {
+        reason: String,
+        raw: Vec<u8>,
+    },
+}
+
+impl IdbKey {
+    const fn rank(&self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Number(_) => 1,
+            Self::Date(_) => 2,
+            Self::String(_) => 3,
+            Self::Binary(_) => 4,
+            Self::Array(_) => 5,
+        }
+    }
+
+    /// The order of `indexedDB.cmp`
+    pub fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Number(a), Self::Number(b)) | (Self::Date(a), Self::Date(b)) => a.total_cmp(b),
+            (Self::String(a), Self::String(b)) => a
+                .encode_utf16()
+                .cmp(b.encode_utf16()),
+            (Self::Binary(a), Self::Binary(b)) => a.cmp(b),
+            (Self::Array(a), Self::Array(b)) => a
+                .iter()
+                .zip(b)
+                .map(|(a, b)| a.compare(b))
+                .find(|v| v.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::None => Value::Null,
+            Self::Number(v) => JsValue::Number(*v).to_json(),
+            Self::Date(v) => JsValue::Date(*v).to_json(),
+            Self::String(v) => Value::String(v.clone()),
+            Self::Binary(v) => JsValue::ArrayBuffer(v.clone()).to_json(),
+            Self::Array(v) => Value::Array(
+                v.iter()
+                    .map(Self::to_json)
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl IdbValue {
+    /// The record value is the version of the row, then the serialized script value
+    fn decode(mut input: &[u8]) -> Self {
+        let raw = input;
+        if varint(&mut input).is_none() {
+            return Self::Undecodable {
+                reason: "missing version".to_owned(),
+                raw: raw.to_vec(),
+            };
+        }
+        match unwrap(input) {
+            Ok(Unwrapped::External { size, offset }) => Self::External { size, offset },
+            Ok(Unwrapped::Inline(data)) => match v8::deserialize(&data) {
+                Ok(v) => Self::Js(v),
+                Err(e) => Self::Undecodable {
+                    reason: e.to_string(),
+                    raw: input.to_vec(),
+                },
+            },
+            Err(reason) => Self::Undecodable { reason, raw: input.to_vec() },
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Js(v) => v.to_json(),
+            Self::External { size, .. } => Value::String(format!("[external {size} bytes]")),
+            Self::Undecodable { reason, .. } => Value::String(format!("[undecodable: {reason}]")),
+        }
+    }
+}
+
+enum Unwrapped {
+    Inline(Vec<u8>),
+    External {
+        size: u64,
+        offset: u64,
+    },
+}
+
+/// `IDBValueWrapper` marks values that are replaced with a blob or compressed
+/// with a fake Blink version
+const REQUIRES_PROCESSING_SSV_PSEUDO_VERSION: u8 = 17;
+const REPLACE_WITH_BLOB: u8 = 1;
+const COMPRESSED_WITH_SNAPPY: u8 = 2;
+
+fn unwrap(input: &[u8]) -> Result<Unwrapped, String> {
+    match input {
+        [
+            0xFF,
+            REQUIRES_PROCESSING_SSV_PSEUDO_VERSION,
+            REPLACE_WITH_BLOB,
+            rest @ ..,
+        ] => {
+            let mut rest = rest;
+            let (Some(size), Some(offset)) = (varint(&mut rest), varint(&mut rest))
+            else {
+                return Err("bad blob wrapper".to_owned());
+            };
+            Ok(Unwrapped::External { size, offset })
+        },
+        [
+            0xFF,
+            REQUIRES_PROCESSING_SSV_PSEUDO_VERSION,
+            COMPRESSED_WITH_SNAPPY,
+            rest @ ..,
+        ] => snap::raw::Decoder::new()
+            .decompress_vec(rest)
+            .map(Unwrapped::Inline)
+            .map_err(|e| e.to_string()),
+        _ => Ok(Unwrapped::Inline(input.to_vec())),
+    }
+}
+
+impl IndexedDb {
+    /// The host of the origin, without scheme and port
+    pub fn host(&self) -> &str {
+        origin_host(&self.origin)
+    }
+
+    /// The host or a subdomain of it
+    pub fn match_host(&self, host: &str) -> bool {
+        match_origin_host(&self.origin, host)
+    }
+
+    /// Every database, object store and record as JSON
+    pub fn to_json(&self) -> Value {
+        let databases = self
+            .databases
+            .iter()
+            .map(|db| {
+                let object_stores = db
+                    .object_stores
+                    .iter()
+                    .map(|store| {
+                        let records = store
+                            .records
+                            .iter()
+                            .map(|v| {
+                                let mut map = Map::new();
+                                map.insert("key".to_owned(), v.key.to_json());
+                                map.insert("value".to_owned(), v.value.to_json());
+                                Value::Object(map)
+                            })
+                            .collect();
+                        let mut map = Map::new();
+                        map.insert("name".to_owned(), Value::from(store.name.as_str()));
+                        map.insert("records".to_owned(), Value::Array(records));
+                        Value::Object(map)
+                    })
+                    .collect();
+                let mut map = Map::new();
+                map.insert("name".to_owned(), Value::from(db.name.as_str()));
+                map.insert("version".to_owned(), Value::from(db.version));
+                map.insert("object_stores".to_owned(), Value::Array(object_stores));
+                Value::Object(map)
+            })
+            .collect();
+
+        let mut map = Map::new();
+        map.insert("origin".to_owned(), Value::from(self.origin.as_str()));
+        map.insert("databases".to_owned(), Value::Array(databases));
+        Value::Object(map)
+    }
+
+    /// `https_app.slack.com_0.indexeddb.leveldb` to `https://app.slack.com`, port `0` is the default
+    pub fn origin_of_dir(name: &str) -> Option<String> {
+        let id = name.strip_suffix(".indexeddb.leveldb")?;
+        let (scheme, rest) = id.split_once('_')?;
+        let (host, port) = rest.rsplit_once('_')?;
+        Some(match port {
+            "0" => format!("{scheme}://{host}"),
+            port => format!("{scheme}://{host}:{port}"),
+        })
+    }
+
+    pub fn from_leveldb(origin: String, db: &LevelDb) -> Self {
+        Self::from_entries(origin, db.entries())
+    }
+
+    fn from_entries<'a, I>(origin: String, entries: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a [u8], &'a [u8])>,
+    {
+        let mut databases: BTreeMap<i64, IdbDatabase> = BTreeMap::new();
+        let mut stores: BTreeMap<(i64, i64), IdbObjectStore> = BTreeMap::new();
+        let mut indexes: BTreeMap<(i64, i64, i64), IdbIndex> = BTreeMap::new();
+
+        for (key, value) in entries {
+            let mut input = key;
+            let Some(prefix) = KeyPrefix::decode(&mut input)
+            else {
+                continue;
+            };
+            match prefix {
+                KeyPrefix { database_id: 0, .. } => {
+                    if let Some((id, name)) = global_database_name(input, value) {
+                        databases
+                            .entry(id)
+                            .or_insert_with(|| IdbDatabase::new(id))
+                            .name = name;
+                    }
+                },
+                KeyPrefix {
+                    database_id,
+                    object_store_id: 0,
+                    index_id: 0,
+                } => {
+                    let database = databases
+                        .entry(database_id)
+                        .or_insert_with(|| IdbDatabase::new(database_id));
+                    database_meta(
+                        database_id,
+                        database,
+                        &mut stores,
+                        &mut indexes,
+                        input,
+                        value,
+                    );
+                },
+                KeyPrefix {
+                    database_id,
+                    object_store_id,
+                    index_id: OBJECT_STORE_DATA,
+                } => {
+                    let Some(key) = idb_key(&mut input)
+                    else {
+                        continue;
+                    };
+                    stores
+                        .entry((database_id, object_store_id))
+                        .or_insert_with(|| IdbObjectStore::new(object_store_id))
+                        .records
+                        .push(IdbRecord { key, value: IdbValue::decode(value) });
+                },
+                // exists entries, blob entries and index entries
+                KeyPrefix { .. } => {},
+            }
+        }
+
+        for ((database_id, object_store_id, _), index) in indexes {
+            if let Some(store) = stores.get_mut(&(database_id, object_store_id)) {
+                store.indexes.push(index);
+            }
+        }
+        for ((database_id, _), mut store) in stores {
+            store
+                .records
+                .sort_by(|a, b| a.key.compare(&b.key));
+            databases
+                .entry(database_id)
+                .or_insert_with(|| IdbDatabase::new(database_id))
+                .object_stores
+                .push(store);
+        }
+
+        Self {
+            origin,
+            databases: databases.into_values().collect(),
+        }
+    }
+}
+
+impl IdbDatabase {
+    const fn new(id: i64) -> Self {
+        Self {
+            id,
+            name: String::new(),
+            version: -1,
+            object_stores: vec![],
+        }
+    }
+}
+
+impl IdbObjectStore {
+    const fn new(id: i64) -> Self {
+        Self {
+            id,
+            name: String::new(),
+            key_path: IdbKeyPath::None,
+            auto_increment: false,
+            indexes: vec![],
+            records: vec![],
+        }
+    }
+}
+
+const OBJECT_STORE_DATA: i64 = 1;
+
+const GLOBAL_DATABASE_NAME: u8 = 201;
+
+const DATABASE_NAME: u8 = 1;
+const DATABASE_USER_VERSION: u8 = 4;
+const OBJECT_STORE_META_DATA: u8 = 50;
+const INDEX_META_DATA: u8 = 100;
+
+const OBJECT_STORE_NAME: u8 = 0;
+const OBJECT_STORE_KEY_PATH: u8 = 1;
+const OBJECT_STORE_AUTO_INCREMENT: u8 = 2;
+
+const INDEX_NAME: u8 = 0;
+const INDEX_UNIQUE: u8 = 1;
+const INDEX_KEY_PATH: u8 = 2;
+const INDEX_MULTI_ENTRY: u8 = 3;
+
+/// `201, origin, database name` to the database id
+fn global_database_name(mut input: &[u8], value: &[u8]) -> Option<(i64, String)> {
+    if byte(&mut input)? != GLOBAL_DATABASE_NAME {
+        return None;
+    }
+    let _origin = string_with_length(&mut input)?;
+    let name = string_with_length(&mut input)?;
+    Some((int(value)?, name))
+}
+
+fn database_meta(
+    database_id: i64,
+    database: &mut IdbDatabase,
+    stores: &mut BTreeMap<(i64, i64), IdbObjectStore>,
+    indexes: &mut BTreeMap<(i64, i64, i64), IdbIndex>,
+    mut input: &[u8],
+    mut value: &[u8],
+) -> Option<()> {
+    match byte(&mut input)? {
+        DATABASE_NAME if database.name.is_empty() => database.name = string(value),
+        DATABASE_USER_VERSION => database.version = varint(&mut value)?.cast_signed(),
+        OBJECT_STORE_META_DATA => {
+            let id = varint(&mut input)?.cast_signed();
+            let store = stores
+                .entry((database_id, id))
+                .or_insert_with(|| IdbObjectStore::new(id));
+            match byte(&mut input)? {
+                OBJECT_STORE_NAME => store.name = string(value),
+                OBJECT_STORE_KEY_PATH => store.key_path = key_path(value)?,
+                OBJECT_STORE_AUTO_INCREMENT => store.auto_increment = bool_value(value),
+                _ => {},
+            }
+        },
+        INDEX_META_DATA => {
+            let store_id = varint(&mut input)?.cast_signed();
+            let id = varint(&mut input)?.cast_signed();
+            let index = indexes
+                .entry((database_id, store_id, id))
+                .or_insert_with(|| IdbIndex {
+                    id,
+                    name: String::new(),
+                    key_path: IdbKeyPath::None,
+                    unique: false,
+                    multi_entry: false,
+                });
+            match byte(&mut input)? {
+                INDEX_NAME => index.name = string(value),
+                INDEX_UNIQUE => index.unique = bool_value(value),
+                INDEX_KEY_PATH => index.key_path = key_path(value)?,
+                INDEX_MULTI_ENTRY => index.multi_entry = bool_value(value),
+                _ => {},
+            }
+        },
+        _ => {},
+    }
+    Some(())
+}
+
+fn bool_value(value: &[u8]) -> bool {
+    int(value).is_some_and(|v| v != 0)
+}
+
+/// Typed key paths start with two zero bytes, older ones are a bare string
+fn key_path(value: &[u8]) -> Option<IdbKeyPath> {
+    let [0, 0, kind, rest @ ..] = value
+    else {
+        return Some(IdbKeyPath::String(string(value)));
+    };
+    let mut rest = rest;
+    Some(match *kind {
+        0 => IdbKeyPath::None,
+        1 => IdbKeyPath::String(string_with_length(&mut rest)?),
+        2 => {
+            let len = varint(&mut rest)?;
+            let mut res = vec![];
+            for _ in 0..len {
+                res.push(string_with_length(&mut rest)?);
+            }
+            IdbKeyPath::Array(res)
+        },
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16_be(s: &str) -> Vec<u8> {
+        s.encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect()
+    }
+
+    fn with_length(s: &str) -> Vec<u8> {
+        let mut res = vec![s.len() as u8];
+        res.extend(u16_be(s));
+        res
+    }
+
+    #[test]
+    fn backing_store() {
+        let mut entries: Vec<(Vec<u8>, Vec<u8>)> = vec![];
+        let mut name = vec![0, 0, 0, 0, GLOBAL_DATABASE_NAME];
+        name.extend(with_length("https_a.com_0"));
+        name.extend(with_length("db"));
+        entries.push((name, vec![1]));
+        entries.push((vec![0, 1, 0, 0, DATABASE_USER_VERSION], vec![2]));
+        entries.push((
+            vec![0, 1, 0, 0, OBJECT_STORE_META_DATA, 1, OBJECT_STORE_NAME],
+            u16_be("store"),
+        ));
+        let mut key_path = vec![0, 0, 2, 2];
+        key_path.extend(with_length("a"));
+        key_path.extend(with_length("b"));
+        entries.push((
+            vec![0, 1, 0, 0, OBJECT_STORE_META_DATA, 1, OBJECT_STORE_KEY_PATH],
+            key_path,
+        ));
+
+        let value = |v: &[u8]| [&[1, 0xFF, 0x0F][..], v].concat();
+        let mut string_key = vec![0, 1, 1, 1, 1, 1];
+        string_key.extend(u16_be("k"));
+        entries.push((string_key, value(b"T")));
+        let mut number_key = vec![0, 1, 1, 1, 3];
+        number_key.extend(7_f64.to_le_bytes());
+        entries.push((number_key, value(b"I\x04")));
+        entries.push((vec![0, 1, 1, 1, 6, 1, 0xAA], value(b"~")));
+
+        let idb = IndexedDb::from_entries(
+            "https://a.com".to_owned(),
+            entries
+                .iter()
+                .map(|(k, v)| (k.as_slice(), v.as_slice())),
+        );
+        let db = &idb.databases[0];
+        assert_eq!((db.name.as_str(), db.version), ("db", 2));
+        let store = &db.object_stores[0];
+        assert_eq!(store.name, "store");
+        assert_eq!(
+            store.key_path,
+            IdbKeyPath::Array(vec!["a".to_owned(), "b".to_owned()])
+        );
+        // sorted as Number < String < Binary
+        assert_eq!(store.records[0].key, IdbKey::Number(7.0));
+        assert_eq!(store.records[0].value, IdbValue::Js(JsValue::Number(2.0)));
+        assert_eq!(store.records[1].key, IdbKey::String("k".to_owned()));
+        assert_eq!(store.records[2].key, IdbKey::Binary(vec![0xAA]));
+        assert!(matches!(
+            store.records[2].value,
+            IdbValue::Undecodable { .. }
+        ));
+        assert_eq!(
+            idb.to_json()["databases"][0]["object_stores"][0]["records"][0]["value"],
+            2
+        );
+
+        assert_eq!(
+            IndexedDb::origin_of_dir("http_localhost_8080.indexeddb.leveldb").unwrap(),
+            "http://localhost:8080"
+        );
+
+        assert_eq!(
+            idb_key(&mut &[4, 1, 4, 0][..]),
+            Some(IdbKey::Array(vec![IdbKey::Array(vec![])]))
+        );
+        assert_eq!(idb_key(&mut &*[4, 1].repeat(300)), None);
+    }
+}
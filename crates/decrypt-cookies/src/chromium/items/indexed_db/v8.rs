@@ -0,0 +1,852 @@
+//! Values serialized by V8's `ValueSerializer`, wrapped in Blink's envelope
+//!
+//! <https://source.chromium.org/chromium/chromium/src/+/main:v8/src/objects/value-serializer.cc>
+//! <https://source.chromium.org/chromium/chromium/src/+/main:third_party/blink/renderer/bindings/core/v8/serialization/serialization_tag.h>
+
+use std::fmt::Write;
+
+use chrono::DateTime;
+use serde_json::{Map, Number, Value};
+use snafu::{Location, OptionExt, Snafu, ensure};
+
+use super::coding::varint;
+
+/// A JavaScript value stored by a page
+///
+/// Boxed primitives like `new String("a")` are unboxed, array holes are `Undefined`.
+/// Sparse arrays that are mostly holes are `Object`s keyed by the index.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JsValue {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    /// Decimal digits
+    BigInt(String),
+    String(String),
+    /// Milliseconds since the unix epoch
+    Date(f64),
+    RegExp {
+        pattern: String,
+        flags: String,
+    },
+    Array(Vec<Self>),
+    Object(Vec<(String, Self)>),
+    Map(Vec<(Self, Self)>),
+    Set(Vec<Self>),
+    ArrayBuffer(Vec<u8>),
+    /// Typed arrays and `DataView`, `kind` is like `Uint8Array`
+    ArrayBufferView {
+        kind: String,
+        bytes: Vec<u8>,
+    },
+    Error {
+        name: String,
+        message: Option<String>,
+    },
+    /// A `Blob` or `File` stored next to the database, by its index in the record
+    BlobIndex(u64),
+    /// A `FileList` stored next to the database
+    FileListIndex(Vec<u64>),
+    /// A reference back to an object that contains it
+    Circular,
+}
+
+impl JsValue {
+    /// Like `JSON.stringify`, but dates are ISO 8601 strings,
+    /// bigints are strings and binary data are arrays of bytes
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Undefined | Self::Null | Self::Circular => Value::Null,
+            Self::Bool(v) => Value::Bool(*v),
+            Self::Number(v) => number_to_json(*v),
+            Self::BigInt(v) | Self::String(v) => Value::String(v.clone()),
+            Self::Date(v) => DateTime::from_timestamp_millis(*v as i64)
+                .map_or(Value::Null, |v| Value::String(v.to_rfc3339())),
+            Self::RegExp { pattern, flags } => Value::String(format!("/{pattern}/{flags}")),
+            Self::Array(v) | Self::Set(v) => Value::Array(
+                v.iter()
+                    .map(Self::to_json)
+                    .collect(),
+            ),
+            Self::Object(v) => Value::Object(
+                v.iter()
+                    .map(|(k, v)| (k.clone(), v.to_json()))
+                    .collect(),
+            ),
+            Self::Map(v) => Value::Array(
+                v.iter()
+                    .map(|(k, v)| Value::Array(vec![k.to_json(), v.to_json()]))
+                    .collect(),
+            ),
+            Self::ArrayBuffer(bytes) | Self::ArrayBufferView { bytes, .. } => Value::Array(
+                bytes
+                    .iter()
+                    .map(|&v| Value::from(v))
+                    .collect(),
+            ),
+            Self::Error { name, message } => {
+                let mut map = Map::new();
+                map.insert("name".to_owned(), Value::from(name.as_str()));
+                if let Some(message) = message {
+                    map.insert("message".to_owned(), Value::from(message.as_str()));
+                }
+                Value::Object(map)
+            },
+            Self::BlobIndex(v) => Value::from(format!("[blob {v}]")),
+            Self::FileListIndex(v) => Value::from(format!("[file list {v:?}]")),
+        }
+    }
+
+    /// Bytes it takes in memory, roughly
+    fn size(&self) -> usize {
+        size_of::<Self>()
+            + match self {
+                Self::BigInt(v) | Self::String(v) => v.len(),
+                Self::RegExp { pattern, flags } => pattern.len() + flags.len(),
+                Self::Array(v) | Self::Set(v) => v.iter().map(Self::size).sum(),
+                Self::Object(v) => v
+                    .iter()
+                    .map(|(k, v)| k.len() + v.size())
+                    .sum(),
+                Self::Map(v) => v
+                    .iter()
+                    .map(|(k, v)| k.size() + v.size())
+                    .sum(),
+                Self::ArrayBuffer(bytes) => bytes.len(),
+                Self::ArrayBufferView { kind, bytes } => kind.len() + bytes.len(),
+                Self::Error { name, message } => {
+                    name.len()
+                        + message
+                            .as_ref()
+                            .map_or(0, String::len)
+                },
+                Self::FileListIndex(v) => size_of_val(&**v),
+                _ => 0,
+            }
+    }
+}
+
+/// Integers stay integers, `NaN` and infinities are `null` like `JSON.stringify`
+fn number_to_json(v: f64) -> Value {
+    const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+    if v.fract() == 0.0 && v.abs() <= MAX_SAFE_INTEGER {
+        return Value::from(v as i64);
+    }
+    Number::from_f64(v).map_or(Value::Null, Value::Number)
+}
+
+#[derive(Debug)]
+#[derive(Snafu)]
+#[snafu(visibility(pub))]
+#[non_exhaustive]
+pub enum V8Error {
+    #[snafu(display("Unexpected end of data\n@:{location}"))]
+    Eof {
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("Unknown tag {tag:#04x} at {offset}\n@:{location}"))]
+    Tag {
+        tag: u8,
+        offset: usize,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("Unsupported Blink host object {tag:#04x}\n@:{location}"))]
+    HostObject {
+        tag: u8,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{reason}\n@:{location}"))]
+    Invalid {
+        reason: &'static str,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("Nested deeper than {max}\n@:{location}"))]
+    Depth {
+        max: usize,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("Copies of referenced objects take more than {max} bytes\n@:{location}"))]
+    TooLarge {
+        max: usize,
+        #[snafu(implicit)]
+        location: Location,
+    },
+}
+
+type Result<T> = std::result::Result<T, V8Error>;
+
+const VERSION: u8 = 0xFF;
+const PADDING: u8 = b'\0';
+const VERIFY_OBJECT_COUNT: u8 = b'?';
+const THE_HOLE: u8 = b'-';
+const UNDEFINED: u8 = b'_';
+const NULL: u8 = b'0';
+const TRUE: u8 = b'T';
+const FALSE: u8 = b'F';
+const INT32: u8 = b'I';
+const UINT32: u8 = b'U';
+const DOUBLE: u8 = b'N';
+const BIGINT: u8 = b'Z';
+const UTF8_STRING: u8 = b'S';
+const ONE_BYTE_STRING: u8 = b'"';
+const TWO_BYTE_STRING: u8 = b'c';
+const OBJECT_REFERENCE: u8 = b'^';
+const BEGIN_JS_OBJECT: u8 = b'o';
+const END_JS_OBJECT: u8 = b'{';
+const BEGIN_SPARSE_JS_ARRAY: u8 = b'a';
+const END_SPARSE_JS_ARRAY: u8 = b'@';
+const BEGIN_DENSE_JS_ARRAY: u8 = b'A';
+const END_DENSE_JS_ARRAY: u8 = b'$';
+const DATE: u8 = b'D';
+const TRUE_OBJECT: u8 = b'y';
+const FALSE_OBJECT: u8 = b'x';
+const NUMBER_OBJECT: u8 = b'n';
+const BIGINT_OBJECT: u8 = b'z';
+const STRING_OBJECT: u8 = b's';
+const REGEXP: u8 = b'R';
+const BEGIN_JS_MAP: u8 = b';';
+const END_JS_MAP: u8 = b':';
+const BEGIN_JS_SET: u8 = b'\'';
+const END_JS_SET: u8 = b',';
+const ARRAY_BUFFER: u8 = b'B';
+const RESIZABLE_ARRAY_BUFFER: u8 = b'~';
+const ARRAY_BUFFER_VIEW: u8 = b'V';
+const ERROR: u8 = b'r';
+const HOST_OBJECT: u8 = b'\\';
+
+/// Objects nested deeper are rejected instead of overflowing the stack
+const MAX_DEPTH: usize = 128;
+
+/// Objects referenced many times, like `a = {x: a, y: a}` over and over,
+/// are rejected once their copies take this many bytes
+const MAX_COPIED: usize = 1 << 28;
+
+/// A sparse array longer than this many times its elements (at least 8) is read as an `Object` by index
+const SPARSE_ARRAY_FILL: usize = 2;
+
+/// Blink's trailer of the envelope, offset (8) and size (4) of the trailer
+const TRAILER_OFFSET: u8 = 0xFE;
+
+const BLOB_INDEX: u8 = b'i';
+const FILE_INDEX: u8 = b'e';
+const FILE_LIST_INDEX: u8 = b'L';
+
+/// Deserialize a Blink serialized script value
+pub fn deserialize(data: &[u8]) -> Result<JsValue> {
+    let mut de = Deserializer {
+        data,
+        pos: 0,
+        version: 0,
+        objects: vec![],
+        depth: 0,
+        copied: 0,
+    };
+    de.read_header()?;
+    de.read_object()
+}
+
+struct Deserializer<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Format version of V8
+    version: u64,
+    /// By the id V8 gives objects, `None` while being read
+    objects: Vec<Option<JsValue>>,
+    /// Objects being read
+    depth: usize,
+    /// Bytes of the copies kept in `objects` and made for references
+    copied: usize,
+}
+
+impl Deserializer<'_> {
+    /// `0xFF <blink version>`, the trailer offset, `0xFF <v8 version>`.
+    /// Before Blink version 16 the versions are shared.
+    fn read_header(&mut self) -> Result<()> {
+        ensure!(
+            self.read_u8()? == VERSION,
+            InvalidSnafu { reason: "missing version" }
+        );
+        let version = self.read_varint()?;
+        if self.peek_u8() == Some(TRAILER_OFFSET) {
+            self.read_bytes(1 + 8 + 4)?;
+        }
+        if self.peek_u8() == Some(VERSION) {
+            self.pos += 1;
+            self.version = self.read_varint()?;
+        }
+        else {
+            self.version = version;
+        }
+        Ok(())
+    }
+
+    fn peek_u8(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        let res = self.peek_u8().context(EofSnafu)?;
+        self.pos += 1;
+        Ok(res)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&[u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .context(EofSnafu)?;
+        let res = self
+            .data
+            .get(self.pos..end)
+            .context(EofSnafu)?;
+        self.pos = end;
+        Ok(res)
+    }
+
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut input = &self.data[self.pos..];
+        let before = input.len();
+        let res = varint(&mut input).context(EofSnafu)?;
+        self.pos += before - input.len();
+        Ok(res)
+    }
+
+    fn read_len(&mut self) -> Result<usize> {
+        usize::try_from(self.read_varint()?)
+            .ok()
+            .context(InvalidSnafu { reason: "length overflow" })
+    }
+
+    fn read_double(&mut self) -> Result<f64> {
+        let bytes = self.read_bytes(8)?;
+        Ok(f64::from_le_bytes(
+            bytes
+                .try_into()
+                .unwrap_or_default(),
+        ))
+    }
+
+    fn peek_tag(&mut self) -> Option<u8> {
+        while self.peek_u8() == Some(PADDING) {
+            self.pos += 1;
+        }
+        self.peek_u8()
+    }
+
+    fn read_tag(&mut self) -> Result<u8> {
+        self.peek_tag().context(EofSnafu)?;
+        self.read_u8()
+    }
+
+    fn new_id(&mut self) -> usize {
+        self.objects.push(None);
+        self.objects.len() - 1
+    }
+
+    fn finish(&mut self, id: usize, value: JsValue) -> Result<JsValue> {
+        self.spend(value.size())?;
+        self.objects[id] = Some(value.clone());
+        Ok(value)
+    }
+
+    /// Count a copy of `size` bytes against `MAX_COPIED`
+    fn spend(&mut self, size: usize) -> Result<()> {
+        self.copied = self.copied.saturating_add(size);
+        ensure!(self.copied <= MAX_COPIED, TooLargeSnafu { max: MAX_COPIED });
+        Ok(())
+    }
+
+    fn read_object(&mut self) -> Result<JsValue> {
+        ensure!(self.depth < MAX_DEPTH, DepthSnafu { max: MAX_DEPTH });
+        self.depth += 1;
+        let value = self.read_object_internal();
+        self.depth -= 1;
+        let value = value?;
+        // a view consumes the buffer before it
+        if let JsValue::ArrayBuffer(buffer) = &value
+            && self.peek_tag() == Some(ARRAY_BUFFER_VIEW)
+        {
+            self.pos += 1;
+            return self.read_array_buffer_view(buffer);
+        }
+        Ok(value)
+    }
+
+    fn read_object_internal(&mut self) -> Result<JsValue> {
+        let offset = self.pos;
+        let tag = self.read_tag()?;
+        let value = match tag {
+            VERIFY_OBJECT_COUNT => {
+                self.read_varint()?;
+                self.read_object()?
+            },
+            UNDEFINED => JsValue::Undefined,
+            NULL => JsValue::Null,
+            TRUE => JsValue::Bool(true),
+            FALSE => JsValue::Bool(false),
+            INT32 => {
+                let v = self.read_varint()?;
+                // zigzag
+                let v = ((v >> 1) as i64) ^ -((v & 1) as i64);
+                JsValue::Number(v as f64)
+            },
+            UINT32 => JsValue::Number(self.read_varint()? as f64),
+            DOUBLE => JsValue::Number(self.read_double()?),
+            BIGINT => JsValue::BigInt(self.read_bigint()?),
+            UTF8_STRING | ONE_BYTE_STRING | TWO_BYTE_STRING => {
+                JsValue::String(self.read_string_body(tag)?)
+            },
+            OBJECT_REFERENCE => {
+                let id = self.read_len()?;
+                let size = self
+                    .objects
+                    .get(id)
+                    .context(InvalidSnafu { reason: "bad object reference" })?
+                    .as_ref()
+                    .map_or(0, JsValue::size);
+                self.spend(size)?;
+                self.objects[id]
+                    .clone()
+                    .unwrap_or(JsValue::Circular)
+            },
+            BEGIN_JS_OBJECT => {
+                let id = self.new_id();
+                let props = self.read_properties(END_JS_OBJECT)?;
+                self.read_varint()?;
+                self.finish(id, JsValue::Object(props))?
+            },
+            BEGIN_SPARSE_JS_ARRAY => {
+                let id = self.new_id();
+                let len = self.read_len()?;
+                let props = self.read_properties(END_SPARSE_JS_ARRAY)?;
+                self.read_varint()?;
+                self.read_varint()?;
+                // the length is given by the page, don't fill in more holes than it has elements
+                let value = if len <= props.len().max(8) * SPARSE_ARRAY_FILL {
+                    let mut elements = vec![JsValue::Undefined; len];
+                    for (key, value) in props {
+                        if let Ok(i) = key.parse::<usize>()
+                            && let Some(element) = elements.get_mut(i)
+                        {
+                            *element = value;
+                        }
+                    }
+                    JsValue::Array(elements)
+                }
+                else {
+                    JsValue::Object(props)
+                };
+                self.finish(id, value)?
+            },
+            BEGIN_DENSE_JS_ARRAY => {
+                let id = self.new_id();
+                let len = self.read_len()?;
+                let mut elements = Vec::with_capacity(len.min(1024));
+                for _ in 0..len {
+                    if self.peek_tag() == Some(THE_HOLE) {
+                        self.pos += 1;
+                        elements.push(JsValue::Undefined);
+                    }
+                    else {
+                        elements.push(self.read_object()?);
+                    }
+                }
+                // extra properties of the array are dropped
+                self.read_properties(END_DENSE_JS_ARRAY)?;
+                self.read_varint()?;
+                self.read_varint()?;
+                self.finish(id, JsValue::Array(elements))?
+            },
+            DATE => {
+                let id = self.new_id();
+                let v = self.read_double()?;
+                self.finish(id, JsValue::Date(v))?
+            },
+            TRUE_OBJECT | FALSE_OBJECT => {
+                let id = self.new_id();
+                self.finish(id, JsValue::Bool(tag == TRUE_OBJECT))?
+            },
+            NUMBER_OBJECT => {
+                let id = self.new_id();
+                let v = self.read_double()?;
+                self.finish(id, JsValue::Number(v))?
+            },
+            BIGINT_OBJECT => {
+                let id = self.new_id();
+                let v = self.read_bigint()?;
+                self.finish(id, JsValue::BigInt(v))?
+            },
+            STRING_OBJECT => {
+                let id = self.new_id();
+                let v = self.read_string()?;
+                self.finish(id, JsValue::String(v))?
+            },
+            REGEXP => {
+                let id = self.new_id();
+                let pattern = self.read_string()?;
+                let flags = regexp_flags(self.read_varint()?);
+                self.finish(id, JsValue::RegExp { pattern, flags })?
+            },
+            BEGIN_JS_MAP => {
+                let id = self.new_id();
+                let mut entries = vec![];
+                while self.peek_tag() != Some(END_JS_MAP) {
+                    let key = self.read_object()?;
+                    let value = self.read_object()?;
+                    entries.push((key, value));
+                }
+                self.pos += 1;
+                self.read_varint()?;
+                self.finish(id, JsValue::Map(entries))?
+            },
+            BEGIN_JS_SET => {
+                let id = self.new_id();
+                let mut values = vec![];
+                while self.peek_tag() != Some(END_JS_SET) {
+                    values.push(self.read_object()?);
+                }
+                self.pos += 1;
+                self.read_varint()?;
+                self.finish(id, JsValue::Set(values))?
+            },
+            ARRAY_BUFFER => {
+                let id = self.new_id();
+                let len = self.read_len()?;
+                let bytes = self.read_bytes(len)?.to_vec();
+                self.finish(id, JsValue::ArrayBuffer(bytes))?
+            },
+            RESIZABLE_ARRAY_BUFFER => {
+                let id = self.new_id();
+                let len = self.read_len()?;
+                let _max_len = self.read_varint()?;
+                let bytes = self.read_bytes(len)?.to_vec();
+                self.finish(id, JsValue::ArrayBuffer(bytes))?
+            },
+            ERROR => {
+                let id = self.new_id();
+                let value = self.read_error()?;
+                self.finish(id, value)?
+            },
+            HOST_OBJECT => {
+                let id = self.new_id();
+                let value = self.read_host_object()?;
+                self.finish(id, value)?
+            },
+            tag => return TagSnafu { tag, offset }.fail(),
+        };
+        Ok(value)
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        let tag = self.read_tag()?;
+        ensure!(
+            matches!(tag, UTF8_STRING | ONE_BYTE_STRING | TWO_BYTE_STRING),
+            InvalidSnafu { reason: "expect a string" }
+        );
+        self.read_string_body(tag)
+    }
+
+    fn read_string_body(&mut self, tag: u8) -> Result<String> {
+        let len = self.read_len()?;
+        let bytes = self.read_bytes(len)?;
+        Ok(match tag {
+            UTF8_STRING => String::from_utf8_lossy(bytes).into_owned(),
+            ONE_BYTE_STRING => bytes
+                .iter()
+                .map(|&v| char::from(v))
+                .collect(),
+            _ => {
+                let (units, _) = bytes.as_chunks();
+                let units: Vec<u16> = units
+                    .iter()
+                    .map(|&v| u16::from_le_bytes(v))
+                    .collect();
+                String::from_utf16_lossy(&units)
+            },
+        })
+    }
+
+    /// Key and value pairs until `end`, keys are strings or numbers
+    fn read_properties(&mut self, end: u8) -> Result<Vec<(String, JsValue)>> {
+        let mut res = vec![];
+        while self.peek_tag() != Some(end) {
+            let key = match self.read_object()? {
+                JsValue::String(v) => v,
+                JsValue::Number(v) => v.to_string(),
+                _ => return InvalidSnafu { reason: "bad property key" }.fail(),
+            };
+            let value = self.read_object()?;
+            res.push((key, value));
+        }
+        self.pos += 1;
+        Ok(res)
+    }
+
+    /// A bitfield of the sign and the byte length, then the digits in little endian
+    fn read_bigint(&mut self) -> Result<String> {
+        let bitfield = self.read_varint()?;
+        let negative = bitfield & 1 == 1;
+        let len = usize::try_from(bitfield >> 1).unwrap_or(usize::MAX);
+        let bytes = self.read_bytes(len)?;
+        let digits = bigint_to_decimal(bytes);
+        Ok(if negative && digits != "0" {
+            format!("-{digits}")
+        }
+        else {
+            digits
+        })
+    }
+
+    fn read_array_buffer_view(&mut self, buffer: &[u8]) -> Result<JsValue> {
+        let id = self.new_id();
+        let kind = match self.read_u8()? {
+            b'b' => "Int8Array",
+            b'B' => "Uint8Array",
+            b'C' => "Uint8ClampedArray",
+            b'w' => "Int16Array",
+            b'W' => "Uint16Array",
+            b'd' => "Int32Array",
+            b'D' => "Uint32Array",
+            b'h' => "Float16Array",
+            b'f' => "Float32Array",
+            b'F' => "Float64Array",
+            b'q' => "BigInt64Array",
+            b'Q' => "BigUint64Array",
+            b'?' => "DataView",
+            _ => return InvalidSnafu { reason: "bad array buffer view" }.fail(),
+        };
+        let offset = self.read_len()?;
+        let len = self.read_len()?;
+        if self.version >= 14 {
+            self.read_varint()?;
+        }
+        let bytes = offset
+            .checked_add(len)
+            .and_then(|end| buffer.get(offset..end))
+            .context(InvalidSnafu { reason: "view out of the buffer" })?
+            .to_vec();
+        self.finish(
+            id,
+            JsValue::ArrayBufferView { kind: kind.to_owned(), bytes },
+        )
+    }
+
+    fn read_error(&mut self) -> Result<JsValue> {
+        let mut name = "Error";
+        let mut message = None;
+        loop {
+            match self.read_u8()? {
+                b'E' => name = "EvalError",
+                b'R' => name = "RangeError",
+                b'F' => name = "ReferenceError",
+                b'S' => name = "SyntaxError",
+                b'T' => name = "TypeError",
+                b'U' => name = "URIError",
+                b'm' => message = Some(self.read_string()?),
+                b's' => {
+                    self.read_string()?;
+                },
+                b'c' => {
+                    self.read_object()?;
+                },
+                b'.' => break,
+                _ => return InvalidSnafu { reason: "bad error" }.fail(),
+            }
+        }
+        Ok(JsValue::Error { name: name.to_owned(), message })
+    }
+
+    /// Blink writes its own tag, only those referring to blobs make sense in IndexedDB
+    fn read_host_object(&mut self) -> Result<JsValue> {
+        match self.read_u8()? {
+            BLOB_INDEX | FILE_INDEX => Ok(JsValue::BlobIndex(self.read_varint()?)),
+            FILE_LIST_INDEX => {
+                let len = self.read_len()?;
+                let mut res = Vec::with_capacity(len.min(1024));
+                for _ in 0..len {
+                    res.push(self.read_varint()?);
+                }
+                Ok(JsValue::FileListIndex(res))
+            },
+            tag => HostObjectSnafu { tag }.fail(),
+        }
+    }
+}
+
+/// Bits of `JSRegExp::Flags`
+fn regexp_flags(bits: u64) -> String {
+    const FLAGS: [(u64, char); 9] = [
+        (1 << 5, 'd'),
+        (1 << 0, 'g'),
+        (1 << 1, 'i'),
+        (1 << 6, 'l'),
+        (1 << 2, 'm'),
+        (1 << 7, 's'),
+        (1 << 4, 'u'),
+        (1 << 8, 'v'),
+        (1 << 3, 'y'),
+    ];
+    FLAGS
+        .iter()
+        .filter(|(bit, _)| bits & bit != 0)
+        .map(|&(_, c)| c)
+        .collect()
+}
+
+/// Little endian bytes to decimal digits
+fn bigint_to_decimal(bytes: &[u8]) -> String {
+    let mut limbs: Vec<u32> = bytes
+        .chunks(4)
+        .map(|v| {
+            v.iter()
+                .rev()
+                .fold(0, |acc, &b| (acc << 8) | u32::from(b))
+        })
+        .collect();
+    let mut chunks = vec![];
+    while limbs.iter().any(|&v| v != 0) {
+        let mut rem = 0_u64;
+        for limb in limbs.iter_mut().rev() {
+            let cur = (rem << 32) | u64::from(*limb);
+            *limb = (cur / 1_000_000_000) as u32;
+            rem = cur % 1_000_000_000;
+        }
+        chunks.push(rem);
+    }
+    let Some((last, rest)) = chunks.split_last()
+    else {
+        return "0".to_owned();
+    };
+    let mut res = last.to_string();
+    for chunk in rest.iter().rev() {
+        _ = write!(res, "{chunk:09}");
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values() {
+        // {"a": 1, "s": "水", "l": [true, , null], "d": new Date(0), "m": new Map([[1, 2n ** 70n]])}
+        let mut data = vec![0xFF, 0x14, 0xFF, 0x0F, b'o'];
+        data.extend_from_slice(b"\"\x01aI\x02");
+        data.extend_from_slice(b"\"\x01s\0c\x02\x34\x6c");
+        data.extend_from_slice(b"\"\x01lA\x03T-0$\x00\x03");
+        data.extend_from_slice(b"\"\x01dD");
+        data.extend_from_slice(&0_f64.to_le_bytes());
+        data.extend_from_slice(b"\"\x01m;I\x02Z\x12\0\0\0\0\0\0\0\0\x40:\x02");
+        data.extend_from_slice(b"\"\x01r^\x02");
+        data.extend_from_slice(b"{\x06");
+
+        let value = deserialize(&data).unwrap();
+        let JsValue::Object(props) = &value
+        else {
+            panic!("{value:?}");
+        };
+        assert_eq!(props[0], ("a".to_owned(), JsValue::Number(1.0)));
+        assert_eq!(props[1].1, JsValue::String("水".to_owned()));
+        assert_eq!(
+            props[2].1,
+            JsValue::Array(vec![JsValue::Bool(true), JsValue::Undefined, JsValue::Null])
+        );
+        assert_eq!(
+            props[4].1,
+            JsValue::Map(vec![(
+                JsValue::Number(1.0),
+                JsValue::BigInt("1180591620717411303424".to_owned())
+            )])
+        );
+        // ids: object 0, array 1, date 2
+        assert_eq!(props[5].1, JsValue::Date(0.0));
+
+        let json = value.to_json();
+        assert_eq!(json["d"], "1970-01-01T00:00:00+00:00");
+        assert_eq!(json["l"][1], Value::Null);
+        assert_eq!(json["m"][0][1], "1180591620717411303424");
+    }
+
+    #[test]
+    fn buffers() {
+        // new Uint8Array([1, 2, 3]).subarray(1), /a/gi
+        let data = b"\xff\x0fo\"\x01bB\x03\x01\x02\x03VB\x01\x02\x00\"\x01rR\"\x01a\x03{\x02";
+        let json = deserialize(data)
+            .unwrap()
+            .to_json();
+        assert_eq!(json["b"], serde_json::json!([2, 3]));
+        assert_eq!(json["r"], "/a/gi");
+    }
+
+    #[test]
+    fn untrusted_lengths() {
+        // [, , true]
+        let value = deserialize(b"\xff\x0fa\x03I\x04T@\x01\x03").unwrap();
+        assert_eq!(
+            value,
+            JsValue::Array(vec![
+                JsValue::Undefined,
+                JsValue::Undefined,
+                JsValue::Bool(true)
+            ])
+        );
+
+        // a = []; a[999999] = true
+        let value = deserialize(b"\xff\x0fa\xc0\x84\x3dI\xfe\x88\x7aT@\x01\xc0\x84\x3d").unwrap();
+        assert_eq!(
+            value,
+            JsValue::Object(vec![("999999".to_owned(), JsValue::Bool(true))])
+        );
+
+        let nested = [&b"\xff\x0f"[..], &b"A\x01".repeat(MAX_DEPTH + 1)].concat();
+        assert!(matches!(deserialize(&nested), Err(V8Error::Depth { .. })));
+    }
+
+    /// `a = {}`, then `levels` times `a = {x: a, y: a}`
+    fn doubly_referenced(levels: u8) -> Vec<u8> {
+        let mut data = b"\xff\x0f".to_vec();
+        for _ in 0..levels {
+            data.extend_from_slice(b"o\"\x01x");
+        }
+        data.extend_from_slice(b"o{\x00");
+        // the outermost object has id 0, the innermost `levels`
+        for id in (1..=levels).rev() {
+            data.extend_from_slice(b"\"\x01y^");
+            data.push(id);
+            data.extend_from_slice(b"{\x02");
+        }
+        data
+    }
+
+    #[test]
+    fn references() {
+        let value = deserialize(&doubly_referenced(2)).unwrap();
+        let inner = JsValue::Object(vec![
+            ("x".to_owned(), JsValue::Object(vec![])),
+            ("y".to_owned(), JsValue::Object(vec![])),
+        ]);
+        assert_eq!(
+            value,
+            JsValue::Object(vec![
+                ("x".to_owned(), inner.clone()),
+                ("y".to_owned(), inner)
+            ])
+        );
+
+        assert!(matches!(
+            deserialize(&doubly_referenced(60)),
+            Err(V8Error::TooLarge { .. })
+        ));
+    }
+}
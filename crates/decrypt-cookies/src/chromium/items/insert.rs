@@ -0,0 +1,82 @@
+//! `INSERT`s that work across the schema versions of a Chromium database
+
+use std::collections::HashSet;
+
+use sea_orm::{
+    ConnectionTrait, DbBackend, DbErr, EntityTrait, IdenStatic, Iterable, ModelTrait, Statement,
+    Value,
+};
+
+use crate::utils::schema::table_info;
+
+/// An `INSERT` of the columns of `E` the table has.
+///
+/// Columns the table doesn't have are skipped,
+/// `NOT NULL` columns without a default that `E` doesn't know are set to 0.
+pub struct Insert<E: EntityTrait> {
+    columns: Vec<E::Column>,
+    extra: usize,
+    sql: String,
+}
+
+impl<E: EntityTrait> Insert<E> {
+    /// Read the columns of the table, `skip` is left to the database, e.g. an `AUTOINCREMENT` id
+    pub async fn prepare<C>(conn: &C, skip: &[E::Column]) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let entity = E::default();
+        let table = entity.table_name();
+        let mut known = HashSet::new();
+        let mut extra = vec![];
+        for column in table_info(conn, table).await? {
+            if E::Column::iter().any(|v| v.as_str() == column.name) {
+                known.insert(column.name);
+            }
+            else if column.not_null && !column.has_default && !column.pk {
+                extra.push(column.name);
+            }
+        }
+        let columns: Vec<_> = E::Column::iter()
+            .filter(|v| {
+                known.contains(v.as_str())
+                    && !skip
+                        .iter()
+                        .any(|s| s.as_str() == v.as_str())
+            })
+            .collect();
+
+        let names: Vec<_> = columns
+            .iter()
+            .map(IdenStatic::as_str)
+            .chain(extra.iter().map(String::as_str))
+            .collect();
+        let sql = format!(
+            "INSERT INTO {table} ({}) VALUES ({})",
+            names.join(", "),
+            vec!["?"; names.len()].join(", ")
+        );
+
+        Ok(Self { columns, extra: extra.len(), sql })
+    }
+
+    /// Whether the table has `column`
+    pub fn has(&self, column: E::Column) -> bool {
+        self.columns
+            .iter()
+            .any(|v| v.as_str() == column.as_str())
+    }
+
+    pub fn statement(&self, backend: DbBackend, model: &E::Model) -> Statement
+    where
+        E::Model: ModelTrait<Entity = E>,
+    {
+        let values: Vec<Value> = self
+            .columns
+            .iter()
+            .map(|&v| model.get(v))
+            .chain((0..self.extra).map(|_| 0_i32.into()))
+            .collect();
+        Statement::from_sql_and_values(backend, &self.sql, values)
+    }
+}
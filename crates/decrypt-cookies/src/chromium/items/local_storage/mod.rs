@@ -0,0 +1,91 @@
+use leveldb_reader::LevelDb;
+
+use super::{match_origin_host, origin_host};
+
+/// A `localStorage` item of an origin
+///
+/// <https://source.chromium.org/chromium/chromium/src/+/main:components/services/storage/dom_storage/local_storage_impl.cc>
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocalStorageItem {
+    /// Like `https://example.com`, with partitioned storage the top level site follows after `^0`
+    pub origin: String,
+    pub key: String,
+    pub value: String,
+}
+
+impl LocalStorageItem {
+    /// The host of the origin, without scheme and port
+    pub fn host(&self) -> &str {
+        origin_host(&self.origin)
+    }
+
+    /// The host or a subdomain of it
+    pub fn match_host(&self, host: &str) -> bool {
+        match_origin_host(&self.origin, host)
+    }
+}
+
+/// Items are keyed by `_<origin>\x00<key>`,
+/// keys like `META:<origin>` and `VERSION` are bookkeeping
+pub fn items(db: &LevelDb) -> Vec<LocalStorageItem> {
+    db.prefix(b"_")
+        .filter_map(|(key, value)| {
+            let (origin, key) = key[1..].split_at(
+                key[1..]
+                    .iter()
+                    .position(|&v| v == 0)?,
+            );
+            Some(LocalStorageItem {
+                origin: String::from_utf8_lossy(origin).into_owned(),
+                key: decode_string(&key[1..])?,
+                value: decode_string(value)?,
+            })
+        })
+        .collect()
+}
+
+/// The first byte tells the encoding, `0` for UTF-16LE and `1` for Latin-1
+fn decode_string(data: &[u8]) -> Option<String> {
+    let (&format, data) = data.split_first()?;
+    match format {
+        0 => {
+            let (units, _) = data.as_chunks();
+            let units: Vec<u16> = units
+                .iter()
+                .map(|&v| u16::from_le_bytes(v))
+                .collect();
+            Some(String::from_utf16_lossy(&units))
+        },
+        1 => Some(
+            data.iter()
+                .map(|&v| char::from(v))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        assert_eq!(decode_string(b"\x01caf\xe9").unwrap(), "café");
+        assert_eq!(decode_string(b"\x00a\x00\x34\x6c").unwrap(), "a水");
+        assert_eq!(decode_string(b"\x02"), None);
+
+        let item = LocalStorageItem {
+            origin: "https://app.slack.com:8443".to_owned(),
+            key: String::new(),
+            value: String::new(),
+        };
+        assert_eq!(item.host(), "app.slack.com");
+        assert!(item.match_host("slack.com"));
+        assert!(!item.match_host("ack.com"));
+    }
+}
@@ -1,12 +1,57 @@
 use chrono::{DateTime, TimeZone, Utc, offset::LocalResult};
 
+pub mod account;
+pub mod bookmark;
+pub mod cache;
 pub mod cookie;
+pub mod extension;
+pub mod history;
+pub mod indexed_db;
+mod insert;
+pub mod local_storage;
 pub mod passwd;
+mod pickle;
+pub mod preferences;
+pub mod session;
+pub mod web_data;
+
+/// The host of an origin like `https://example.com:8443`,
+/// with partitioned storage the top level site after `^` is ignored
+fn origin_host(origin: &str) -> &str {
+    let origin = origin
+        .split_once('^')
+        .map_or(origin, |(v, _)| v);
+    let host = origin
+        .split_once("://")
+        .map_or(origin, |(_, v)| v);
+    host.rsplit_once(':')
+        .map_or(host, |(v, _)| v)
+}
+
+/// The origin is of the host or a subdomain of it
+pub fn match_origin_host(origin: &str, host: &str) -> bool {
+    let own = origin_host(origin);
+    own == host
+        || own
+            .strip_suffix(host)
+            .is_some_and(|v| v.ends_with('.'))
+}
 
 pub(super) trait I64ToChromiumDateTime {
     fn micros_to_chromium_utc(&self) -> Option<DateTime<Utc>>;
 }
 
+/// Microseconds since 1601-01-01, the inverse of [`I64ToChromiumDateTime`]
+pub(super) trait ChromiumDateTimeToI64 {
+    fn to_chromium_micros(&self) -> i64;
+}
+
+impl ChromiumDateTimeToI64 for DateTime<Utc> {
+    fn to_chromium_micros(&self) -> i64 {
+        self.timestamp_micros() + 11_644_473_600 * 1_000_000
+    }
+}
+
 // https://source.chromium.org/chromium/chromium/src/+/main:base/time/time.h;l=5;
 impl I64ToChromiumDateTime for i64 {
     fn micros_to_chromium_utc(&self) -> Option<DateTime<Utc>> {
@@ -0,0 +1,291 @@
+//! `logins.form_data` and `logins.possible_username_pairs`, `base::Pickle`s of the form
+//! the credential was saved from
+//!
+//! <https://source.chromium.org/chromium/chromium/src/+/main:components/autofill/core/common/form_data.cc>
+//! <https://source.chromium.org/chromium/chromium/src/+/main:components/autofill/core/common/form_field_data.cc>
+
+use super::super::pickle::Pickle;
+
+/// The newest `kFormDataPickleVersion`
+const FORM_DATA_VERSION: i32 = 6;
+/// The newest `kFormFieldDataPickleVersion`
+const FORM_FIELD_DATA_VERSION: i32 = 8;
+
+/// `autofill::FormData`, the form a credential was saved from
+#[non_exhaustive]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ffi", repr(C))]
+pub struct FormData {
+    /// The `name` or `id` of the form
+    pub name: String,
+    pub url: String,
+    pub action: String,
+    pub fields: Vec<FormFieldData>,
+    /// `false` for fields that are not inside a `<form>`
+    pub is_form_tag: bool,
+}
+
+/// `autofill::FormFieldData`, one input of a [`FormData`]
+#[non_exhaustive]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ffi", repr(C))]
+pub struct FormFieldData {
+    pub label: String,
+    pub name: String,
+    pub value: String,
+    /// `text`, `password`, `email`, ...
+    pub form_control_type: String,
+    pub autocomplete_attribute: String,
+    pub max_length: u64,
+    pub is_autofilled: bool,
+    pub check_status: i32,
+    pub is_focusable: bool,
+    pub should_autocomplete: bool,
+    pub role: i32,
+    pub text_direction: i32,
+    pub option_values: Vec<String>,
+    pub option_contents: Vec<String>,
+    /// Since version 4, empty before
+    pub placeholder: String,
+    /// Since version 5, empty before
+    pub css_classes: String,
+    /// Since version 6, 0 before
+    pub properties_mask: u32,
+    /// Since version 7, empty before
+    pub id_attribute: String,
+    /// Since version 8, empty before
+    pub name_attribute: String,
+}
+
+/// One of `possible_username_pairs`, a value typed into a field that may be the username
+#[non_exhaustive]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ffi", repr(C))]
+pub struct PossibleUsername {
+    pub value: String,
+    pub field_name: String,
+}
+
+impl FormData {
+    /// `None` when the pickle is broken or newer than this crate knows
+    pub fn from_pickle(data: &[u8]) -> Option<Self> {
+        let mut pickle = Pickle::new(data)?;
+        let version = pickle.int()?;
+        if !(1..=FORM_DATA_VERSION).contains(&version) {
+            return None;
+        }
+        let name = pickle.string16()?;
+        if version == 1 {
+            // method
+            pickle.string16()?;
+        }
+        let url = pickle.string()?;
+        let action = pickle.string()?;
+        if version < 4 {
+            // user_submitted
+            pickle.bool()?;
+        }
+        let len = usize::try_from(pickle.int()?).ok()?;
+        let fields = (0..len)
+            .map(|_| FormFieldData::from_pickle(&mut pickle))
+            .collect::<Option<_>>()?;
+        let is_form_tag = if version >= 3 { pickle.bool()? } else { true };
+
+        Some(Self {
+            name,
+            url,
+            action,
+            fields,
+            is_form_tag,
+        })
+    }
+
+    /// The field named `name`, like [`LoginData::username_element`](super::LoginData::username_element)
+    pub fn field(&self, name: &str) -> Option<&FormFieldData> {
+        self.fields
+            .iter()
+            .find(|v| v.name == name)
+    }
+}
+
+impl FormFieldData {
+    fn from_pickle(pickle: &mut Pickle<'_>) -> Option<Self> {
+        let version = pickle.int()?;
+        if !(1..=FORM_FIELD_DATA_VERSION).contains(&version) {
+            return None;
+        }
+        let mut res = Self {
+            label: pickle.string16()?,
+            name: pickle.string16()?,
+            value: pickle.string16()?,
+            form_control_type: pickle.string()?,
+            autocomplete_attribute: pickle.string()?,
+            max_length: pickle.u64()?,
+            is_autofilled: pickle.bool()?,
+            ..Default::default()
+        };
+        if version == 1 {
+            let is_checked = pickle.bool()?;
+            let is_checkable = pickle.bool()?;
+            // `CheckStatus`: not checkable, checkable but unchecked, checked
+            res.check_status = match (is_checkable, is_checked) {
+                (false, _) => 0,
+                (true, false) => 1,
+                (true, true) => 2,
+            };
+        }
+        else {
+            res.check_status = pickle.int()?;
+        }
+        res.is_focusable = pickle.bool()?;
+        res.should_autocomplete = pickle.bool()?;
+        if version >= 3 {
+            res.role = pickle.int()?;
+        }
+        res.text_direction = pickle.int()?;
+        res.option_values = strings(pickle)?;
+        res.option_contents = strings(pickle)?;
+        if version >= 4 {
+            res.placeholder = pickle.string16()?;
+        }
+        if version >= 5 {
+            res.css_classes = pickle.string16()?;
+        }
+        if version >= 6 {
+            res.properties_mask = pickle.u32()?;
+        }
+        if version >= 7 {
+            res.id_attribute = pickle.string16()?;
+        }
+        if version >= 8 {
+            res.name_attribute = pickle.string16()?;
+        }
+        Some(res)
+    }
+}
+
+impl PossibleUsername {
+    /// Pairs of value and field name until the pickle ends, `None` when it is broken
+    pub fn from_pickle(data: &[u8]) -> Option<Vec<Self>> {
+        let mut pickle = Pickle::new(data)?;
+        let mut res = vec![];
+        while let Some(value) = pickle.string16() {
+            let field_name = pickle.string16()?;
+            res.push(Self { value, field_name });
+        }
+        Some(res)
+    }
+}
+
+fn strings(pickle: &mut Pickle<'_>) -> Option<Vec<String>> {
+    let len = usize::try_from(pickle.int()?).ok()?;
+    (0..len)
+        .map(|_| pickle.string16())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chromium::items::pickle::tests::PickleWriter;
+
+    fn field(w: &mut PickleWriter, version: i32, name: &str, control: &str) {
+        w.int(version)
+            .string16("")
+            .string16(name)
+            .string16("")
+            .string(control)
+            .string("username")
+            .u64(524_288)
+            .int(0);
+        if version == 1 {
+            w.int(1).int(1);
+        }
+        else {
+            w.int(2);
+        }
+        w.int(1).int(1);
+        if version >= 3 {
+            w.int(0);
+        }
+        w.int(3)
+            .int(1)
+            .string16("a")
+            .int(1)
+            .string16("A");
+        if version >= 4 {
+            w.string16("Email");
+        }
+        if version >= 5 {
+            w.string16("input");
+        }
+        if version >= 6 {
+            w.int(1);
+        }
+        if version >= 7 {
+            w.string16("id");
+        }
+        if version >= 8 {
+            w.string16("name");
+        }
+    }
+
+    #[test]
+    fn form_data() {
+        let mut w = PickleWriter::default();
+        w.int(FORM_DATA_VERSION)
+            .string16("login")
+            .string("https://a.com/")
+            .string("https://a.com/post")
+            .int(2);
+        field(&mut w, FORM_FIELD_DATA_VERSION, "user", "text");
+        field(&mut w, 1, "pass", "password");
+        w.int(1);
+        let form = FormData::from_pickle(&w.finish()).unwrap();
+
+        assert_eq!(form.name, "login");
+        assert_eq!(form.action, "https://a.com/post");
+        assert!(form.is_form_tag);
+        let user = form.field("user").unwrap();
+        assert_eq!(user.autocomplete_attribute, "username");
+        assert_eq!(user.option_contents, ["A"]);
+        assert_eq!(user.placeholder, "Email");
+        assert_eq!(user.name_attribute, "name");
+        let pass = form.field("pass").unwrap();
+        assert_eq!(pass.form_control_type, "password");
+        assert_eq!(pass.check_status, 2);
+        assert_eq!(pass.id_attribute, "");
+
+        let mut w = PickleWriter::default();
+        w.int(FORM_DATA_VERSION + 1);
+        assert_eq!(FormData::from_pickle(&w.finish()), None);
+    }
+
+    #[test]
+    fn possible_usernames() {
+        let mut w = PickleWriter::default();
+        w.string16("alice")
+            .string16("email")
+            .string16("bob")
+            .string16("nick");
+        let pairs = PossibleUsername::from_pickle(&w.finish()).unwrap();
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[1].value, "bob");
+        assert_eq!(pairs[1].field_name, "nick");
+
+        w.string16("carol");
+        assert_eq!(PossibleUsername::from_pickle(&w.finish()), None);
+    }
+}
@@ -1,27 +1,39 @@
-use std::path::Path;
+use std::{collections::HashSet, path::Path};
 
 use sea_orm::{
-    DbErr,
-    prelude::{DatabaseConnection, EntityTrait, QueryFilter},
+    ConnectionTrait, DbErr, Statement,
+    prelude::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter},
     sea_query::IntoCondition,
 };
 
-use super::login_data_entities::{logins, prelude::Logins};
-use crate::utils::connect_db;
+use super::{
+    login_data_entities::{
+        insecure_credentials, logins, logins_edge_extended, password_notes,
+        prelude::{InsecureCredentials, LoginsEdgeExtended, PasswordNotes, Stats},
+        stats,
+    },
+    login_data_writer::{LoginKey, login_keys},
+};
+use crate::utils::{connect_db, schema::Schema};
 
 type Result<T> = std::result::Result<T, DbErr>;
 
+/// Stay below the sqlite limit of bound parameters
+const IDS_PER_QUERY: usize = 10_000;
+
 #[derive(Clone)]
 #[derive(Debug)]
 #[derive(Default)]
 pub struct LoginDataQuery {
     conn: DatabaseConnection,
+    schema: Schema<logins::Entity>,
 }
 
 impl LoginDataQuery {
     pub async fn new<P: AsRef<Path> + Send>(path: P) -> Result<Self> {
         let db = connect_db(&path).await?;
-        Ok(Self { conn: db })
+        let schema = Schema::read(&db).await?;
+        Ok(Self { conn: db, schema })
     }
 
     /// filter login data
@@ -29,15 +41,157 @@ impl LoginDataQuery {
     where
         F: IntoCondition + Send,
     {
-        Logins::find()
+        self.schema
+            .find()
             .filter(filter)
             .all(&self.conn)
             .await
     }
     /// query all login data
     pub async fn query_all_login_dt(&self) -> Result<Vec<logins::Model>> {
-        Logins::find()
+        self.schema
+            .find()
             .all(&self.conn)
             .await
     }
+
+    /// The keys of all logins, to find duplicates
+    pub async fn query_keys(&self) -> Result<HashSet<LoginKey>> {
+        login_keys(&self.conn).await
+    }
+
+    /// The notes of the logins with `ids`
+    pub async fn query_password_notes(&self, ids: &[i32]) -> Result<Vec<password_notes::Model>> {
+        let mut res = Vec::new();
+        for ids in ids.chunks(IDS_PER_QUERY) {
+            let part = PasswordNotes::find()
+                .filter(password_notes::Column::ParentId.is_in(ids.iter().copied()))
+                .all(&self.conn)
+                .await?;
+            res.extend(part);
+        }
+        Ok(res)
+    }
+
+    /// The password check results of the logins with `ids`
+    pub async fn query_insecure_credentials(
+        &self,
+        ids: &[i32],
+    ) -> Result<Vec<insecure_credentials::Model>> {
+        let mut res = Vec::new();
+        for ids in ids.chunks(IDS_PER_QUERY) {
+            let part = InsecureCredentials::find()
+                .filter(insecure_credentials::Column::ParentId.is_in(ids.iter().copied()))
+                .all(&self.conn)
+                .await?;
+            res.extend(part);
+        }
+        Ok(res)
+    }
+
+    /// Microsoft Edge's extra data of the logins with `ids`
+    pub async fn query_edge_extended(
+        &self,
+        ids: &[i32],
+    ) -> Result<Vec<logins_edge_extended::Model>> {
+        let mut res = Vec::new();
+        for ids in ids.chunks(IDS_PER_QUERY) {
+            let part = LoginsEdgeExtended::find()
+                .filter(logins_edge_extended::Column::Id.is_in(ids.iter().copied()))
+                .all(&self.conn)
+                .await?;
+            res.extend(part);
+        }
+        Ok(res)
+    }
+
+    /// query all login stats
+    pub async fn query_all_stats(&self) -> Result<Vec<stats::Model>> {
+        Stats::find().all(&self.conn).await
+    }
+
+    async fn has_table(&self, name: &str) -> Result<bool> {
+        let backend = self.conn.get_database_backend();
+        let table = self
+            .conn
+            .query_one(Statement::from_sql_and_values(
+                backend,
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?",
+                [name.into()],
+            ))
+            .await?;
+        Ok(table.is_some())
+    }
+
+    /// `local_encryptor_data` of `meta`, only `Ya Passman Data` has it
+    pub async fn query_local_encryptor_data(&self) -> Result<Option<Vec<u8>>> {
+        if !self.has_table("meta").await? {
+            return Ok(None);
+        }
+        let backend = self.conn.get_database_backend();
+        let row = self
+            .conn
+            .query_one(Statement::from_string(
+                backend,
+                "SELECT value FROM meta WHERE key = 'local_encryptor_data'",
+            ))
+            .await?;
+        row.map(|v| v.try_get_by_index(0))
+            .transpose()
+    }
+
+    /// Whether `active_keys` holds a key sealed by a Yandex master password
+    pub async fn has_master_password(&self) -> Result<bool> {
+        if !self
+            .has_table("active_keys")
+            .await?
+        {
+            return Ok(false);
+        }
+        let backend = self.conn.get_database_backend();
+        let sealed = self
+            .conn
+            .query_one(Statement::from_string(
+                backend,
+                "SELECT 1 FROM active_keys WHERE length(sealed_key) > 0",
+            ))
+            .await?;
+        Ok(sealed.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{chromium::LoginData, utils::schema::fixture};
+
+    #[tokio::test]
+    async fn version_matrix() {
+        for version in [28, 41] {
+            let name = format!("login_data_v{version}");
+            let query = LoginDataQuery::new(fixture(&name).await)
+                .await
+                .unwrap();
+
+            let login = LoginData::from(
+                query
+                    .query_all_login_dt()
+                    .await
+                    .unwrap()
+                    .remove(0),
+            );
+            assert_eq!(login.username_value.as_deref(), Some("alice"), "{name}");
+            if version >= 41 {
+                assert_eq!(login.sender_email, "bob@b.com");
+                assert_eq!(login.sender_name, "Bob");
+                assert_eq!(login.date_received, login.date_created);
+                assert!(login.sharing_notification_displayed);
+            }
+            else {
+                assert_eq!(login.sender_name, "");
+                assert_eq!(login.date_received, None);
+                assert!(!login.sharing_notification_displayed);
+            }
+        }
+    }
 }
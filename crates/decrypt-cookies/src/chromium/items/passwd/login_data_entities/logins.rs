@@ -31,16 +31,16 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = true)]
     pub id: i32,
     pub date_last_used: i64,
-    // #[sea_orm(column_type = "Blob", nullable)]
-    // pub moving_blocked_for:             Option<Vec<u8>>,
+    #[sea_orm(column_type = "Blob", nullable)]
+    pub moving_blocked_for: Option<Vec<u8>>,
     pub date_password_modified: i64,
-    // pub sender_email:                   Option<String>,
-    // pub sender_name:                    Option<String>,
-    // pub date_received:                  Option<i32>,
-    // pub sharing_notification_displayed: i32,
-    // #[sea_orm(column_type = "Blob", nullable)]
-    // pub keychain_identifier:            Option<Vec<u8>>,
-    // pub sender_profile_image_url:       Option<String>,
+    pub sender_email: Option<String>,
+    pub sender_name: Option<String>,
+    pub date_received: Option<i64>,
+    pub sharing_notification_displayed: Option<i32>,
+    #[sea_orm(column_type = "Blob", nullable)]
+    pub keychain_identifier: Option<Vec<u8>>,
+    pub sender_profile_image_url: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
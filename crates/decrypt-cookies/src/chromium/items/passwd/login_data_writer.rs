@@ -0,0 +1,273 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QuerySelect, TransactionTrait,
+};
+
+use super::{LoginData, login_data_entities::logins};
+use crate::{
+    chromium::items::{ChromiumDateTimeToI64, insert::Insert},
+    utils::connect_db_rw,
+};
+
+type Result<T> = std::result::Result<T, DbErr>;
+
+/// The columns of the unique index of `logins`, no two logins of a store share them
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoginKey {
+    pub origin_url: String,
+    pub username_element: String,
+    pub username_value: String,
+    pub password_element: String,
+    pub signon_realm: String,
+}
+
+impl LoginKey {
+    /// The key `login` gets once imported, with its `signon_realm` filled in
+    pub fn new(login: &LoginData) -> Self {
+        Self {
+            origin_url: login.origin_url.clone(),
+            username_element: login
+                .username_element
+                .clone()
+                .unwrap_or_default(),
+            username_value: login
+                .username_value
+                .clone()
+                .unwrap_or_default(),
+            password_element: login
+                .password_element
+                .clone()
+                .unwrap_or_default(),
+            signon_realm: signon_realm(login),
+        }
+    }
+}
+
+/// What [`import_logins`](crate::chromium::ChromiumLoginImporter::import_logins) did, or would do
+#[non_exhaustive]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImportReport {
+    /// Inserted, or to be inserted on a dry run
+    pub imported: Vec<LoginKey>,
+    /// Skipped, already stored or earlier in the same batch
+    pub duplicates: Vec<LoginKey>,
+    /// The copy of `Login Data` taken before writing, `None` on a dry run
+    pub backup: Option<PathBuf>,
+    pub dry_run: bool,
+}
+
+type KeyRow = (
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    String,
+);
+
+impl From<KeyRow> for LoginKey {
+    fn from(
+        (origin_url, username_element, username_value, password_element, signon_realm): KeyRow,
+    ) -> Self {
+        Self {
+            origin_url,
+            username_element: username_element.unwrap_or_default(),
+            username_value: username_value.unwrap_or_default(),
+            password_element: password_element.unwrap_or_default(),
+            signon_realm,
+        }
+    }
+}
+
+/// `signon_realm` of `login`, the origin of `origin_url` with a trailing `/` when it is empty
+fn signon_realm(login: &LoginData) -> String {
+    if !login.signon_realm.is_empty() {
+        return login.signon_realm.clone();
+    }
+    let url = &login.origin_url;
+    let Some((scheme, rest)) = url.split_once("://")
+    else {
+        return url.clone();
+    };
+    let host = rest
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default();
+    format!("{scheme}://{host}/")
+}
+
+/// The row of `login`, dates it lacks are `now` and `password_value` is the ciphertext
+pub fn login_model(
+    login: LoginData,
+    password_value: Option<Vec<u8>>,
+    now: DateTime<Utc>,
+) -> logins::Model {
+    let signon_realm = signon_realm(&login);
+    let date_created = login.date_created.unwrap_or(now);
+    logins::Model {
+        origin_url: login.origin_url,
+        action_url: Some(
+            login
+                .action_url
+                .unwrap_or_default(),
+        ),
+        username_element: Some(
+            login
+                .username_element
+                .unwrap_or_default(),
+        ),
+        username_value: Some(
+            login
+                .username_value
+                .unwrap_or_default(),
+        ),
+        password_element: Some(
+            login
+                .password_element
+                .unwrap_or_default(),
+        ),
+        password_value,
+        submit_element: Some(login.submit_element),
+        signon_realm,
+        date_created: date_created.to_chromium_micros(),
+        blacklisted_by_user: login.blacklisted_by_user.into(),
+        scheme: login.scheme.into(),
+        password_type: Some(login.password_type.into()),
+        times_used: Some(login.times_used),
+        form_data: login.form_data,
+        display_name: Some(login.display_name),
+        icon_url: Some(login.icon_url),
+        federation_url: Some(login.federation_url),
+        skip_zero_click: Some(login.skip_zero_click),
+        generation_upload_status: Some(
+            login
+                .generation_upload_status
+                .into(),
+        ),
+        possible_username_pairs: login.possible_username_pairs,
+        id: 0,
+        date_last_used: login
+            .date_last_used
+            .as_ref()
+            .map_or(0, ChromiumDateTimeToI64::to_chromium_micros),
+        moving_blocked_for: login.moving_blocked_for,
+        date_password_modified: login
+            .date_password_modified
+            .unwrap_or(date_created)
+            .to_chromium_micros(),
+        sender_email: Some(login.sender_email),
+        sender_name: Some(login.sender_name),
+        date_received: login
+            .date_received
+            .as_ref()
+            .map(ChromiumDateTimeToI64::to_chromium_micros),
+        sharing_notification_displayed: Some(
+            login
+                .sharing_notification_displayed
+                .into(),
+        ),
+        keychain_identifier: Some(login.keychain_identifier),
+        sender_profile_image_url: login.sender_profile_image_url,
+    }
+}
+
+/// The keys of the logins of `conn`
+pub async fn login_keys<C: ConnectionTrait>(conn: &C) -> Result<HashSet<LoginKey>> {
+    let rows: Vec<KeyRow> = logins::Entity::find()
+        .select_only()
+        .columns([
+            logins::Column::OriginUrl,
+            logins::Column::UsernameElement,
+            logins::Column::UsernameValue,
+            logins::Column::PasswordElement,
+            logins::Column::SignonRealm,
+        ])
+        .into_tuple()
+        .all(conn)
+        .await?;
+    Ok(rows
+        .into_iter()
+        .map(LoginKey::from)
+        .collect())
+}
+
+/// Writes a `Login Data` database in place, the browser must not be running
+#[derive(Debug)]
+pub struct LoginDataWriter {
+    conn: DatabaseConnection,
+}
+
+impl LoginDataWriter {
+    pub async fn new<P: AsRef<Path> + Send>(path: P) -> Result<Self> {
+        let conn = connect_db_rw(&path).await?;
+        Ok(Self { conn })
+    }
+
+    /// The keys of the stored logins
+    pub async fn keys(&self) -> Result<HashSet<LoginKey>> {
+        login_keys(&self.conn).await
+    }
+
+    /// Insert `rows` in one transaction, the ids are assigned by the database
+    pub async fn insert(&self, rows: Vec<logins::Model>) -> Result<()> {
+        let backend = self.conn.get_database_backend();
+        let insert = Insert::<logins::Entity>::prepare(&self.conn, &[logins::Column::Id]).await?;
+
+        let txn = self.conn.begin().await?;
+        for row in rows {
+            txn.execute(insert.statement(backend, &row))
+                .await?;
+        }
+        txn.commit().await
+    }
+
+    pub async fn close(self) -> Result<()> {
+        self.conn.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chromium::items::passwd::tests::model;
+
+    #[test]
+    fn fill_in() {
+        let now = Utc::now();
+        let mut login = LoginData::from(model(1, "", "alice", 0));
+        login.origin_url = "https://a.com:8443/login?next=/".to_owned();
+        login.date_created = None;
+        login.date_password_modified = None;
+
+        let key = LoginKey::new(&login);
+        assert_eq!(key.signon_realm, "https://a.com:8443/");
+        assert_eq!(key.username_value, "alice");
+
+        let row = login_model(login, Some(b"v10".to_vec()), now);
+        assert_eq!(row.signon_realm, "https://a.com:8443/");
+        assert_eq!(row.date_created, now.to_chromium_micros());
+        assert_eq!(row.date_password_modified, row.date_created);
+        assert_eq!(row.date_last_used, 0);
+        let stored = LoginKey::from((
+            row.origin_url,
+            row.username_element,
+            row.username_value,
+            row.password_element,
+            row.signon_realm,
+        ));
+        assert_eq!(stored, key);
+    }
+}
@@ -1,12 +1,363 @@
-use std::fmt::Display;
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    fmt::Display,
+    sync::{Arc, OnceLock},
+};
 
+use chromium_crypto::{Decrypter, SecretString};
 use chrono::{DateTime, Utc};
 
-use self::login_data_entities::logins;
+use self::{
+    form_data::{FormData, PossibleUsername},
+    login_data_entities::{
+        insecure_credentials, logins, logins_edge_extended, password_notes, stats,
+    },
+    yandex::YandexEncryptor,
+};
 use super::I64ToChromiumDateTime;
 
+pub mod form_data;
 pub mod login_data_dao;
 pub mod login_data_entities;
+pub mod login_data_writer;
+pub mod yandex;
+
+/// Which database a [`LoginData`] was read from
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoginStore {
+    /// `Login Data`, the profile store
+    #[default]
+    Local = 0,
+    /// `Login Data For Account`, passwords saved to the Google account
+    Account = 1,
+}
+
+impl Display for LoginStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Local => "local",
+            Self::Account => "account",
+        })
+    }
+}
+
+/// A note attached to a login, `password_notes`
+#[non_exhaustive]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ffi", repr(C))]
+pub struct PasswordNote {
+    /// Empty for the note the password manager shows
+    pub key: String,
+    /// Decrypted like the password
+    pub value: Option<SecretString>,
+    pub date_created: Option<DateTime<Utc>>,
+    pub confidential: bool,
+}
+
+impl From<password_notes::Model> for PasswordNote {
+    fn from(v: password_notes::Model) -> Self {
+        Self {
+            key: v.key,
+            value: None,
+            date_created: v
+                .date_created
+                .micros_to_chromium_utc(),
+            confidential: v.confidential.unwrap_or_default() != 0,
+        }
+    }
+}
+
+/// Why the password manager flags a credential, `InsecureType`
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InsecurityType {
+    /// Found in a data breach
+    Leaked,
+    /// Entered on a phishing site
+    Phished,
+    Weak,
+    /// The same password is saved for another site
+    Reused,
+    Other(i32),
+}
+
+impl From<i32> for InsecurityType {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Leaked,
+            1 => Self::Phished,
+            2 => Self::Weak,
+            3 => Self::Reused,
+            v => Self::Other(v),
+        }
+    }
+}
+
+impl From<InsecurityType> for i32 {
+    fn from(value: InsecurityType) -> Self {
+        match value {
+            InsecurityType::Leaked => 0,
+            InsecurityType::Phished => 1,
+            InsecurityType::Weak => 2,
+            InsecurityType::Reused => 3,
+            InsecurityType::Other(v) => v,
+        }
+    }
+}
+
+impl Display for InsecurityType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Leaked => "leaked",
+            Self::Phished => "phished",
+            Self::Weak => "weak",
+            Self::Reused => "reused",
+            Self::Other(v) => return v.fmt(f),
+        })
+    }
+}
+
+/// A row of `insecure_credentials`
+#[non_exhaustive]
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ffi", repr(C))]
+pub struct InsecureCredential {
+    pub insecurity_type: InsecurityType,
+    /// When the issue was detected
+    pub create_time: Option<DateTime<Utc>>,
+    /// Dismissed by the user
+    pub is_muted: bool,
+    pub trigger_notification_from_backend: bool,
+}
+
+impl From<insecure_credentials::Model> for InsecureCredential {
+    fn from(v: insecure_credentials::Model) -> Self {
+        Self {
+            insecurity_type: v.insecurity_type.into(),
+            create_time: v
+                .create_time
+                .micros_to_chromium_utc(),
+            is_muted: v.is_muted != 0,
+            trigger_notification_from_backend: v.trigger_notification_from_backend != 0,
+        }
+    }
+}
+
+/// The row of Microsoft Edge's `logins_edge_extended` for a login.
+///
+/// Edge's breach check results are the [`InsecurityType::Leaked`] rows of [`LoginData::insecure_credentials`].
+#[non_exhaustive]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "ffi", repr(C))]
+pub struct EdgeLoginExtended {
+    /// How the password got into Edge, e.g. saved or imported
+    pub source: String,
+    /// The state of the weak password alert of the password monitor
+    pub strength_alert_status: i32,
+    pub password_nickname: String,
+    pub password_category: i32,
+}
+
+impl From<logins_edge_extended::Model> for EdgeLoginExtended {
+    fn from(v: logins_edge_extended::Model) -> Self {
+        Self {
+            source: v.source,
+            strength_alert_status: v.strength_alert_status,
+            password_nickname: v.password_nickname,
+            password_category: v.password_category,
+        }
+    }
+}
+
+/// How often the user dismissed the save prompt on a site, a row of `stats`
+#[non_exhaustive]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoginStats {
+    pub origin_domain: String,
+    pub username_value: Option<String>,
+    pub dismissal_count: i32,
+    pub update_time: Option<DateTime<Utc>>,
+    pub store: LoginStore,
+}
+
+impl LoginStats {
+    pub(crate) fn new(v: stats::Model, store: LoginStore) -> Self {
+        Self {
+            origin_domain: v.origin_domain,
+            username_value: v.username_value,
+            dismissal_count: v
+                .dismissal_count
+                .unwrap_or_default(),
+            update_time: v
+                .update_time
+                .micros_to_chromium_utc(),
+            store,
+        }
+    }
+}
+
+/// `PasswordForm::Scheme`, how the credential is submitted
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoginScheme {
+    /// A web form
+    #[default]
+    Html,
+    /// HTTP basic auth
+    Basic,
+    /// HTTP digest auth
+    Digest,
+    /// Another HTTP auth scheme, `kOther`
+    OtherAuth,
+    /// A form with the username only
+    UsernameOnly,
+    Other(i32),
+}
+
+impl From<i32> for LoginScheme {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Html,
+            1 => Self::Basic,
+            2 => Self::Digest,
+            3 => Self::OtherAuth,
+            4 => Self::UsernameOnly,
+            v => Self::Other(v),
+        }
+    }
+}
+
+impl From<LoginScheme> for i32 {
+    fn from(value: LoginScheme) -> Self {
+        match value {
+            LoginScheme::Html => 0,
+            LoginScheme::Basic => 1,
+            LoginScheme::Digest => 2,
+            LoginScheme::OtherAuth => 3,
+            LoginScheme::UsernameOnly => 4,
+            LoginScheme::Other(v) => v,
+        }
+    }
+}
+
+/// `PasswordForm::Type`, how the password was saved
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PasswordType {
+    /// Saved after a form was submitted
+    #[default]
+    FormSubmission,
+    /// Generated by the password manager
+    Generated,
+    /// Saved through the Credential Management API
+    Api,
+    /// Added in the settings
+    ManuallyAdded,
+    Imported,
+    /// Shared by another user
+    ReceivedViaSharing,
+    Other(i32),
+}
+
+impl From<i32> for PasswordType {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::FormSubmission,
+            1 => Self::Generated,
+            2 => Self::Api,
+            3 => Self::ManuallyAdded,
+            4 => Self::Imported,
+            5 => Self::ReceivedViaSharing,
+            v => Self::Other(v),
+        }
+    }
+}
+
+impl From<PasswordType> for i32 {
+    fn from(value: PasswordType) -> Self {
+        match value {
+            PasswordType::FormSubmission => 0,
+            PasswordType::Generated => 1,
+            PasswordType::Api => 2,
+            PasswordType::ManuallyAdded => 3,
+            PasswordType::Imported => 4,
+            PasswordType::ReceivedViaSharing => 5,
+            PasswordType::Other(v) => v,
+        }
+    }
+}
+
+/// `PasswordForm::GenerationUploadStatus`, the password generation vote sent for the form
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GenerationUploadStatus {
+    #[default]
+    NoSignalSent,
+    PositiveSignalSent,
+    NegativeSignalSent,
+    /// `kUnknownStatus`
+    UnknownStatus,
+    Other(i32),
+}
+
+impl From<i32> for GenerationUploadStatus {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::NoSignalSent,
+            1 => Self::PositiveSignalSent,
+            2 => Self::NegativeSignalSent,
+            10 => Self::UnknownStatus,
+            v => Self::Other(v),
+        }
+    }
+}
+
+impl From<GenerationUploadStatus> for i32 {
+    fn from(value: GenerationUploadStatus) -> Self {
+        match value {
+            GenerationUploadStatus::NoSignalSent => 0,
+            GenerationUploadStatus::PositiveSignalSent => 1,
+            GenerationUploadStatus::NegativeSignalSent => 2,
+            GenerationUploadStatus::UnknownStatus => 10,
+            GenerationUploadStatus::Other(v) => v,
+        }
+    }
+}
 
 #[non_exhaustive]
 #[derive(Clone)]
@@ -20,45 +371,77 @@ pub struct LoginData {
     pub username_element: Option<String>,
     pub username_value: Option<String>,
     pub password_element: Option<String>,
-    pub password_value: Option<String>,
+    pub password_value: Option<SecretString>,
     pub submit_element: String,
     pub signon_realm: String,
     pub date_created: Option<DateTime<Utc>>,
-    pub blacklisted_by_user: i32,
-    pub scheme: i32,
-    pub password_type: i32,
+    /// Saving passwords is blocked for the site, "Never" was picked
+    pub blacklisted_by_user: bool,
+    pub scheme: LoginScheme,
+    pub password_type: PasswordType,
     pub times_used: i64,
     pub form_data: Option<Vec<u8>>,
     pub display_name: String,
     pub icon_url: String,
     pub federation_url: String,
     pub skip_zero_click: i32,
-    pub generation_upload_status: i32,
+    pub generation_upload_status: GenerationUploadStatus,
     pub possible_username_pairs: Option<Vec<u8>>,
     pub id: i32,
     pub date_last_used: Option<DateTime<Utc>>,
-    // pub moving_blocked_for:             Option<Vec<u8>>,
+    /// Accounts the login may not be moved to, a serialized protobuf
+    pub moving_blocked_for: Option<Vec<u8>>,
     pub date_password_modified: Option<DateTime<Utc>>,
-    // pub sender_email:                   String,
-    // pub sender_name:                    String,
-    // NOTE: I'm not sure what it do
-    // pub date_received:                  i64,
-    // pub sharing_notification_displayed: i32,
-    // pub keychain_identifier:            Vec<u8>,
-    // pub sender_profile_image_url:       Option<String>,
+    /// Who shared the password, empty for logins that were not shared
+    pub sender_email: String,
+    pub sender_name: String,
+    /// When the shared password was received
+    pub date_received: Option<DateTime<Utc>>,
+    /// The user was told about the shared password
+    pub sharing_notification_displayed: bool,
+    /// The macOS Keychain item the password was imported from
+    pub keychain_identifier: Vec<u8>,
+    pub sender_profile_image_url: Option<String>,
+    pub store: LoginStore,
+    pub notes: Vec<PasswordNote>,
+    /// Leaked, phished, weak or reused, empty when the password check found nothing
+    pub insecure_credentials: Vec<InsecureCredential>,
+    /// Only Microsoft Edge has it
+    pub edge: Option<EdgeLoginExtended>,
+    /// Decoded `form_data`, `None` when it is missing or can't be decoded
+    pub form: Option<FormData>,
+    /// Decoded `possible_username_pairs`
+    pub possible_usernames: Vec<PossibleUsername>,
 }
 
 impl LoginData {
+    /// The note the password manager shows
+    pub fn note(&self) -> Option<&str> {
+        self.notes
+            .iter()
+            .find(|v| v.key.is_empty())
+            .and_then(|v| v.value.as_ref())
+            .map(SecretString::expose_secret)
+    }
+
+    /// The flag of `kind`, if the credential has it
+    pub fn insecurity(&self, kind: InsecurityType) -> Option<&InsecureCredential> {
+        self.insecure_credentials
+            .iter()
+            .find(|v| v.insecurity_type == kind)
+    }
+
     pub fn to_csv<D: Display>(&self, sep: D) -> String {
         format!(
-            "{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}",
+            "{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}",
             self.origin_url,
             self.username_value
                 .as_deref()
                 .unwrap_or_default(),
             self.display_name,
             self.password_value
-                .as_deref()
+                .as_ref()
+                .map(SecretString::expose_secret)
                 .unwrap_or_default(),
             self.date_created
                 .unwrap_or_default(),
@@ -66,12 +449,133 @@ impl LoginData {
                 .unwrap_or_default(),
             self.date_password_modified
                 .unwrap_or_default(),
+            self.store,
         )
     }
 }
 
+/// A [`LoginData`] that keeps the encrypted password and decrypts it on first access.
+///
+/// The decrypted password is cached, so the work is done at most once per login.
+#[derive(Clone)]
+pub struct LazyLoginData {
+    login: LoginData,
+    password_value: EncryptedValue,
+    /// The encrypted values of `login.notes`
+    note_values: Vec<EncryptedValue>,
+    crypto: Arc<Decrypter>,
+    yandex: Option<Arc<YandexEncryptor>>,
+    decrypted_password: OnceLock<Option<SecretString>>,
+    decrypted_notes: OnceLock<Vec<PasswordNote>>,
+}
+
+impl std::fmt::Debug for LazyLoginData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LazyLoginData")
+            .field("login", &self.login)
+            .field(
+                "decrypted",
+                &self
+                    .decrypted_password
+                    .get()
+                    .is_some(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl LazyLoginData {
+    pub(crate) fn new(mut value: RawLogin, crypto: Arc<Decrypter>) -> Self {
+        let yandex = value.yandex.take();
+        let (login, password_value, note_values) = value.split();
+        Self {
+            login,
+            password_value,
+            note_values,
+            crypto,
+            yandex,
+            decrypted_password: OnceLock::new(),
+            decrypted_notes: OnceLock::new(),
+        }
+    }
+
+    /// The login data without the password and the note values
+    pub const fn login(&self) -> &LoginData {
+        &self.login
+    }
+
+    pub fn encrypted_password(&self) -> Option<&[u8]> {
+        self.password_value.as_deref()
+    }
+
+    /// Decrypt the password if it has not been decrypted yet
+    pub fn password(&self) -> Option<&str> {
+        self.decrypted_password
+            .get_or_init(|| {
+                decrypt_password(
+                    &self.crypto,
+                    self.yandex.as_deref(),
+                    &self.login,
+                    self.password_value.clone(),
+                )
+            })
+            .as_ref()
+            .map(SecretString::expose_secret)
+    }
+
+    /// Decrypt the notes if they have not been decrypted yet
+    pub fn notes(&self) -> &[PasswordNote] {
+        self.decrypted_notes
+            .get_or_init(|| {
+                self.login
+                    .notes
+                    .iter()
+                    .zip(&self.note_values)
+                    .map(|(note, value)| PasswordNote {
+                        value: decrypt_value(&self.crypto, value.clone()),
+                        ..note.clone()
+                    })
+                    .collect()
+            })
+    }
+
+    /// Decrypt and convert to [`LoginData`]
+    pub fn into_login_data(self) -> LoginData {
+        self.password();
+        self.notes();
+        let Self {
+            mut login,
+            decrypted_password,
+            decrypted_notes,
+            ..
+        } = self;
+        login.password_value = decrypted_password
+            .into_inner()
+            .flatten();
+        login.notes = decrypted_notes
+            .into_inner()
+            .unwrap_or_default();
+        login
+    }
+}
+
+impl From<LazyLoginData> for LoginData {
+    fn from(value: LazyLoginData) -> Self {
+        value.into_login_data()
+    }
+}
+
 impl From<logins::Model> for LoginData {
     fn from(v: logins::Model) -> Self {
+        let form = v
+            .form_data
+            .as_deref()
+            .and_then(FormData::from_pickle);
+        let possible_usernames = v
+            .possible_username_pairs
+            .as_deref()
+            .and_then(PossibleUsername::from_pickle)
+            .unwrap_or_default();
         Self {
             origin_url: v.origin_url,
             action_url: v.action_url,
@@ -86,9 +590,12 @@ impl From<logins::Model> for LoginData {
             date_created: v
                 .date_created
                 .micros_to_chromium_utc(),
-            blacklisted_by_user: v.blacklisted_by_user,
-            scheme: v.scheme,
-            password_type: v.password_type.unwrap_or_default(),
+            blacklisted_by_user: v.blacklisted_by_user != 0,
+            scheme: v.scheme.into(),
+            password_type: v
+                .password_type
+                .unwrap_or_default()
+                .into(),
             times_used: v.times_used.unwrap_or_default(),
             form_data: v.form_data,
             display_name: v.display_name.unwrap_or_default(),
@@ -101,24 +608,375 @@ impl From<logins::Model> for LoginData {
                 .unwrap_or_default(),
             generation_upload_status: v
                 .generation_upload_status
-                .unwrap_or_default(),
+                .unwrap_or_default()
+                .into(),
             possible_username_pairs: v.possible_username_pairs,
             id: v.id,
             date_last_used: v
                 .date_last_used
                 .micros_to_chromium_utc(),
-            // moving_blocked_for:             v.moving_blocked_for,
+            moving_blocked_for: v.moving_blocked_for,
             date_password_modified: v
                 .date_password_modified
                 .micros_to_chromium_utc(),
-            // sender_email:                   v.sender_email.unwrap_or_default(),
-            // sender_name:                    v.sender_name.unwrap_or_default(),
-            // date_received:                  v.date_received.unwrap_or_default(),
-            // sharing_notification_displayed: v.sharing_notification_displayed,
-            // keychain_identifier:            v
-            //     .keychain_identifier
-            //     .unwrap_or_default(),
-            // sender_profile_image_url:       v.sender_profile_image_url,
+            sender_email: v.sender_email.unwrap_or_default(),
+            sender_name: v.sender_name.unwrap_or_default(),
+            date_received: v
+                .date_received
+                .as_ref()
+                .and_then(I64ToChromiumDateTime::micros_to_chromium_utc),
+            sharing_notification_displayed: v
+                .sharing_notification_displayed
+                .is_some_and(|v| v != 0),
+            keychain_identifier: v
+                .keychain_identifier
+                .unwrap_or_default(),
+            sender_profile_image_url: v.sender_profile_image_url,
+            store: LoginStore::default(),
+            notes: vec![],
+            insecure_credentials: vec![],
+            edge: None,
+            form,
+            possible_usernames,
         }
     }
 }
+
+/// A password or note value as stored
+type EncryptedValue = Option<Vec<u8>>;
+
+/// The password of a Yandex store has the extra layer of [`YandexEncryptor`]
+fn decrypt_password(
+    crypto: &Decrypter,
+    yandex: Option<&YandexEncryptor>,
+    login: &LoginData,
+    value: EncryptedValue,
+) -> Option<SecretString> {
+    let mut buf = value?;
+    match yandex {
+        Some(yandex) => yandex
+            .decrypt(login, &buf)
+            .inspect_err(|_e| {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %_e, url = login.origin_url, "decrypt Yandex password failed");
+            })
+            .ok(),
+        None => crypto
+            .decrypt(&mut buf)
+            .inspect_err(|_e| {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %_e, url = login.origin_url, "decrypt password failed");
+            })
+            .ok(),
+    }
+}
+
+fn decrypt_value(crypto: &Decrypter, value: EncryptedValue) -> Option<SecretString> {
+    let mut buf = value?;
+    crypto.decrypt(&mut buf).ok()
+}
+
+/// A `logins` row with its `password_notes` and `insecure_credentials` rows
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct RawLogin {
+    pub login: logins::Model,
+    pub store: LoginStore,
+    pub notes: Vec<password_notes::Model>,
+    pub insecure_credentials: Vec<insecure_credentials::Model>,
+    pub edge: Option<logins_edge_extended::Model>,
+    /// The key of the extra layer when the store is a `Ya Passman Data`
+    pub yandex: Option<Arc<YandexEncryptor>>,
+}
+
+impl RawLogin {
+    /// The [`LoginData`] without secrets, the encrypted password and note values
+    fn split(self) -> (LoginData, EncryptedValue, Vec<EncryptedValue>) {
+        let Self {
+            mut login,
+            store,
+            notes,
+            insecure_credentials,
+            edge,
+            ..
+        } = self;
+        let password_value = login.password_value.take();
+        let note_values = notes
+            .iter()
+            .map(|v| v.value.clone())
+            .collect();
+
+        let mut res = LoginData::from(login);
+        res.store = store;
+        res.notes = notes
+            .into_iter()
+            .map(PasswordNote::from)
+            .collect();
+        res.insecure_credentials = insecure_credentials
+            .into_iter()
+            .map(InsecureCredential::from)
+            .collect();
+        res.edge = edge.map(EdgeLoginExtended::from);
+        (res, password_value, note_values)
+    }
+
+    pub fn decrypt(mut self, crypto: &Decrypter) -> LoginData {
+        let yandex = self.yandex.take();
+        let (mut login, password_value, note_values) = self.split();
+        login.password_value = decrypt_password(crypto, yandex.as_deref(), &login, password_value);
+        for (note, value) in login
+            .notes
+            .iter_mut()
+            .zip(note_values)
+        {
+            note.value = decrypt_value(crypto, value);
+        }
+        login
+    }
+}
+
+/// The rows of the tables that extend `logins`, tagged with their store
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct LoginRows {
+    pub notes: Vec<(password_notes::Model, LoginStore)>,
+    pub insecure_credentials: Vec<(insecure_credentials::Model, LoginStore)>,
+    pub edge: Vec<(logins_edge_extended::Model, LoginStore)>,
+}
+
+/// Attach the extending rows to the merged logins of [`merge_stores`],
+/// the rows are matched by the `id` of the store the login was taken from
+pub fn join_login_rows(merged: Vec<(logins::Model, LoginStore)>, rows: LoginRows) -> Vec<RawLogin> {
+    let LoginRows { notes, insecure_credentials, edge } = rows;
+    let mut notes_of: HashMap<_, Vec<_>> = HashMap::new();
+    for (note, store) in notes {
+        notes_of
+            .entry((store, note.parent_id))
+            .or_default()
+            .push(note);
+    }
+    let mut insecure_of: HashMap<_, Vec<_>> = HashMap::new();
+    for (credential, store) in insecure_credentials {
+        if let Some(parent_id) = credential.parent_id {
+            insecure_of
+                .entry((store, parent_id))
+                .or_default()
+                .push(credential);
+        }
+    }
+
+    let mut edge_of: HashMap<_, _> = edge
+        .into_iter()
+        .map(|(v, store)| ((store, v.id), v))
+        .collect();
+
+    merged
+        .into_iter()
+        .map(|(login, store)| RawLogin {
+            notes: notes_of
+                .remove(&(store, login.id))
+                .unwrap_or_default(),
+            insecure_credentials: insecure_of
+                .remove(&(store, login.id))
+                .unwrap_or_default(),
+            edge: edge_of.remove(&(store, login.id)),
+            login,
+            store,
+            yandex: None,
+        })
+        .collect()
+}
+
+/// Merge the rows of both stores, one row per `(signon_realm, username_value)`.
+///
+/// The most recently modified password wins, local on a tie.
+pub fn merge_stores(
+    local: Vec<logins::Model>,
+    account: Vec<logins::Model>,
+) -> Vec<(logins::Model, LoginStore)> {
+    let mut merged: Vec<(logins::Model, LoginStore)> =
+        Vec::with_capacity(local.len() + account.len());
+    let mut index = HashMap::with_capacity(merged.capacity());
+
+    let rows = local
+        .into_iter()
+        .map(|v| (v, LoginStore::Local))
+        .chain(
+            account
+                .into_iter()
+                .map(|v| (v, LoginStore::Account)),
+        );
+    for (model, store) in rows {
+        match index.entry((model.signon_realm.clone(), model.username_value.clone())) {
+            Entry::Vacant(e) => {
+                e.insert(merged.len());
+                merged.push((model, store));
+            },
+            Entry::Occupied(e) => {
+                if let Some(slot) = merged.get_mut(*e.get())
+                    && model.date_password_modified > slot.0.date_password_modified
+                {
+                    *slot = (model, store);
+                }
+            },
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    pub fn model(id: i32, realm: &str, user: &str, modified: i64) -> logins::Model {
+        logins::Model {
+            origin_url: realm.to_owned(),
+            action_url: None,
+            username_element: None,
+            username_value: Some(user.to_owned()),
+            password_element: None,
+            password_value: None,
+            submit_element: None,
+            signon_realm: realm.to_owned(),
+            date_created: 0,
+            blacklisted_by_user: 0,
+            scheme: 0,
+            password_type: None,
+            times_used: None,
+            form_data: None,
+            display_name: None,
+            icon_url: None,
+            federation_url: None,
+            skip_zero_click: None,
+            generation_upload_status: None,
+            possible_username_pairs: None,
+            id,
+            date_last_used: 0,
+            moving_blocked_for: None,
+            date_password_modified: modified,
+            sender_email: None,
+            sender_name: None,
+            date_received: None,
+            sharing_notification_displayed: None,
+            keychain_identifier: None,
+            sender_profile_image_url: None,
+        }
+    }
+
+    #[test]
+    fn merge_keeps_both_stores() {
+        let local = vec![
+            model(1, "https://a.com/", "alice", 10),
+            model(2, "https://b.com/", "bob", 10),
+        ];
+        let account = vec![
+            model(1, "https://a.com/", "alice", 20),
+            model(2, "https://b.com/", "bob", 10),
+            model(3, "https://c.com/", "carol", 0),
+        ];
+
+        let merged: Vec<_> = merge_stores(local, account)
+            .into_iter()
+            .map(|(v, store)| (v.signon_realm, store))
+            .collect();
+
+        assert_eq!(
+            merged,
+            [
+                ("https://a.com/".to_owned(), LoginStore::Account),
+                ("https://b.com/".to_owned(), LoginStore::Local),
+                ("https://c.com/".to_owned(), LoginStore::Account),
+            ]
+        );
+    }
+
+    #[test]
+    fn join_by_store() {
+        let merged = merge_stores(
+            vec![model(1, "https://a.com/", "alice", 10)],
+            vec![
+                model(1, "https://b.com/", "bob", 10),
+                model(2, "https://a.com/", "alice", 0),
+            ],
+        );
+        let note = |parent_id, key: &str| password_notes::Model {
+            id: 1,
+            parent_id,
+            key: key.to_owned(),
+            value: None,
+            date_created: 0,
+            confidential: Some(1),
+        };
+        let insecure = |parent_id, insecurity_type| insecure_credentials::Model {
+            parent_id: Some(parent_id),
+            insecurity_type,
+            create_time: 13_340_000_000_000_000,
+            is_muted: 1,
+            trigger_notification_from_backend: 0,
+        };
+
+        let edge = logins_edge_extended::Model {
+            id: 1,
+            source: "imported".to_owned(),
+            strength_alert_status: 1,
+            password_nickname: String::new(),
+            password_category: 0,
+        };
+
+        let joined = join_login_rows(
+            merged,
+            LoginRows {
+                notes: vec![
+                    (note(1, ""), LoginStore::Local),
+                    (note(1, "account"), LoginStore::Account),
+                    (note(2, "stale"), LoginStore::Account),
+                ],
+                insecure_credentials: vec![
+                    (insecure(1, 0), LoginStore::Local),
+                    (insecure(1, 2), LoginStore::Local),
+                    (insecure(1, 9), LoginStore::Local),
+                    (insecure(1, 3), LoginStore::Account),
+                ],
+                edge: vec![(edge, LoginStore::Account)],
+            },
+        );
+        let [a, b] = joined.as_slice()
+        else {
+            panic!("{joined:?}");
+        };
+        assert_eq!(a.login.signon_realm, "https://a.com/");
+        assert_eq!(a.notes.len(), 1);
+        assert_eq!(a.insecure_credentials.len(), 3);
+        assert_eq!(b.notes[0].key, "account");
+        assert!(a.edge.is_none());
+        assert_eq!(
+            b.edge
+                .as_ref()
+                .map(|v| v.source.as_str()),
+            Some("imported")
+        );
+
+        let (login, ..) = a.clone().split();
+        assert!(login.notes[0].confidential);
+        assert_eq!(login.insecure_credentials.len(), 3);
+        assert!(
+            login
+                .insecurity(InsecurityType::Other(9))
+                .is_some()
+        );
+        let leaked = login
+            .insecurity(InsecurityType::Leaked)
+            .unwrap();
+        assert!(leaked.is_muted);
+        assert_eq!(
+            leaked.create_time,
+            13_340_000_000_000_000.micros_to_chromium_utc()
+        );
+        assert!(
+            login
+                .insecurity(InsecurityType::Reused)
+                .is_none()
+        );
+    }
+}
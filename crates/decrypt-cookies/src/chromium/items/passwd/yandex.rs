@@ -0,0 +1,172 @@
+//! Yandex wraps each password of `Ya Passman Data` in another AES-256-GCM layer.
+//!
+//! The key of that layer is sealed by `OSCrypt` in `meta.local_encryptor_data`,
+//! the associated data is the SHA-1 of the url and username fields of the login.
+
+use aes_gcm::{Aes256Gcm, KeyInit, aead::Aead};
+use chromium_crypto::{Decrypter, SecretBytes, SecretString};
+use sha1::{Digest, Sha1};
+use snafu::{OptionExt, ResultExt, Snafu, ensure};
+
+use super::LoginData;
+
+#[derive(Debug)]
+#[derive(Snafu)]
+#[snafu(visibility(pub))]
+#[non_exhaustive]
+pub enum YandexError {
+    #[snafu(display(
+        "The profile is protected by a Yandex master password, which is not supported"
+    ))]
+    MasterPassword,
+    #[snafu(display("Importing into a `Ya Passman Data` is not supported"))]
+    Import,
+    #[snafu(display("`local_encryptor_data` has no sealed key"))]
+    NoSealedKey,
+    #[snafu(display("Unseal the local encryptor key failed: {source}"))]
+    Unseal {
+        source: chromium_crypto::error::CryptoError,
+    },
+    #[snafu(display("Unknown local encryptor key format"))]
+    KeyFormat,
+    #[snafu(display("Decrypt password failed: {source}"))]
+    AesGcm {
+        source: aes_gcm::Error,
+    },
+    #[snafu(display("Password is not UTF-8: {source}"))]
+    Utf8 {
+        source: std::string::FromUtf8Error,
+    },
+}
+
+type Result<T> = std::result::Result<T, YandexError>;
+
+/// The prefix `OSCrypt` puts on the sealed key
+const SEALED_KEY_PREFIX: &[u8] = b"v10";
+/// A 68 bytes record, sealed like a `v10` value of the platform
+const SEALED_KEY_LEN: usize = if cfg!(target_os = "windows") {
+    // nonce, ciphertext and tag
+    12 + 68 + 16
+}
+else {
+    // padded AES-128-CBC
+    80
+};
+/// Protobuf tags of the unsealed record, `1: 1`, then the 32 bytes key in `2`
+const KEY_HEADER: [u8; 4] = [0x08, 0x01, 0x12, 0x20];
+const NONCE_LEN: usize = 12;
+
+/// The key of the extra password layer of one `Ya Passman Data`
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct YandexEncryptor {
+    key: SecretBytes,
+}
+
+impl YandexEncryptor {
+    /// Unseal the key of `meta.local_encryptor_data` with the profile [`Decrypter`]
+    pub fn unseal(crypto: &Decrypter, local_encryptor_data: &[u8]) -> Result<Self> {
+        let start = local_encryptor_data
+            .windows(SEALED_KEY_PREFIX.len())
+            .position(|v| v == SEALED_KEY_PREFIX)
+            .context(NoSealedKeySnafu)?;
+        let mut sealed = local_encryptor_data
+            .get(start..start + SEALED_KEY_PREFIX.len() + SEALED_KEY_LEN)
+            .context(NoSealedKeySnafu)?
+            .to_vec();
+        let record = crypto
+            .decrypt_raw(&mut sealed)
+            .context(UnsealSnafu)?;
+        Self::from_record(record.expose_secret())
+    }
+
+    fn from_record(record: &[u8]) -> Result<Self> {
+        let key = record
+            .strip_prefix(&KEY_HEADER)
+            .and_then(|v| v.get(..32))
+            .context(KeyFormatSnafu)?;
+        Ok(Self { key: SecretBytes::new(key.to_vec()) })
+    }
+
+    /// Decrypt a `password_value`, the nonce followed by the ciphertext
+    pub fn decrypt(&self, login: &LoginData, password_value: &[u8]) -> Result<SecretString> {
+        ensure!(password_value.len() > NONCE_LEN, KeyFormatSnafu);
+        let (nonce, ciphertext) = password_value.split_at(NONCE_LEN);
+        let aad = associated_data(login);
+
+        let plain = Aes256Gcm::new(self.key.expose_secret().into())
+            .decrypt(
+                nonce.into(),
+                aes_gcm::aead::Payload { msg: ciphertext, aad: &aad },
+            )
+            .context(AesGcmSnafu)?;
+        String::from_utf8(plain)
+            .map(SecretString::from)
+            .context(Utf8Snafu)
+    }
+}
+
+/// SHA-1 of `origin_url`, `username_element`, `username_value`, `password_element`
+/// and `signon_realm` joined by `\0`
+fn associated_data(login: &LoginData) -> [u8; 20] {
+    let mut hasher = Sha1::new();
+    hasher.update(&login.origin_url);
+    for v in [
+        &login.username_element,
+        &login.username_value,
+        &login.password_element,
+    ] {
+        hasher.update(b"\0");
+        hasher.update(v.as_deref().unwrap_or_default());
+    }
+    hasher.update(b"\0");
+    hasher.update(&login.signon_realm);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypt() {
+        let key = [7; 32];
+        let mut record = KEY_HEADER.to_vec();
+        record.extend_from_slice(&key);
+        record.extend_from_slice(&[0; 32]);
+        let encryptor = YandexEncryptor::from_record(&record).unwrap();
+
+        let mut login =
+            LoginData::from(super::super::tests::model(1, "https://a.com/", "alice", 0));
+        login.username_element = Some("login".to_owned());
+        let nonce = [1; NONCE_LEN];
+        let mut value = nonce.to_vec();
+        value.extend(
+            Aes256Gcm::new(&key.into())
+                .encrypt(
+                    &nonce.into(),
+                    aes_gcm::aead::Payload {
+                        msg: b"hunter2",
+                        aad: &associated_data(&login),
+                    },
+                )
+                .unwrap(),
+        );
+
+        assert_eq!(
+            encryptor
+                .decrypt(&login, &value)
+                .unwrap()
+                .expose_secret(),
+            "hunter2"
+        );
+        // the associated data binds the password to its login
+        login.username_value = Some("bob".to_owned());
+        assert!(
+            encryptor
+                .decrypt(&login, &value)
+                .is_err()
+        );
+        assert!(YandexEncryptor::from_record(&record[1..]).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! `base::Pickle` and plain structs, how Chromium serializes values into a buffer
+//!
+//! <https://source.chromium.org/chromium/chromium/src/+/main:base/pickle.cc>
+
+/// A `base::Pickle`, a `u32` payload size then fields aligned to 4 bytes
+pub struct Pickle<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Pickle<'a> {
+    pub fn new(payload: &'a [u8]) -> Option<Self> {
+        let (size, data) = payload.split_first_chunk::<4>()?;
+        let size = u32::from_le_bytes(*size) as usize;
+        Some(Self { data: data.get(..size)? })
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let aligned = len.checked_next_multiple_of(4)?;
+        let res = self.data.get(..len)?;
+        self.data = self
+            .data
+            .get(aligned..)
+            .unwrap_or_default();
+        Some(res)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.read_bytes(N)?
+            .first_chunk()
+            .copied()
+    }
+
+    pub fn int(&mut self) -> Option<i32> {
+        self.read_array()
+            .map(i32::from_le_bytes)
+    }
+
+    pub fn u32(&mut self) -> Option<u32> {
+        self.read_array()
+            .map(u32::from_le_bytes)
+    }
+
+    pub fn i64(&mut self) -> Option<i64> {
+        self.read_array()
+            .map(i64::from_le_bytes)
+    }
+
+    pub fn u64(&mut self) -> Option<u64> {
+        self.read_array()
+            .map(u64::from_le_bytes)
+    }
+
+    pub fn bool(&mut self) -> Option<bool> {
+        self.int().map(|v| v != 0)
+    }
+
+    /// Length prefixed bytes, invalid UTF-8 is replaced
+    pub fn string(&mut self) -> Option<String> {
+        let len = usize::try_from(self.int()?).ok()?;
+        self.read_bytes(len)
+            .map(|v| String::from_utf8_lossy(v).into_owned())
+    }
+
+    /// Length in UTF-16 code units, then the units
+    pub fn string16(&mut self) -> Option<String> {
+        let len = usize::try_from(self.int()?).ok()?;
+        let bytes = self.read_bytes(len.checked_mul(2)?)?;
+        let (units, _) = bytes.as_chunks::<2>();
+        Some(String::from_utf16_lossy(
+            &units
+                .iter()
+                .map(|&v| u16::from_le_bytes(v))
+                .collect::<Vec<_>>(),
+        ))
+    }
+}
+
+/// Fixed size payloads are C structs written as is, fields at their natural alignment
+pub fn i32_at(payload: &[u8], offset: usize) -> Option<i32> {
+    payload
+        .get(offset..)?
+        .first_chunk()
+        .copied()
+        .map(i32::from_le_bytes)
+}
+
+pub fn u32_at(payload: &[u8], offset: usize) -> Option<u32> {
+    payload
+        .get(offset..)?
+        .first_chunk()
+        .copied()
+        .map(u32::from_le_bytes)
+}
+
+pub fn i64_at(payload: &[u8], offset: usize) -> Option<i64> {
+    payload
+        .get(offset..)?
+        .first_chunk()
+        .copied()
+        .map(i64::from_le_bytes)
+}
+
+pub fn u64_at(payload: &[u8], offset: usize) -> Option<u64> {
+    payload
+        .get(offset..)?
+        .first_chunk()
+        .copied()
+        .map(u64::from_le_bytes)
+}
+
+pub fn bool_at(payload: &[u8], offset: usize) -> Option<bool> {
+    payload
+        .get(offset)
+        .map(|&v| v != 0)
+}
+
+#[cfg(test)]
+pub mod tests {
+    /// Writes fields like `base::Pickle`
+    #[derive(Default)]
+    pub struct PickleWriter(Vec<u8>);
+
+    impl PickleWriter {
+        fn align(&mut self) {
+            while !self.0.len().is_multiple_of(4) {
+                self.0.push(0);
+            }
+        }
+
+        pub fn int(&mut self, v: i32) -> &mut Self {
+            self.0
+                .extend_from_slice(&v.to_le_bytes());
+            self
+        }
+
+        pub fn i64(&mut self, v: i64) -> &mut Self {
+            self.0
+                .extend_from_slice(&v.to_le_bytes());
+            self
+        }
+
+        pub fn u64(&mut self, v: u64) -> &mut Self {
+            self.0
+                .extend_from_slice(&v.to_le_bytes());
+            self
+        }
+
+        pub fn string(&mut self, v: &str) -> &mut Self {
+            self.int(v.len() as i32);
+            self.0
+                .extend_from_slice(v.as_bytes());
+            self.align();
+            self
+        }
+
+        pub fn string16(&mut self, v: &str) -> &mut Self {
+            let units: Vec<u16> = v.encode_utf16().collect();
+            self.int(units.len() as i32);
+            for unit in units {
+                self.0
+                    .extend_from_slice(&unit.to_le_bytes());
+            }
+            self.align();
+            self
+        }
+
+        pub fn finish(&self) -> Vec<u8> {
+            let mut res = (self.0.len() as u32)
+                .to_le_bytes()
+                .to_vec();
+            res.extend_from_slice(&self.0);
+            res
+        }
+    }
+}
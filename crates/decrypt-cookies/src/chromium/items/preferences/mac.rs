@@ -0,0 +1,157 @@
+//! The MACs of tracked preferences, `HMAC-SHA256(seed, device_id + path + value)`
+//!
+//! <https://source.chromium.org/chromium/chromium/src/+/main:services/preferences/tracked/pref_hash_calculator.cc>
+//! <https://source.chromium.org/chromium/chromium/src/+/main:base/json/json_writer.cc>
+
+use std::fmt::Write;
+
+use hmac::{Hmac, Mac};
+use serde_json::{Map, Number, Value};
+use sha2::Sha256;
+
+/// `value` serialized like `base::JSONWriter`, empty containers are pruned from dictionaries
+pub fn value_as_string(value: Option<&Value>) -> String {
+    let mut res = String::new();
+    match value {
+        None => {},
+        Some(Value::Object(v)) => write_value(&mut res, &Value::Object(without_empty_children(v))),
+        Some(v) => write_value(&mut res, v),
+    }
+    res
+}
+
+/// `DeepCopyWithoutEmptyChildren`
+fn without_empty_children(dict: &Map<String, Value>) -> Map<String, Value> {
+    dict.iter()
+        .filter_map(|(k, v)| Some((k.clone(), prune(v)?)))
+        .collect()
+}
+
+fn prune(value: &Value) -> Option<Value> {
+    match value {
+        Value::Object(v) => {
+            let v = without_empty_children(v);
+            (!v.is_empty()).then_some(Value::Object(v))
+        },
+        Value::Array(v) => {
+            let v: Vec<_> = v
+                .iter()
+                .filter_map(prune)
+                .collect();
+            (!v.is_empty()).then_some(Value::Array(v))
+        },
+        v => Some(v.clone()),
+    }
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(v) => _ = write!(out, "{v}"),
+        Value::Number(v) => write_number(out, v),
+        Value::String(v) => write_string(out, v),
+        Value::Array(v) => {
+            out.push('[');
+            for (i, item) in v.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        },
+        Value::Object(v) => {
+            // `base::Value::Dict` is sorted by the bytes of the keys
+            let mut entries: Vec<_> = v.iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (k, v)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, k);
+                out.push(':');
+                write_value(out, v);
+            }
+            out.push('}');
+        },
+    }
+}
+
+/// Integers out of the `int` range are parsed as doubles and written with `.0`
+fn write_number(out: &mut String, number: &Number) {
+    if let Some(v) = number
+        .as_i64()
+        .and_then(|v| i32::try_from(v).ok())
+    {
+        _ = write!(out, "{v}");
+        return;
+    }
+    let Some(v) = number.as_f64()
+    else {
+        return;
+    };
+    let start = out.len();
+    if v != 0.0 && v.abs() < 1e-6 {
+        _ = write!(out, "{v:e}");
+    }
+    else {
+        _ = write!(out, "{v}");
+    }
+    if !out[start..].contains(['.', 'e', 'E']) {
+        out.push_str(".0");
+    }
+}
+
+/// `EscapeJSONString`, `<` is escaped so the result can be put in a `<script>`
+fn write_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '<' | '\u{2028}' | '\u{2029}' => _ = write!(out, "\\u{:04X}", c as u32),
+            c if (c as u32) < 0x20 => _ = write!(out, "\\u{:04X}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Upper case hex of the MAC
+pub fn calculate(seed: &[u8], device_id: &str, path: &str, value: Option<&Value>) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(seed).expect("HMAC takes any key length");
+    mac.update(device_id.as_bytes());
+    mac.update(path.as_bytes());
+    mac.update(value_as_string(value).as_bytes());
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .fold(String::with_capacity(64), |mut acc, v| {
+            _ = write!(acc, "{v:02X}");
+            acc
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn canonical() {
+        let value = json!({"g": true, "a": 1, "b": [], "c": {"d": {}}, "e": "x<y\n", "f": 1.5, "h": [[], {"i": 2}], "j": 4_294_967_296_i64});
+        assert_eq!(
+            value_as_string(Some(&value)),
+            r#"{"a":1,"e":"x\u003Cy\n","f":1.5,"g":true,"h":[{"i":2}],"j":4294967296.0}"#
+        );
+        assert_eq!(value_as_string(None), "");
+        assert_eq!(value_as_string(Some(&json!([]))), "[]");
+    }
+}
@@ -0,0 +1,455 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use super::{
+    I64ToChromiumDateTime,
+    account::{self, ProfileAccount},
+};
+
+mod mac;
+
+/// The `Preferences` and `Secure Preferences` files of a profile
+///
+/// On Windows and macOS tracked preferences like `homepage` are moved to `Secure Preferences`,
+/// getters look there first.
+///
+/// <https://source.chromium.org/chromium/chromium/src/+/main:chrome/common/pref_names.h>
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Preferences {
+    pub preferences: Value,
+    /// `Null` when the file doesn't exist
+    pub secure_preferences: Value,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Startup {
+    pub kind: StartupKind,
+    /// Opened with [`StartupKind::Urls`] and [`StartupKind::LastAndUrls`]
+    pub urls: Vec<String>,
+}
+
+/// `session.restore_on_startup`
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StartupKind {
+    NewTab,
+    /// Continue where you left off
+    Last,
+    Urls,
+    LastAndUrls,
+    Other(i64),
+}
+
+impl From<i64> for StartupKind {
+    fn from(value: i64) -> Self {
+        match value {
+            1 => Self::Last,
+            4 => Self::Urls,
+            5 => Self::NewTab,
+            6 => Self::LastAndUrls,
+            v => Self::Other(v),
+        }
+    }
+}
+
+/// `default_search_provider_data.template_url_data`, set when the user picks a search engine
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SearchEngine {
+    pub name: String,
+    pub keyword: String,
+    /// With a `{searchTerms}` placeholder
+    pub url: String,
+}
+
+/// An entry of `profile.content_settings.exceptions`
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SiteException {
+    /// Like `media_stream_camera`, `media_stream_mic`, `notifications`, `cookies`, `geolocation`
+    pub content_type: String,
+    /// The site, like `https://example.com:443` or `[*.]example.com`
+    pub primary_pattern: String,
+    /// Usually `*`, the embedding site for some types
+    pub secondary_pattern: String,
+    pub setting: ContentSetting,
+    pub last_modified: Option<DateTime<Utc>>,
+    /// For settings granted for a while
+    pub expiration: Option<DateTime<Utc>>,
+}
+
+/// `ContentSetting`
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContentSetting {
+    Default,
+    Allow,
+    Block,
+    Ask,
+    SessionOnly,
+    DetectImportantContent,
+    Other(i64),
+}
+
+impl From<i64> for ContentSetting {
+    fn from(value: i64) -> Self {
+        match value {
+            0 => Self::Default,
+            1 => Self::Allow,
+            2 => Self::Block,
+            3 => Self::Ask,
+            4 => Self::SessionOnly,
+            5 => Self::DetectImportantContent,
+            v => Self::Other(v),
+        }
+    }
+}
+
+/// Which file holds a MAC
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrefsFile {
+    /// On Linux
+    Preferences,
+    /// On Windows and macOS
+    SecurePreferences,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MacCheck {
+    pub file: PrefsFile,
+    /// Like `homepage` or `extensions.settings.<id>`, empty for the `super_mac` over all MACs
+    pub path: String,
+    pub valid: bool,
+}
+
+/// Micros since 1601 as a string, `"0"` is none
+fn time_of(value: Option<&Value>) -> Option<DateTime<Utc>> {
+    value?
+        .as_str()?
+        .parse::<i64>()
+        .ok()
+        .filter(|&v| v != 0)?
+        .micros_to_chromium_utc()
+}
+
+/// `a.b.c` in nested dictionaries
+fn lookup<'a>(root: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(root, |v, k| v.get(k))
+}
+
+/// Leaves of `protection.macs` with their dotted paths
+fn collect_macs<'a>(macs: &'a Value, path: &mut String, out: &mut Vec<(String, &'a str)>) {
+    match macs {
+        Value::String(v) => out.push((path.clone(), v)),
+        Value::Object(v) => {
+            for (k, v) in v {
+                let len = path.len();
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(k);
+                collect_macs(v, path, out);
+                path.truncate(len);
+            }
+        },
+        _ => {},
+    }
+}
+
+impl Preferences {
+    /// Google Chrome's seed, from `resources.pak`. Chromium builds use an empty seed
+    pub const CHROME_SEED: [u8; 64] = [
+        0xE7, 0x48, 0xF3, 0x36, 0xD8, 0x5E, 0xA5, 0xF9, 0xDC, 0xDF, 0x25, 0xD8, 0xF3, 0x47, 0xA6,
+        0x5B, 0x4C, 0xDF, 0x66, 0x76, 0x00, 0xF0, 0x2D, 0xF6, 0x72, 0x4A, 0x2A, 0xF1, 0x8A, 0x21,
+        0x2D, 0x26, 0xB7, 0x88, 0xA2, 0x50, 0x86, 0x91, 0x0C, 0xF3, 0xA9, 0x03, 0x13, 0x69, 0x68,
+        0x71, 0xF3, 0xDC, 0x05, 0x82, 0x37, 0x30, 0xC9, 0x1D, 0xF8, 0xBA, 0x5C, 0x4F, 0xD9, 0xC8,
+        0x84, 0xB5, 0x05, 0xA8,
+    ];
+
+    /// Parse the content of `Preferences` and `Secure Preferences`
+    pub fn from_json(
+        preferences: &[u8],
+        secure_preferences: Option<&[u8]>,
+    ) -> serde_json::Result<Self> {
+        Ok(Self {
+            preferences: serde_json::from_slice(preferences)?,
+            secure_preferences: secure_preferences
+                .map(serde_json::from_slice)
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+
+    /// A preference by its dotted name like `download.default_directory`
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        lookup(&self.secure_preferences, path).or_else(|| lookup(&self.preferences, path))
+    }
+
+    fn get_str(&self, path: &str) -> Option<&str> {
+        self.get(path)?.as_str()
+    }
+
+    pub fn homepage(&self) -> Option<&str> {
+        self.get_str("homepage")
+    }
+
+    /// The home button opens the new tab page instead of [`Self::homepage`]
+    pub fn homepage_is_new_tab_page(&self) -> bool {
+        self.get("homepage_is_newtabpage")
+            .and_then(Value::as_bool)
+            .unwrap_or(true)
+    }
+
+    pub fn startup(&self) -> Startup {
+        Startup {
+            kind: self
+                .get("session.restore_on_startup")
+                .and_then(Value::as_i64)
+                .map_or(StartupKind::NewTab, StartupKind::from),
+            urls: self
+                .get("session.startup_urls")
+                .and_then(Value::as_array)
+                .map(|v| {
+                    v.iter()
+                        .filter_map(Value::as_str)
+                        .map(ToOwned::to_owned)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// `None` when the default of the browser is used
+    pub fn default_search_engine(&self) -> Option<SearchEngine> {
+        let data = self.get("default_search_provider_data.template_url_data")?;
+        let field = |k: &str| {
+            data.get(k)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        Some(SearchEngine {
+            name: field("short_name"),
+            keyword: field("keyword"),
+            url: field("url"),
+        })
+    }
+
+    /// `None` when the downloads go to the default dir of the platform
+    pub fn download_directory(&self) -> Option<&str> {
+        self.get_str("download.default_directory")
+    }
+
+    /// Ask where to save each file
+    pub fn prompt_for_download(&self) -> bool {
+        self.get("download.prompt_for_download")
+            .and_then(Value::as_bool)
+            .unwrap_or_default()
+    }
+
+    /// Per site content settings, website settings with structured values are skipped
+    pub fn site_exceptions(&self) -> Vec<SiteException> {
+        let Some(exceptions) = self
+            .get("profile.content_settings.exceptions")
+            .and_then(Value::as_object)
+        else {
+            return vec![];
+        };
+
+        let mut res = vec![];
+        for (content_type, sites) in exceptions {
+            let Some(sites) = sites.as_object()
+            else {
+                continue;
+            };
+            for (patterns, exception) in sites {
+                let Some(setting) = exception
+                    .get("setting")
+                    .and_then(Value::as_i64)
+                else {
+                    continue;
+                };
+                let (primary, secondary) = patterns
+                    .split_once(',')
+                    .unwrap_or((patterns, "*"));
+                res.push(SiteException {
+                    content_type: content_type.clone(),
+                    primary_pattern: primary.to_owned(),
+                    secondary_pattern: secondary.to_owned(),
+                    setting: setting.into(),
+                    last_modified: time_of(exception.get("last_modified")),
+                    expiration: time_of(exception.get("expiration")),
+                });
+            }
+        }
+        res
+    }
+
+    /// Exceptions of a content type like `notifications`
+    pub fn site_exceptions_of(&self, content_type: &str) -> Vec<SiteException> {
+        let mut res = self.site_exceptions();
+        res.retain(|v| v.content_type == content_type);
+        res
+    }
+
+    /// Signed in accounts, `account_info`
+    pub fn accounts(&self) -> Vec<ProfileAccount> {
+        account::from_preferences_value(&self.preferences).unwrap_or_default()
+    }
+
+    /// Check every MAC of `protection.macs` and the `protection.super_mac` over them.
+    ///
+    /// `seed` is [`Self::CHROME_SEED`] for Google Chrome, empty for Chromium builds.
+    /// `device_id` is the machine SID like `S-1-5-21-1-2-3` on Windows, empty on Linux.
+    /// An invalid MAC means the preference was changed by something else than the browser.
+    pub fn verify_macs(&self, seed: &[u8], device_id: &str) -> Vec<MacCheck> {
+        let mut res = vec![];
+        for (file, own, other) in [
+            (
+                PrefsFile::Preferences,
+                &self.preferences,
+                &self.secure_preferences,
+            ),
+            (
+                PrefsFile::SecurePreferences,
+                &self.secure_preferences,
+                &self.preferences,
+            ),
+        ] {
+            let Some(macs) = lookup(own, "protection.macs")
+            else {
+                continue;
+            };
+            let mut leaves = vec![];
+            collect_macs(macs, &mut String::new(), &mut leaves);
+            for (path, expected) in leaves {
+                let value = lookup(own, &path).or_else(|| lookup(other, &path));
+                let valid =
+                    mac::calculate(seed, device_id, &path, value).eq_ignore_ascii_case(expected);
+                res.push(MacCheck { file, path, valid });
+            }
+
+            if let Some(expected) = lookup(own, "protection.super_mac").and_then(Value::as_str) {
+                let valid =
+                    mac::calculate(seed, device_id, "", Some(macs)).eq_ignore_ascii_case(expected);
+                res.push(MacCheck { file, path: String::new(), valid });
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preferences() {
+        let prefs = br#"{
+            "homepage_is_newtabpage": false,
+            "session": {"restore_on_startup": 4, "startup_urls": ["https://a.com/"]},
+            "download": {"default_directory": "/home/a/dl"},
+            "default_search_provider_data": {"template_url_data": {"short_name": "Evil", "keyword": "e", "url": "https://e.com/?q={searchTerms}"}},
+            "account_info": [{"account_id": "1", "email": "a@gmail.com"}],
+            "profile": {"content_settings": {"exceptions": {
+                "media_stream_camera": {"https://meet.com:443,*": {"last_modified": "13340000000000000", "setting": 1}},
+                "notifications": {"https://spam.com:443,*": {"expiration": "0", "setting": 2}},
+                "site_engagement": {"https://a.com:443,*": {"setting": {"rawScore": 1.0}}}
+            }}}
+        }"#;
+        let secure = br#"{"homepage": "https://h.com/"}"#;
+        let prefs = Preferences::from_json(prefs, Some(secure)).unwrap();
+
+        assert_eq!(prefs.homepage(), Some("https://h.com/"));
+        assert!(!prefs.homepage_is_new_tab_page());
+        assert_eq!(
+            prefs.startup(),
+            Startup {
+                kind: StartupKind::Urls,
+                urls: vec!["https://a.com/".to_owned()]
+            }
+        );
+        assert_eq!(
+            prefs
+                .default_search_engine()
+                .unwrap()
+                .name,
+            "Evil"
+        );
+        assert_eq!(prefs.download_directory(), Some("/home/a/dl"));
+        assert_eq!(prefs.accounts()[0].email, "a@gmail.com");
+
+        let exceptions = prefs.site_exceptions();
+        assert_eq!(exceptions.len(), 2);
+        let camera = &prefs.site_exceptions_of("media_stream_camera")[0];
+        assert_eq!(camera.primary_pattern, "https://meet.com:443");
+        assert_eq!(camera.setting, ContentSetting::Allow);
+        assert_eq!(
+            camera.last_modified,
+            13_340_000_000_000_000.micros_to_chromium_utc()
+        );
+        assert_eq!(
+            prefs.site_exceptions_of("notifications")[0].expiration,
+            None
+        );
+    }
+
+    #[test]
+    fn macs() {
+        let secure = br#"{
+            "extensions": {"settings": {"abc": {"g": true, "a": 1, "b": [], "c": {"d": {}}, "e": "x<y", "f": 1.5}}},
+            "homepage": "https://h.com/",
+            "protection": {
+                "macs": {
+                    "browser": {"show_home_button": "71D6153F9F7B682D1A3D06DC48CDB893C201826B9C67899F042DE668BE8330F3"},
+                    "extensions": {"settings": {"abc": "50AC837AEEC0883F48C33FB528AD7B3CDB753B64CED829AA2E42DD3CBE95397A"}},
+                    "homepage": "F3D36717309691BBD1B05F00E3444FFBB088D2C711E4AEBE4BD00FE53CCA19EE"
+                },
+                "super_mac": "6DEF99F231EC8D559105D4BE51E37F6330D7C6055BFEAAA3F542396EBCFBB715"
+            }
+        }"#;
+        let mut prefs = Preferences::from_json(b"{}", Some(secure)).unwrap();
+        let checks = prefs.verify_macs(b"seed", "S-1-5-21-1-2-3");
+        assert_eq!(checks.len(), 4);
+        assert!(checks.iter().all(|v| v.valid), "{checks:?}");
+
+        prefs.secure_preferences["homepage"] = Value::String("https://evil.com/".to_owned());
+        let invalid: Vec<_> = prefs
+            .verify_macs(b"seed", "S-1-5-21-1-2-3")
+            .into_iter()
+            .filter(|v| !v.valid)
+            .map(|v| v.path)
+            .collect();
+        assert_eq!(invalid, ["homepage"]);
+    }
+}
@@ -0,0 +1,847 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use snss::Command;
+
+use super::{
+    I64ToChromiumDateTime,
+    history::PageTransition,
+    pickle::{Pickle, bool_at, i32_at, i64_at, u64_at},
+};
+
+mod snss;
+
+pub use snss::SnssError;
+
+/// The `Sessions/Session_*` and `Sessions/Tabs_*` files of a profile, the newest of each
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sessions {
+    /// The windows open now, or when the browser exited
+    pub session: Option<Session>,
+    /// Recently closed windows and tabs
+    pub tabs: Option<Session>,
+}
+
+/// The state rebuilt from the commands of a SNSS file
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Session {
+    pub windows: Vec<SessionWindow>,
+    /// Tabs closed on their own, only in a `Tabs_*` file
+    pub closed_tabs: Vec<SessionTab>,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionWindow {
+    pub id: i32,
+    pub kind: WindowKind,
+    pub bounds: Option<WindowBounds>,
+    /// Index in `tabs`
+    pub selected_tab_index: i32,
+    pub active: bool,
+    pub app_name: Option<String>,
+    /// Named by the user
+    pub user_title: Option<String>,
+    pub tabs: Vec<SessionTab>,
+    /// Only in a `Tabs_*` file
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindowKind {
+    #[default]
+    Normal,
+    Popup,
+    App,
+    DevTools,
+    AppPopup,
+    Other(i32),
+}
+
+impl From<i32> for WindowKind {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Normal,
+            1 => Self::Popup,
+            2 => Self::App,
+            3 => Self::DevTools,
+            4 => Self::AppPopup,
+            v => Self::Other(v),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[expect(clippy::exhaustive_structs, reason = "a rectangle")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionTab {
+    pub id: i32,
+    /// Position in the tab strip
+    pub index: i32,
+    pub pinned: bool,
+    pub group: Option<TabGroup>,
+    pub guid: Option<String>,
+    /// Index in `navigations` of the page shown
+    pub current_navigation_index: i32,
+    pub navigations: Vec<SessionNavigation>,
+    pub last_active_time: Option<DateTime<Utc>>,
+    /// Only in a `Tabs_*` file
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+impl SessionTab {
+    /// The page shown in the tab
+    pub fn current(&self) -> Option<&SessionNavigation> {
+        usize::try_from(self.current_navigation_index)
+            .ok()
+            .and_then(|v| self.navigations.get(v))
+            .or_else(|| self.navigations.last())
+    }
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TabGroup {
+    /// 32 hex digits of the `base::Token`
+    pub id: String,
+    pub title: String,
+    /// `tab_groups::TabGroupColorId`, 0 is grey, 1 blue, 2 red, 3 yellow, 4 green,
+    /// 5 pink, 6 purple, 7 cyan, 8 orange
+    pub color: u32,
+}
+
+/// An entry of a tab's back/forward list
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionNavigation {
+    pub index: i32,
+    /// Empty when the url was too long to be saved
+    pub url: String,
+    pub title: String,
+    pub transition: PageTransition,
+    pub referrer: String,
+    /// Before redirects
+    pub original_request_url: String,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub http_status_code: Option<i32>,
+}
+
+impl SessionNavigation {
+    /// <https://source.chromium.org/chromium/chromium/src/+/main:components/sessions/core/serialized_navigation_entry.cc>
+    fn from_pickle(pickle: &mut Pickle<'_>) -> Option<Self> {
+        let index = pickle.int()?;
+        let url = pickle.string()?;
+        let title = pickle
+            .string16()
+            .unwrap_or_default();
+        let mut res = Self {
+            index,
+            url,
+            title,
+            ..Default::default()
+        };
+
+        let mut rest = || {
+            // encoded page state
+            pickle.string()?;
+            res.transition = PageTransition::new(pickle.u32()?);
+            // type mask
+            pickle.int()?;
+            res.referrer = pickle.string()?;
+            // referrer policy
+            pickle.int()?;
+            res.original_request_url = pickle.string()?;
+            // is overriding user agent
+            pickle.bool()?;
+            res.timestamp = pickle
+                .i64()?
+                .micros_to_chromium_utc();
+            // search terms
+            pickle.string16()?;
+            res.http_status_code = pickle.int().filter(|&v| v != 0);
+            Some(())
+        };
+        // Older versions write fewer fields
+        _ = rest();
+
+        Some(res)
+    }
+}
+
+impl Sessions {
+    /// Parse the content of a `Session_*` file and a `Tabs_*` file
+    pub fn from_snss(session: Option<&[u8]>, tabs: Option<&[u8]>) -> Result<Self, SnssError> {
+        Ok(Self {
+            session: session
+                .map(Session::from_session_snss)
+                .transpose()?,
+            tabs: tabs
+                .map(Session::from_tabs_snss)
+                .transpose()?,
+        })
+    }
+}
+
+impl Session {
+    /// Parse the content of a `Session_*` file
+    pub fn from_session_snss(data: &[u8]) -> Result<Self, SnssError> {
+        let mut builder = SessionBuilder::default();
+        for command in snss::commands(data)? {
+            // A broken command is skipped like the browser does
+            _ = builder.apply(command);
+        }
+        Ok(builder.finish())
+    }
+
+    /// Parse the content of a `Tabs_*` file
+    pub fn from_tabs_snss(data: &[u8]) -> Result<Self, SnssError> {
+        let mut builder = TabRestoreBuilder::default();
+        for command in snss::commands(data)? {
+            _ = builder.apply(command);
+        }
+        Ok(builder.finish())
+    }
+
+    /// Tabs of all windows and closed tabs
+    pub fn tabs(&self) -> impl Iterator<Item = &SessionTab> {
+        self.windows
+            .iter()
+            .flat_map(|v| &v.tabs)
+            .chain(&self.closed_tabs)
+    }
+}
+
+fn group_id(high: u64, low: u64) -> String {
+    format!("{high:016X}{low:016X}")
+}
+
+fn set_navigation(tab: &mut SessionTab, navigation: SessionNavigation) {
+    match tab
+        .navigations
+        .binary_search_by_key(&navigation.index, |v| v.index)
+    {
+        Ok(i) => tab.navigations[i] = navigation,
+        Err(i) => tab
+            .navigations
+            .insert(i, navigation),
+    }
+}
+
+/// Drop the navigations in `start..end`, the later ones move to `start`
+fn prune_navigations(tab: &mut SessionTab, start: i32, end: i32) {
+    let count = end.saturating_sub(start);
+    let current = tab.current_navigation_index;
+    if (start..end).contains(&current) {
+        tab.current_navigation_index = start.saturating_sub(1);
+    }
+    else if current >= end {
+        tab.current_navigation_index = current.saturating_sub(count);
+    }
+    tab.navigations
+        .retain(|v| !(start..end).contains(&v.index));
+    for navigation in &mut tab.navigations {
+        if navigation.index >= end {
+            navigation.index = navigation
+                .index
+                .saturating_sub(count);
+        }
+    }
+}
+
+/// Replays the commands of the session service
+///
+/// <https://source.chromium.org/chromium/chromium/src/+/main:components/sessions/core/session_service_commands.cc>
+#[derive(Default)]
+struct SessionBuilder {
+    windows: HashMap<i32, SessionWindow>,
+    tabs: HashMap<i32, (i32, SessionTab)>,
+    /// By group id
+    groups: HashMap<String, TabGroup>,
+    active_window: Option<i32>,
+}
+
+impl SessionBuilder {
+    const SET_TAB_WINDOW: u8 = 0;
+    const SET_TAB_INDEX_IN_WINDOW: u8 = 2;
+    const TAB_NAVIGATION_PATH_PRUNED_FROM_BACK: u8 = 5;
+    const UPDATE_TAB_NAVIGATION: u8 = 6;
+    const SET_SELECTED_NAVIGATION_INDEX: u8 = 7;
+    const SET_SELECTED_TAB_IN_INDEX: u8 = 8;
+    const SET_WINDOW_TYPE: u8 = 9;
+    const TAB_NAVIGATION_PATH_PRUNED_FROM_FRONT: u8 = 11;
+    const SET_PINNED_STATE: u8 = 12;
+    const SET_WINDOW_BOUNDS3: u8 = 14;
+    const SET_WINDOW_APP_NAME: u8 = 15;
+    const TAB_CLOSED: u8 = 16;
+    const WINDOW_CLOSED: u8 = 17;
+    const SET_ACTIVE_WINDOW: u8 = 20;
+    const LAST_ACTIVE_TIME: u8 = 21;
+    const TAB_NAVIGATION_PATH_PRUNED: u8 = 24;
+    const SET_TAB_GROUP: u8 = 25;
+    const SET_TAB_GROUP_METADATA2: u8 = 27;
+    const SET_TAB_GUID: u8 = 28;
+    const SET_WINDOW_USER_TITLE: u8 = 31;
+
+    fn window(&mut self, id: i32) -> &mut SessionWindow {
+        self.windows
+            .entry(id)
+            .or_insert_with(|| SessionWindow { id, ..Default::default() })
+    }
+
+    fn tab(&mut self, id: i32) -> &mut SessionTab {
+        &mut self
+            .tabs
+            .entry(id)
+            .or_insert_with(|| (0, SessionTab { id, ..Default::default() }))
+            .1
+    }
+
+    fn apply(&mut self, Command { id, payload }: Command<'_>) -> Option<()> {
+        match id {
+            Self::SET_TAB_WINDOW => {
+                let (window, tab) = (i32_at(payload, 0)?, i32_at(payload, 4)?);
+                self.window(window);
+                self.tab(tab);
+                self.tabs.get_mut(&tab)?.0 = window;
+            },
+            Self::SET_TAB_INDEX_IN_WINDOW => {
+                self.tab(i32_at(payload, 0)?).index = i32_at(payload, 4)?;
+            },
+            Self::TAB_NAVIGATION_PATH_PRUNED_FROM_BACK => {
+                let tab = self.tab(i32_at(payload, 0)?);
+                let index = i32_at(payload, 4)?;
+                tab.navigations
+                    .retain(|v| v.index < index);
+            },
+            Self::UPDATE_TAB_NAVIGATION => {
+                let mut pickle = Pickle::new(payload)?;
+                let tab = pickle.int()?;
+                let navigation = SessionNavigation::from_pickle(&mut pickle)?;
+                set_navigation(self.tab(tab), navigation);
+            },
+            Self::SET_SELECTED_NAVIGATION_INDEX => {
+                self.tab(i32_at(payload, 0)?)
+                    .current_navigation_index = i32_at(payload, 4)?;
+            },
+            Self::SET_SELECTED_TAB_IN_INDEX => {
+                self.window(i32_at(payload, 0)?)
+                    .selected_tab_index = i32_at(payload, 4)?;
+            },
+            Self::SET_WINDOW_TYPE => {
+                self.window(i32_at(payload, 0)?)
+                    .kind = i32_at(payload, 4)?.into();
+            },
+            Self::TAB_NAVIGATION_PATH_PRUNED_FROM_FRONT => {
+                let tab = self.tab(i32_at(payload, 0)?);
+                prune_navigations(tab, 0, i32_at(payload, 4)?);
+            },
+            Self::SET_PINNED_STATE => {
+                self.tab(i32_at(payload, 0)?)
+                    .pinned = bool_at(payload, 4)?;
+            },
+            Self::SET_WINDOW_BOUNDS3 => {
+                let bounds = WindowBounds {
+                    x: i32_at(payload, 4)?,
+                    y: i32_at(payload, 8)?,
+                    width: i32_at(payload, 12)?,
+                    height: i32_at(payload, 16)?,
+                };
+                self.window(i32_at(payload, 0)?)
+                    .bounds = Some(bounds);
+            },
+            Self::SET_WINDOW_APP_NAME => {
+                let mut pickle = Pickle::new(payload)?;
+                let window = pickle.int()?;
+                self.window(window).app_name = Some(pickle.string()?);
+            },
+            Self::TAB_CLOSED => {
+                _ = self
+                    .tabs
+                    .remove(&i32_at(payload, 0)?);
+            },
+            Self::WINDOW_CLOSED => {
+                _ = self
+                    .windows
+                    .remove(&i32_at(payload, 0)?);
+            },
+            Self::SET_ACTIVE_WINDOW => self.active_window = Some(i32_at(payload, 0)?),
+            Self::LAST_ACTIVE_TIME => {
+                self.tab(i32_at(payload, 0)?)
+                    .last_active_time = i64_at(payload, 8)?.micros_to_chromium_utc();
+            },
+            Self::TAB_NAVIGATION_PATH_PRUNED => {
+                let tab = self.tab(i32_at(payload, 0)?);
+                let (index, count) = (i32_at(payload, 4)?, i32_at(payload, 8)?);
+                prune_navigations(tab, index, index.saturating_add(count));
+            },
+            Self::SET_TAB_GROUP => {
+                let group = bool_at(payload, 24)?
+                    .then(|| Some(group_id(u64_at(payload, 8)?, u64_at(payload, 16)?)))
+                    .flatten()
+                    .map(|id| TabGroup { id, ..Default::default() });
+                self.tab(i32_at(payload, 0)?).group = group;
+            },
+            Self::SET_TAB_GROUP_METADATA2 => {
+                let mut pickle = Pickle::new(payload)?;
+                let id = group_id(pickle.u64()?, pickle.u64()?);
+                let title = pickle.string16()?;
+                let color = pickle.u32()?;
+                self.groups
+                    .insert(id.clone(), TabGroup { id, title, color });
+            },
+            Self::SET_TAB_GUID => {
+                let mut pickle = Pickle::new(payload)?;
+                let tab = pickle.int()?;
+                self.tab(tab).guid = Some(pickle.string()?);
+            },
+            Self::SET_WINDOW_USER_TITLE => {
+                let mut pickle = Pickle::new(payload)?;
+                let window = pickle.int()?;
+                self.window(window).user_title = Some(pickle.string()?);
+            },
+            _ => {},
+        }
+        Some(())
+    }
+
+    /// Tabs go to their windows in tab strip order, empty windows and tabs are dropped
+    fn finish(mut self) -> Session {
+        for (window, mut tab) in self.tabs.into_values() {
+            if tab.navigations.is_empty() {
+                continue;
+            }
+            if let Some(group) = &mut tab.group
+                && let Some(metadata) = self.groups.get(&group.id)
+            {
+                metadata.clone_into(group);
+            }
+            if let Some(window) = self.windows.get_mut(&window) {
+                window.tabs.push(tab);
+            }
+        }
+
+        let mut windows: Vec<_> = self
+            .windows
+            .into_values()
+            .filter(|v| !v.tabs.is_empty())
+            .collect();
+        windows.sort_unstable_by_key(|v| v.id);
+        for window in &mut windows {
+            window.active = self.active_window == Some(window.id);
+            window
+                .tabs
+                .sort_by_key(|v| (v.index, v.id));
+        }
+
+        Session { windows, closed_tabs: vec![] }
+    }
+}
+
+/// Replays the commands of the tab restore service, a window or a tab
+/// starts an entry, the following commands fill it
+///
+/// <https://source.chromium.org/chromium/chromium/src/+/main:components/sessions/core/tab_restore_service_impl.cc>
+#[derive(Default)]
+struct TabRestoreBuilder {
+    entries: Vec<TabRestoreEntry>,
+    /// Tabs still to come for the last window
+    pending_window_tabs: i32,
+}
+
+enum TabRestoreEntry {
+    Window(SessionWindow),
+    Tab(SessionTab),
+}
+
+impl TabRestoreEntry {
+    const fn id(&self) -> i32 {
+        match self {
+            Self::Window(v) => v.id,
+            Self::Tab(v) => v.id,
+        }
+    }
+}
+
+impl TabRestoreBuilder {
+    const UPDATE_TAB_NAVIGATION: u8 = 1;
+    const RESTORED_ENTRY: u8 = 2;
+    const WINDOW_DEPRECATED: u8 = 3;
+    const SELECTED_NAVIGATION_IN_TAB: u8 = 4;
+    const PINNED_STATE: u8 = 5;
+    const SET_WINDOW_APP_NAME: u8 = 7;
+    const WINDOW: u8 = 9;
+    const SET_WINDOW_USER_TITLE: u8 = 12;
+
+    fn current_window(&mut self) -> Option<&mut SessionWindow> {
+        match self.entries.last_mut()? {
+            TabRestoreEntry::Window(v) => Some(v),
+            TabRestoreEntry::Tab(_) => None,
+        }
+    }
+
+    fn current_tab(&mut self) -> Option<&mut SessionTab> {
+        match self.entries.last_mut()? {
+            TabRestoreEntry::Window(v) => v.tabs.last_mut(),
+            TabRestoreEntry::Tab(v) => Some(v),
+        }
+    }
+
+    fn start_window(
+        &mut self,
+        id: i32,
+        selected_tab_index: i32,
+        num_tabs: i32,
+        timestamp: Option<i64>,
+    ) {
+        self.pending_window_tabs = num_tabs;
+        self.entries
+            .push(TabRestoreEntry::Window(SessionWindow {
+                id,
+                selected_tab_index,
+                closed_at: timestamp.and_then(|v| v.micros_to_chromium_utc()),
+                ..Default::default()
+            }));
+    }
+
+    fn apply(&mut self, Command { id, payload }: Command<'_>) -> Option<()> {
+        match id {
+            Self::UPDATE_TAB_NAVIGATION => {
+                let mut pickle = Pickle::new(payload)?;
+                // the entry id
+                pickle.int()?;
+                let navigation = SessionNavigation::from_pickle(&mut pickle)?;
+                set_navigation(self.current_tab()?, navigation);
+            },
+            Self::RESTORED_ENTRY => {
+                let id = i32_at(payload, 0)?;
+                self.pending_window_tabs = 0;
+                self.entries
+                    .retain(|v| v.id() != id);
+            },
+            // `WindowPayload2`, `{ id, selected_tab_index, num_tabs, timestamp }`
+            Self::WINDOW_DEPRECATED => {
+                self.start_window(
+                    i32_at(payload, 0)?,
+                    i32_at(payload, 4)?,
+                    i32_at(payload, 8)?,
+                    i64_at(payload, 16),
+                );
+            },
+            Self::WINDOW => {
+                let mut pickle = Pickle::new(payload)?;
+                let (id, selected, num_tabs) = (pickle.int()?, pickle.int()?, pickle.int()?);
+                self.start_window(id, selected, num_tabs, pickle.i64());
+            },
+            // `SelectedNavigationInTabPayload2`, `{ id, index, timestamp }`
+            Self::SELECTED_NAVIGATION_IN_TAB => {
+                let tab = SessionTab {
+                    id: i32_at(payload, 0)?,
+                    current_navigation_index: i32_at(payload, 4)?,
+                    closed_at: i64_at(payload, 8).and_then(|v| v.micros_to_chromium_utc()),
+                    ..Default::default()
+                };
+                if self.pending_window_tabs > 0
+                    && let Some(window) = self.current_window()
+                {
+                    let mut tab = tab;
+                    tab.index = window.tabs.len() as i32;
+                    window.tabs.push(tab);
+                    self.pending_window_tabs -= 1;
+                }
+                else {
+                    self.pending_window_tabs = 0;
+                    self.entries
+                        .push(TabRestoreEntry::Tab(tab));
+                }
+            },
+            Self::PINNED_STATE => self.current_tab()?.pinned = payload.iter().any(|&v| v != 0),
+            Self::SET_WINDOW_APP_NAME => {
+                let mut pickle = Pickle::new(payload)?;
+                pickle.int()?;
+                self.current_window()?.app_name = Some(pickle.string()?);
+            },
+            Self::SET_WINDOW_USER_TITLE => {
+                let mut pickle = Pickle::new(payload)?;
+                pickle.int()?;
+                self.current_window()?.user_title = Some(pickle.string()?);
+            },
+            _ => {},
+        }
+        Some(())
+    }
+
+    fn finish(self) -> Session {
+        let mut res = Session::default();
+        for entry in self.entries {
+            match entry {
+                TabRestoreEntry::Window(mut v) => {
+                    v.tabs
+                        .retain(|v| !v.navigations.is_empty());
+                    if !v.tabs.is_empty() {
+                        res.windows.push(v);
+                    }
+                },
+                TabRestoreEntry::Tab(v) => {
+                    if !v.navigations.is_empty() {
+                        res.closed_tabs.push(v);
+                    }
+                },
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::pickle::tests::PickleWriter,
+        snss::tests::{build_snss, ints},
+        *,
+    };
+
+    fn navigation(id: i32, index: i32, url: &str, title: &str, timestamp: i64) -> Vec<u8> {
+        PickleWriter::default()
+            .int(id)
+            .int(index)
+            .string(url)
+            .string16(title)
+            .string("page state")
+            .int(0x0200_0001)
+            .int(0)
+            .string("https://ref.example/")
+            .int(0)
+            .string(url)
+            .int(0)
+            .i64(timestamp)
+            .string16("")
+            .int(200)
+            .finish()
+    }
+
+    #[test]
+    fn session() {
+        let ts = 13_340_000_000_000_000;
+        let mut group = vec![];
+        group.extend_from_slice(&ints(&[3, 0]));
+        group.extend_from_slice(&1_u64.to_le_bytes());
+        group.extend_from_slice(&0xAB_u64.to_le_bytes());
+        group.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+        let metadata = PickleWriter::default()
+            .u64(1)
+            .u64(0xAB)
+            .string16("Work")
+            .int(4)
+            .finish();
+        let data = build_snss(&[
+            (SessionBuilder::SET_WINDOW_TYPE, ints(&[1, 1])),
+            (
+                SessionBuilder::SET_WINDOW_BOUNDS3,
+                ints(&[1, 10, 20, 800, 600, 1]),
+            ),
+            (SessionBuilder::SET_TAB_WINDOW, ints(&[1, 2])),
+            (SessionBuilder::SET_TAB_WINDOW, ints(&[1, 3])),
+            (SessionBuilder::SET_TAB_WINDOW, ints(&[1, 4])),
+            (SessionBuilder::SET_TAB_INDEX_IN_WINDOW, ints(&[2, 1])),
+            (SessionBuilder::SET_TAB_INDEX_IN_WINDOW, ints(&[3, 0])),
+            (SessionBuilder::SET_TAB_INDEX_IN_WINDOW, ints(&[4, 2])),
+            (
+                SessionBuilder::UPDATE_TAB_NAVIGATION,
+                navigation(2, 0, "https://a.example/", "A", ts),
+            ),
+            (
+                SessionBuilder::UPDATE_TAB_NAVIGATION,
+                navigation(2, 1, "https://b.example/", "B", ts + 1),
+            ),
+            (
+                SessionBuilder::UPDATE_TAB_NAVIGATION,
+                navigation(2, 2, "https://c.example/", "C", ts + 2),
+            ),
+            (SessionBuilder::SET_SELECTED_NAVIGATION_INDEX, ints(&[2, 2])),
+            (SessionBuilder::TAB_NAVIGATION_PATH_PRUNED, ints(&[2, 1, 1])),
+            (
+                SessionBuilder::UPDATE_TAB_NAVIGATION,
+                navigation(3, 0, "https://pinned.example/", "P", ts),
+            ),
+            (SessionBuilder::SET_PINNED_STATE, ints(&[3, 1])),
+            (SessionBuilder::SET_TAB_GROUP, group),
+            (SessionBuilder::SET_TAB_GROUP_METADATA2, metadata),
+            (
+                SessionBuilder::UPDATE_TAB_NAVIGATION,
+                navigation(4, 0, "https://closed.example/", "X", ts),
+            ),
+            (SessionBuilder::TAB_CLOSED, ints(&[4, 0, 0, 0])),
+            (SessionBuilder::SET_SELECTED_TAB_IN_INDEX, ints(&[1, 1])),
+            (SessionBuilder::SET_ACTIVE_WINDOW, ints(&[1])),
+            // Truncated
+            (SessionBuilder::SET_TAB_WINDOW, ints(&[1])),
+        ]);
+        let mut data = data;
+        data.extend_from_slice(&[0x20, 0]);
+
+        let session = Session::from_session_snss(&data).unwrap();
+        let [window] = session.windows.as_slice()
+        else {
+            panic!("{session:?}");
+        };
+        assert_eq!(window.kind, WindowKind::Popup);
+        assert_eq!(
+            window.bounds,
+            Some(WindowBounds {
+                x: 10,
+                y: 20,
+                width: 800,
+                height: 600
+            })
+        );
+        assert!(window.active);
+        assert_eq!(window.selected_tab_index, 1);
+
+        let [pinned, tab] = window.tabs.as_slice()
+        else {
+            panic!("{window:?}");
+        };
+        assert!(pinned.pinned);
+        let group = pinned.group.as_ref().unwrap();
+        assert_eq!(group.id, "000000000000000100000000000000AB");
+        assert_eq!((group.title.as_str(), group.color), ("Work", 4));
+
+        let urls: Vec<_> = tab
+            .navigations
+            .iter()
+            .map(|v| (v.index, v.url.as_str()))
+            .collect();
+        assert_eq!(urls, [(0, "https://a.example/"), (1, "https://c.example/")]);
+        let current = tab.current().unwrap();
+        assert_eq!(current.title, "C");
+        assert_eq!(current.referrer, "https://ref.example/");
+        assert_eq!(current.http_status_code, Some(200));
+        assert!(
+            current
+                .transition
+                .contains(PageTransition::FROM_ADDRESS_BAR)
+        );
+        assert_eq!(current.timestamp, (ts + 2).micros_to_chromium_utc());
+    }
+
+    #[test]
+    fn tabs() {
+        let ts = 13_340_000_000_000_000_i64;
+        let mut selected = ints(&[7, 0]);
+        selected.extend_from_slice(&ts.to_le_bytes());
+        let window = PickleWriter::default()
+            .int(5)
+            .int(0)
+            .int(1)
+            .i64(ts)
+            .finish();
+        let mut window_tab = ints(&[6, 0]);
+        window_tab.extend_from_slice(&ts.to_le_bytes());
+        let mut restored = ints(&[8, 0]);
+        restored.extend_from_slice(&ts.to_le_bytes());
+        let data = build_snss(&[
+            (TabRestoreBuilder::SELECTED_NAVIGATION_IN_TAB, selected),
+            (
+                TabRestoreBuilder::UPDATE_TAB_NAVIGATION,
+                navigation(7, 0, "https://tab.example/", "T", ts),
+            ),
+            (TabRestoreBuilder::PINNED_STATE, vec![1]),
+            (TabRestoreBuilder::WINDOW, window),
+            (TabRestoreBuilder::SELECTED_NAVIGATION_IN_TAB, window_tab),
+            (
+                TabRestoreBuilder::UPDATE_TAB_NAVIGATION,
+                navigation(6, 0, "https://win.example/", "W", ts),
+            ),
+            (TabRestoreBuilder::SELECTED_NAVIGATION_IN_TAB, restored),
+            (
+                TabRestoreBuilder::UPDATE_TAB_NAVIGATION,
+                navigation(8, 0, "https://restored.example/", "R", ts),
+            ),
+            (TabRestoreBuilder::RESTORED_ENTRY, ints(&[8])),
+        ]);
+
+        let session = Session::from_tabs_snss(&data).unwrap();
+        let [tab] = session.closed_tabs.as_slice()
+        else {
+            panic!("{session:?}");
+        };
+        assert!(tab.pinned);
+        assert_eq!(tab.closed_at, ts.micros_to_chromium_utc());
+        assert_eq!(tab.current().unwrap().url, "https://tab.example/");
+
+        let [window] = session.windows.as_slice()
+        else {
+            panic!("{session:?}");
+        };
+        assert_eq!(window.id, 5);
+        assert_eq!(window.tabs[0].navigations[0].title, "W");
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            Session::from_session_snss(b"SNSX\x01\0\0\0"),
+            Err(SnssError::Magic { .. })
+        ));
+        assert!(matches!(
+            Session::from_session_snss(b"SNSS\x02\0\0\0"),
+            Err(SnssError::Version { version: 2, .. })
+        ));
+        assert_eq!(
+            Session::from_session_snss(b"SNSS\x01\0\0\0").unwrap(),
+            Session::default()
+        );
+    }
+    #[test]
+    fn prune_at_min() {
+        let mut tab = SessionTab {
+            current_navigation_index: i32::MIN,
+            ..Default::default()
+        };
+        prune_navigations(&mut tab, i32::MIN, 0);
+        assert_eq!(tab.current_navigation_index, i32::MIN);
+    }
+}
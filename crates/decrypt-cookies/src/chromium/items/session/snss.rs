@@ -0,0 +1,95 @@
+//! The SNSS command file shared by the session and tab restore services
+//!
+//! <https://source.chromium.org/chromium/chromium/src/+/main:components/sessions/core/command_storage_backend.cc>
+
+use snafu::{Location, Snafu, ensure};
+
+const MAGIC: &[u8; 4] = b"SNSS";
+const VERSION: i32 = 1;
+const VERSION_WITH_MARKER: i32 = 3;
+/// Ends the commands that rebuild the state when the file was opened
+const INITIAL_STATE_MARKER: u8 = 255;
+
+#[derive(Debug)]
+#[derive(Snafu)]
+#[snafu(visibility(pub))]
+#[non_exhaustive]
+pub enum SnssError {
+    #[snafu(display("Not a SNSS file\n@:{location}"))]
+    Magic {
+        #[snafu(implicit)]
+        location: Location,
+    },
+    /// 2 and 4 are encrypted with a key the browser doesn't persist
+    #[snafu(display("Unsupported SNSS version {version}\n@:{location}"))]
+    Version {
+        version: i32,
+        #[snafu(implicit)]
+        location: Location,
+    },
+}
+
+/// `u16` size, the id and the payload of a command
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+pub struct Command<'a> {
+    pub id: u8,
+    pub payload: &'a [u8],
+}
+
+/// The commands of a file, a truncated command at the end is dropped
+pub fn commands(data: &[u8]) -> Result<Vec<Command<'_>>, SnssError> {
+    let Some((magic, rest)) = data.split_first_chunk::<4>()
+    else {
+        return MagicSnafu.fail();
+    };
+    ensure!(magic == MAGIC, MagicSnafu);
+    let Some((version, mut rest)) = rest.split_first_chunk::<4>()
+    else {
+        return MagicSnafu.fail();
+    };
+    let version = i32::from_le_bytes(*version);
+    ensure!(
+        matches!(version, VERSION | VERSION_WITH_MARKER),
+        VersionSnafu { version }
+    );
+
+    let mut res = vec![];
+    while let Some((size, tail)) = rest.split_first_chunk::<2>() {
+        let size = u16::from_le_bytes(*size) as usize;
+        let Some((command, tail)) = tail.split_at_checked(size)
+        else {
+            break;
+        };
+        rest = tail;
+        if let Some((&id, payload)) = command.split_first()
+            && id != INITIAL_STATE_MARKER
+        {
+            res.push(Command { id, payload });
+        }
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+pub mod tests {
+    /// A version 3 file
+    pub fn build_snss(commands: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        let mut res = b"SNSS".to_vec();
+        res.extend_from_slice(&3_i32.to_le_bytes());
+        for (id, payload) in commands {
+            res.extend_from_slice(&(payload.len() as u16 + 1).to_le_bytes());
+            res.push(*id);
+            res.extend_from_slice(payload);
+        }
+        res
+    }
+
+    /// Little endian `i32`s as a C struct
+    pub fn ints(values: &[i32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect()
+    }
+}
@@ -0,0 +1,663 @@
+use std::fmt::Display;
+
+use chromium_crypto::SecretString;
+use chrono::{DateTime, Utc};
+
+use self::web_data_entities::{
+    address_type_tokens, addresses, autofill, credit_cards, local_ibans, token_service,
+};
+use super::account::ProfileAccount;
+
+pub mod web_data_dao;
+pub mod web_data_entities;
+
+/// `Web Data` stores seconds since the unix epoch, `0` means never
+const fn unix_time(secs: i64) -> Option<DateTime<Utc>> {
+    if secs == 0 {
+        return None;
+    }
+    DateTime::from_timestamp(secs, 0)
+}
+
+/// A value typed into a form field, row of the `autofill` table
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AutofillEntry {
+    /// The `name` attribute of the field
+    pub name: String,
+    pub value: String,
+    pub date_created: Option<DateTime<Utc>>,
+    pub date_last_used: Option<DateTime<Utc>>,
+    pub count: i64,
+}
+
+impl AutofillEntry {
+    pub fn to_csv<D: Display>(&self, sep: D) -> String {
+        format!(
+            "{}{sep}{}{sep}{}{sep}{}{sep}{}",
+            self.name,
+            self.value,
+            self.date_created
+                .unwrap_or_default(),
+            self.date_last_used
+                .unwrap_or_default(),
+            self.count,
+        )
+    }
+}
+
+impl From<autofill::Model> for AutofillEntry {
+    fn from(v: autofill::Model) -> Self {
+        Self {
+            name: v.name,
+            value: v.value,
+            date_created: unix_time(v.date_created),
+            date_last_used: unix_time(v.date_last_used),
+            count: v.count,
+        }
+    }
+}
+
+/// Guessed from the leading digits of the card number
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CardNetwork {
+    Visa,
+    Mastercard,
+    Amex,
+    Discover,
+    Jcb,
+    DinersClub,
+    UnionPay,
+    Mir,
+    #[default]
+    Unknown,
+}
+
+impl CardNetwork {
+    pub fn from_number(number: &str) -> Self {
+        let prefix = |len: usize| -> u32 {
+            number
+                .get(..len)
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_default()
+        };
+        match (prefix(1), prefix(2), prefix(3), prefix(4)) {
+            (4, ..) => Self::Visa,
+            (_, 34 | 37, ..) => Self::Amex,
+            (_, _, _, 2200..=2204) => Self::Mir,
+            (_, 51..=55, ..) | (_, _, _, 2221..=2720) => Self::Mastercard,
+            (_, _, _, 6011) | (_, 65, ..) | (_, _, 644..=649, _) => Self::Discover,
+            (_, _, _, 3528..=3589) => Self::Jcb,
+            (_, 36 | 38 | 39, ..) | (_, _, 300..=305, _) => Self::DinersClub,
+            (_, 62, ..) => Self::UnionPay,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl Display for CardNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Visa => "Visa",
+            Self::Mastercard => "Mastercard",
+            Self::Amex => "American Express",
+            Self::Discover => "Discover",
+            Self::Jcb => "JCB",
+            Self::DinersClub => "Diners Club",
+            Self::UnionPay => "UnionPay",
+            Self::Mir => "Mir",
+            Self::Unknown => "Unknown",
+        })
+    }
+}
+
+/// A card saved in the profile, row of the `credit_cards` table.
+///
+/// Use [`CreditCard::masked`] to show or log it.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreditCard {
+    pub guid: String,
+    pub name_on_card: String,
+    pub expiration_month: i64,
+    pub expiration_year: i64,
+    /// Decrypted `card_number_encrypted`
+    pub card_number: Option<SecretString>,
+    pub nickname: String,
+    pub origin: String,
+    pub billing_address_id: String,
+    pub use_count: i64,
+    pub use_date: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+}
+
+/// A [`CreditCard`] without the full number
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaskedCreditCard {
+    pub guid: String,
+    pub name_on_card: String,
+    pub expiration_month: i64,
+    pub expiration_year: i64,
+    pub network: CardNetwork,
+    pub last_four: Option<String>,
+    pub nickname: String,
+    pub use_count: i64,
+    pub use_date: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+}
+
+impl CreditCard {
+    fn digits(&self) -> Option<String> {
+        let number = self.card_number.as_ref()?;
+        Some(
+            number
+                .expose_secret()
+                .chars()
+                .filter(char::is_ascii_digit)
+                .collect(),
+        )
+    }
+
+    pub fn last_four(&self) -> Option<String> {
+        let digits = self
+            .digits()
+            .filter(|v| !v.is_empty())?;
+        let start = digits.len().saturating_sub(4);
+        Some(digits[start..].to_owned())
+    }
+
+    pub fn network(&self) -> CardNetwork {
+        self.digits()
+            .map_or(CardNetwork::Unknown, |v| CardNetwork::from_number(&v))
+    }
+
+    /// `**** 1234`
+    pub fn masked_number(&self) -> Option<String> {
+        self.last_four()
+            .map(|v| format!("**** {v}"))
+    }
+
+    pub fn masked(&self) -> MaskedCreditCard {
+        MaskedCreditCard {
+            guid: self.guid.clone(),
+            name_on_card: self.name_on_card.clone(),
+            expiration_month: self.expiration_month,
+            expiration_year: self.expiration_year,
+            network: self.network(),
+            last_four: self.last_four(),
+            nickname: self.nickname.clone(),
+            use_count: self.use_count,
+            use_date: self.use_date,
+            date_modified: self.date_modified,
+        }
+    }
+
+    /// The full number, see [`MaskedCreditCard::to_csv`] for the masked one
+    pub fn to_csv<D: Display>(&self, sep: D) -> String {
+        format!(
+            "{}{sep}{}{sep}{:02}/{}{sep}{}{sep}{}{sep}{}{sep}{}",
+            self.name_on_card,
+            self.card_number
+                .as_ref()
+                .map(SecretString::expose_secret)
+                .unwrap_or_default(),
+            self.expiration_month,
+            self.expiration_year,
+            self.nickname,
+            self.use_count,
+            self.use_date.unwrap_or_default(),
+            self.date_modified
+                .unwrap_or_default(),
+        )
+    }
+}
+
+impl MaskedCreditCard {
+    pub fn to_csv<D: Display>(&self, sep: D) -> String {
+        format!(
+            "{}{sep}{}{sep}{:02}/{}{sep}{}{sep}{}{sep}{}{sep}{}",
+            self.name_on_card,
+            self.last_four
+                .as_deref()
+                .map(|v| format!("{} **** {v}", self.network))
+                .unwrap_or_default(),
+            self.expiration_month,
+            self.expiration_year,
+            self.nickname,
+            self.use_count,
+            self.use_date.unwrap_or_default(),
+            self.date_modified
+                .unwrap_or_default(),
+        )
+    }
+}
+
+impl From<&CreditCard> for MaskedCreditCard {
+    fn from(value: &CreditCard) -> Self {
+        value.masked()
+    }
+}
+
+impl From<credit_cards::Model> for CreditCard {
+    fn from(v: credit_cards::Model) -> Self {
+        Self {
+            guid: v.guid,
+            name_on_card: v.name_on_card.unwrap_or_default(),
+            expiration_month: v
+                .expiration_month
+                .unwrap_or_default(),
+            expiration_year: v
+                .expiration_year
+                .unwrap_or_default(),
+            card_number: None,
+            nickname: v.nickname.unwrap_or_default(),
+            origin: v.origin.unwrap_or_default(),
+            billing_address_id: v
+                .billing_address_id
+                .unwrap_or_default(),
+            use_count: v.use_count,
+            use_date: unix_time(v.use_date),
+            date_modified: unix_time(v.date_modified),
+        }
+    }
+}
+
+/// An IBAN saved in the profile, row of the `local_ibans` table
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Iban {
+    pub guid: String,
+    /// Decrypted `value_encrypted`
+    pub value: Option<SecretString>,
+    pub nickname: String,
+    pub use_count: i64,
+    pub use_date: Option<DateTime<Utc>>,
+}
+
+/// An [`Iban`] without the account number
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaskedIban {
+    pub guid: String,
+    pub country_code: Option<String>,
+    pub last_four: Option<String>,
+    pub nickname: String,
+    pub use_count: i64,
+    pub use_date: Option<DateTime<Utc>>,
+}
+
+impl Iban {
+    fn compact(&self) -> Option<String> {
+        let value = self.value.as_ref()?;
+        Some(
+            value
+                .expose_secret()
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect(),
+        )
+    }
+
+    /// `DE **** 1234`
+    pub fn masked_value(&self) -> Option<String> {
+        let masked = self.masked();
+        Some(format!(
+            "{} **** {}",
+            masked.country_code?, masked.last_four?
+        ))
+    }
+
+    pub fn masked(&self) -> MaskedIban {
+        let compact = self.compact();
+        MaskedIban {
+            guid: self.guid.clone(),
+            country_code: compact
+                .as_ref()
+                .and_then(|v| v.get(..2))
+                .map(str::to_owned),
+            last_four: compact.as_ref().and_then(|v| {
+                v.get(v.len().saturating_sub(4)..)
+                    .map(str::to_owned)
+            }),
+            nickname: self.nickname.clone(),
+            use_count: self.use_count,
+            use_date: self.use_date,
+        }
+    }
+
+    /// The full account number, see [`MaskedIban::to_csv`] for the masked one
+    pub fn to_csv<D: Display>(&self, sep: D) -> String {
+        format!(
+            "{}{sep}{}{sep}{}{sep}{}",
+            self.value
+                .as_ref()
+                .map(SecretString::expose_secret)
+                .unwrap_or_default(),
+            self.nickname,
+            self.use_count,
+            self.use_date.unwrap_or_default(),
+        )
+    }
+}
+
+impl MaskedIban {
+    pub fn to_csv<D: Display>(&self, sep: D) -> String {
+        format!(
+            "{}{sep}{}{sep}{}{sep}{}",
+            self.country_code
+                .as_deref()
+                .zip(self.last_four.as_deref())
+                .map(|(country, last)| format!("{country} **** {last}"))
+                .unwrap_or_default(),
+            self.nickname,
+            self.use_count,
+            self.use_date.unwrap_or_default(),
+        )
+    }
+}
+
+impl From<&Iban> for MaskedIban {
+    fn from(value: &Iban) -> Self {
+        value.masked()
+    }
+}
+
+impl From<local_ibans::Model> for Iban {
+    fn from(v: local_ibans::Model) -> Self {
+        Self {
+            guid: v.guid,
+            value: None,
+            nickname: v.nickname.unwrap_or_default(),
+            use_count: v.use_count,
+            use_date: unix_time(v.use_date),
+        }
+    }
+}
+
+/// An OAuth refresh token of a signed in account, row of the `token_service` table
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountToken {
+    /// `AccountId-<account id>`
+    pub service: String,
+    pub account_id: String,
+    /// `None` when neither `Preferences` nor `Local State` knows the account
+    pub account: Option<ProfileAccount>,
+    /// Decrypted `encrypted_token`
+    pub token: Option<SecretString>,
+}
+
+impl AccountToken {
+    const SERVICE_PREFIX: &str = "AccountId-";
+
+    pub(crate) fn new(v: &token_service::Model, accounts: &[ProfileAccount]) -> Self {
+        let account_id = v
+            .service
+            .strip_prefix(Self::SERVICE_PREFIX)
+            .unwrap_or(&v.service)
+            .to_owned();
+        let account = accounts
+            .iter()
+            .find(|a| a.account_id == account_id)
+            .cloned();
+        Self {
+            service: v.service.clone(),
+            account_id,
+            account,
+            token: None,
+        }
+    }
+
+    /// Older profiles use the email as the account id
+    pub fn email(&self) -> Option<&str> {
+        self.account
+            .as_ref()
+            .map(|a| a.email.as_str())
+            .or_else(|| {
+                self.account_id
+                    .contains('@')
+                    .then_some(self.account_id.as_str())
+            })
+    }
+}
+
+/// The `type` column of `address_type_tokens`
+///
+/// <https://source.chromium.org/chromium/chromium/src/+/main:components/autofill/core/browser/field_types.h>
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AddressFieldType {
+    NameFirst,
+    NameMiddle,
+    NameLast,
+    NameMiddleInitial,
+    NameFull,
+    NameSuffix,
+    EmailAddress,
+    PhoneHomeNumber,
+    PhoneHomeCityCode,
+    PhoneHomeCountryCode,
+    PhoneHomeCityAndNumber,
+    PhoneHomeWholeNumber,
+    AddressHomeLine1,
+    AddressHomeLine2,
+    AddressHomeAptNum,
+    AddressHomeCity,
+    AddressHomeState,
+    AddressHomeZip,
+    AddressHomeCountry,
+    CompanyName,
+    AddressHomeStreetAddress,
+    AddressHomeSortingCode,
+    AddressHomeDependentLocality,
+    AddressHomeLine3,
+    Other(i64),
+}
+
+impl From<i64> for AddressFieldType {
+    fn from(value: i64) -> Self {
+        match value {
+            3 => Self::NameFirst,
+            4 => Self::NameMiddle,
+            5 => Self::NameLast,
+            6 => Self::NameMiddleInitial,
+            7 => Self::NameFull,
+            8 => Self::NameSuffix,
+            9 => Self::EmailAddress,
+            10 => Self::PhoneHomeNumber,
+            11 => Self::PhoneHomeCityCode,
+            12 => Self::PhoneHomeCountryCode,
+            13 => Self::PhoneHomeCityAndNumber,
+            14 => Self::PhoneHomeWholeNumber,
+            30 => Self::AddressHomeLine1,
+            31 => Self::AddressHomeLine2,
+            32 => Self::AddressHomeAptNum,
+            33 => Self::AddressHomeCity,
+            34 => Self::AddressHomeState,
+            35 => Self::AddressHomeZip,
+            36 => Self::AddressHomeCountry,
+            60 => Self::CompanyName,
+            77 => Self::AddressHomeStreetAddress,
+            78 => Self::AddressHomeSortingCode,
+            79 => Self::AddressHomeDependentLocality,
+            80 => Self::AddressHomeLine3,
+            v => Self::Other(v),
+        }
+    }
+}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AddressField {
+    pub field_type: AddressFieldType,
+    pub value: String,
+    pub verification_status: i64,
+}
+
+/// A saved address, row of the `addresses` table with its `address_type_tokens`
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Address {
+    pub guid: String,
+    pub use_count: i64,
+    pub use_date: Option<DateTime<Utc>>,
+    pub date_modified: Option<DateTime<Utc>>,
+    pub language_code: String,
+    pub label: String,
+    /// 0: local or syncable, 1: account
+    pub record_type: Option<i64>,
+    /// The non empty fields, ordered by type
+    pub fields: Vec<AddressField>,
+}
+
+impl Address {
+    pub(crate) fn new(v: addresses::Model, tokens: Vec<address_type_tokens::Model>) -> Self {
+        let mut fields: Vec<_> = tokens
+            .into_iter()
+            .filter_map(|t| {
+                let value = t.value.filter(|v| !v.is_empty())?;
+                Some(AddressField {
+                    field_type: AddressFieldType::from(t.r#type),
+                    value,
+                    verification_status: t
+                        .verification_status
+                        .unwrap_or_default(),
+                })
+            })
+            .collect();
+        fields.sort_by_key(|f| f.field_type);
+
+        Self {
+            guid: v.guid,
+            use_count: v.use_count,
+            use_date: unix_time(v.use_date),
+            date_modified: unix_time(v.date_modified),
+            language_code: v.language_code.unwrap_or_default(),
+            label: v.label.unwrap_or_default(),
+            record_type: v.record_type,
+            fields,
+        }
+    }
+
+    pub fn get(&self, field_type: AddressFieldType) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|f| f.field_type == field_type)
+            .map(|f| f.value.as_str())
+    }
+
+    pub fn to_csv<D: Display>(&self, sep: D) -> String {
+        use AddressFieldType as T;
+
+        let get = |t| self.get(t).unwrap_or_default();
+        format!(
+            "{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}{sep}{}",
+            get(T::NameFull),
+            get(T::CompanyName),
+            get(T::AddressHomeStreetAddress),
+            get(T::AddressHomeCity),
+            get(T::AddressHomeState),
+            get(T::AddressHomeZip),
+            get(T::AddressHomeCountry),
+            get(T::PhoneHomeWholeNumber),
+            get(T::EmailAddress),
+            self.use_count,
+            self.use_date.unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(number: &str) -> CreditCard {
+        CreditCard::from(credit_cards::Model {
+            guid: "g".to_owned(),
+            name_on_card: Some("A".to_owned()),
+            expiration_month: Some(1),
+            expiration_year: Some(2030),
+            card_number_encrypted: None,
+            date_modified: 0,
+            origin: None,
+            use_count: 0,
+            use_date: 0,
+            billing_address_id: None,
+            nickname: None,
+        })
+        .with_number(number)
+    }
+
+    impl CreditCard {
+        fn with_number(mut self, number: &str) -> Self {
+            self.card_number = Some(number.into());
+            self
+        }
+    }
+
+    #[test]
+    fn masked_views() {
+        let visa = card("4111 1111 1111 1234");
+        assert_eq!(visa.masked_number().as_deref(), Some("**** 1234"));
+        let masked = visa.masked();
+        assert_eq!(masked.network, CardNetwork::Visa);
+        assert_eq!(masked.last_four.as_deref(), Some("1234"));
+        assert!(!format!("{masked:?}").contains("4111"));
+
+        assert_eq!(card("378282246310005").network(), CardNetwork::Amex);
+        assert_eq!(card("2221000000000009").network(), CardNetwork::Mastercard);
+        assert_eq!(card("2200000000000004").network(), CardNetwork::Mir);
+        assert_eq!(card("").masked_number(), None);
+
+        let iban = Iban {
+            guid: "g".to_owned(),
+            value: Some("DE89 3704 0044 0532 0130 00".into()),
+            nickname: String::new(),
+            use_count: 0,
+            use_date: None,
+        };
+        assert_eq!(iban.masked_value().as_deref(), Some("DE **** 3000"));
+        assert!(
+            !iban
+                .masked()
+                .to_csv(',')
+                .contains("3704")
+        );
+        assert!(!masked.to_csv(',').contains("4111"));
+    }
+}
@@ -0,0 +1,125 @@
+use std::path::Path;
+
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, QueryFilter, Statement,
+    sea_query::IntoCondition,
+};
+
+use super::web_data_entities::{
+    address_type_tokens, addresses, autofill, credit_cards, local_ibans, prelude::*, token_service,
+};
+use crate::utils::connect_db;
+
+type Result<T> = std::result::Result<T, DbErr>;
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct WebDataQuery {
+    conn: DatabaseConnection,
+}
+
+impl WebDataQuery {
+    pub async fn new<P: AsRef<Path> + Send>(path: P) -> Result<Self> {
+        let db = connect_db(&path).await?;
+        Ok(Self { conn: db })
+    }
+
+    /// The tables change between Chromium versions
+    async fn has_table(&self, name: &str) -> Result<bool> {
+        let res = self
+            .conn
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Sqlite,
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?",
+                [name.into()],
+            ))
+            .await?;
+        Ok(res.is_some())
+    }
+
+    pub async fn autofill_filter<F>(&self, filter: F) -> Result<Vec<autofill::Model>>
+    where
+        F: IntoCondition + Send,
+    {
+        Autofill::find()
+            .filter(filter)
+            .all(&self.conn)
+            .await
+    }
+
+    pub async fn credit_cards_filter<F>(&self, filter: F) -> Result<Vec<credit_cards::Model>>
+    where
+        F: IntoCondition + Send,
+    {
+        CreditCards::find()
+            .filter(filter)
+            .all(&self.conn)
+            .await
+    }
+
+    /// `addresses` and `address_type_tokens`,
+    /// falls back to `local_addresses` and `local_addresses_type_tokens` of older versions
+    pub async fn addresses(
+        &self,
+    ) -> Result<(Vec<addresses::Model>, Vec<address_type_tokens::Model>)> {
+        if self.has_table("addresses").await? {
+            let addresses = Addresses::find()
+                .all(&self.conn)
+                .await?;
+            let tokens = AddressTypeTokens::find()
+                .all(&self.conn)
+                .await?;
+            return Ok((addresses, tokens));
+        }
+        if !self
+            .has_table("local_addresses")
+            .await?
+        {
+            return Ok((vec![], vec![]));
+        }
+
+        let addresses = Addresses::find()
+            .from_raw_sql(Statement::from_string(
+                DbBackend::Sqlite,
+                "SELECT guid, use_count, use_date, date_modified, language_code, label, NULL AS \
+                 record_type FROM local_addresses",
+            ))
+            .all(&self.conn)
+            .await?;
+        let tokens = AddressTypeTokens::find()
+            .from_raw_sql(Statement::from_string(
+                DbBackend::Sqlite,
+                "SELECT guid, type, value, verification_status FROM local_addresses_type_tokens",
+            ))
+            .all(&self.conn)
+            .await?;
+        Ok((addresses, tokens))
+    }
+
+    /// Empty before the table was added
+    pub async fn ibans(&self) -> Result<Vec<local_ibans::Model>> {
+        if !self
+            .has_table("local_ibans")
+            .await?
+        {
+            return Ok(vec![]);
+        }
+        LocalIbans::find()
+            .all(&self.conn)
+            .await
+    }
+
+    /// OAuth refresh tokens of the signed in accounts
+    pub async fn token_service(&self) -> Result<Vec<token_service::Model>> {
+        if !self
+            .has_table("token_service")
+            .await?
+        {
+            return Ok(vec![]);
+        }
+        TokenService::find()
+            .all(&self.conn)
+            .await
+    }
+}
@@ -1,38 +1,106 @@
+pub mod artifact;
 pub mod builder;
 pub(crate) mod items;
 use std::{
+    collections::HashMap,
     fmt::Display,
     marker::{PhantomData, Sync},
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use chromium_crypto::{Decrypter, Which};
-use chrono::prelude::Utc;
+pub use artifact::{ArtifactKind, ArtifactStatus, ProfileArtifacts};
+use chromium_crypto::Decrypter;
+pub use chromium_crypto::SecretString;
+#[cfg(feature = "key-cache")]
+pub use chromium_crypto::{CacheSecret, KeyCache};
+use chrono::prelude::{DateTime, Utc};
 use items::cookie::cookie_entities::cookies;
 #[cfg(feature = "reqwest")]
 pub use items::cookie::jar_extend_chromium;
 pub use items::{
+    account::ProfileAccount,
+    bookmark::{BookmarkKind, BookmarkNode, Bookmarks},
+    cache::{CacheEntry, CacheError, CacheFormat, DiskCache},
     cookie::{
-        ChromiumCookie,
+        ChromiumCookie, CookiePriority, DecryptError, LazyChromiumCookie, SourcePort, SourceScheme,
         cookie_entities::cookies::{
             Column as ChromiumCookieCol, ColumnIter as ChromiumCookieColIter,
         },
+        cookie_writer::SetCookie,
     },
+    extension::{Extension, ExtensionLocation},
+    history::{
+        Download, DownloadState, HistoryUrl, HistoryVisit, KeywordSearchTerm, PageTransition,
+        TransitionCore,
+        history_entities::{
+            downloads::Column as DownloadCol, keyword_search_terms::Column as KeywordSearchTermCol,
+            urls::Column as HistoryUrlCol, visits::Column as HistoryVisitCol,
+        },
+    },
+    indexed_db::{
+        IdbDatabase, IdbIndex, IdbKey, IdbKeyPath, IdbObjectStore, IdbRecord, IdbValue, IndexedDb,
+        JsValue,
+    },
+    local_storage::LocalStorageItem,
     passwd::{
-        LoginData,
+        EdgeLoginExtended, GenerationUploadStatus, InsecureCredential, InsecurityType,
+        LazyLoginData, LoginData, LoginScheme, LoginStats, LoginStore, PasswordNote, PasswordType,
+        form_data::{FormData, FormFieldData, PossibleUsername},
         login_data_entities::logins::{Column as ChromiumLoginCol, Column as ChromiumLoginColIter},
+        login_data_writer::{ImportReport, LoginKey},
+        yandex::YandexError,
+    },
+    preferences::{
+        ContentSetting, MacCheck, Preferences, PrefsFile, SearchEngine, SiteException, Startup,
+        StartupKind,
+    },
+    session::{
+        Session, SessionNavigation, SessionTab, SessionWindow, Sessions, SnssError, TabGroup,
+        WindowBounds, WindowKind,
+    },
+    web_data::{
+        AccountToken, Address, AddressField, AddressFieldType, AutofillEntry, CardNetwork,
+        CreditCard, Iban, MaskedCreditCard, MaskedIban,
+        web_data_entities::{
+            autofill::Column as AutofillCol, credit_cards::Column as CreditCardCol,
+        },
     },
 };
+use leveldb_reader::{LevelDb, LevelDbError};
 use rayon::prelude::*;
-use sea_orm::{ColumnTrait, Condition, DbErr};
+use sea_orm::{ColumnTrait, Condition, DbErr, sea_query::IntoCondition};
 use snafu::{Location, ResultExt, Snafu};
 use tokio::task::{self, JoinError};
 
 use crate::{
-    browser::{ChromiumPath, cookies::LeetCodeCookies},
-    chromium::items::{
-        I64ToChromiumDateTime,
-        cookie::cookie_dao::CookiesQuery,
-        passwd::{login_data_dao::LoginDataQuery, login_data_entities::logins},
+    browser::{
+        ChromiumPath, Edge,
+        cookies::{CookiesInfo, LeetCodeCookies},
+    },
+    chromium::{
+        artifact::Artifact,
+        builder::ChromiumBuilderError,
+        items::{
+            I64ToChromiumDateTime,
+            cookie::{
+                cookie_dao::CookiesQuery,
+                cookie_writer::{CookiesWriter, HOST_KEY_DIGEST_VERSION},
+            },
+            history::{
+                history_dao::HistoryQuery,
+                history_entities::{downloads, keyword_search_terms, urls, visits},
+            },
+            passwd::{
+                LoginRows, RawLogin, join_login_rows,
+                login_data_dao::LoginDataQuery,
+                login_data_entities::logins,
+                login_data_writer::{LoginDataWriter, login_model},
+                merge_stores,
+                yandex::{ImportSnafu, MasterPasswordSnafu, YandexEncryptor},
+            },
+            web_data::{web_data_dao::WebDataQuery, web_data_entities::address_type_tokens},
+        },
     },
 };
 
@@ -58,6 +126,50 @@ pub enum ChromiumError {
         #[snafu(implicit)]
         location: Location,
     },
+    #[snafu(display("{source}\n@:{location}"))]
+    Encrypt {
+        source: chromium_crypto::error::CryptoError,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{source}\n@:{location}"))]
+    Yandex {
+        source: YandexError,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{source}\n@:{location}"))]
+    LevelDb {
+        source: LevelDbError,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("The browser is running, {} exists\n@:{location}", lock.display()))]
+    BrowserRunning {
+        lock: PathBuf,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{source}, path: {}\n@:{location}", path.display()))]
+    Io {
+        source: std::io::Error,
+        path: PathBuf,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{artifact} is missing in the profile\n@:{location}"))]
+    Missing {
+        artifact: ArtifactKind,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{artifact} is unavailable: {source}\n@:{location}"))]
+    Unavailable {
+        artifact: ArtifactKind,
+        source: Arc<ChromiumBuilderError>,
+        #[snafu(implicit)]
+        location: Location,
+    },
 }
 
 type Result<T> = std::result::Result<T, ChromiumError>;
@@ -66,6 +178,9 @@ type Result<T> = std::result::Result<T, ChromiumError>;
 ///
 /// Initialize it with `ChromiumBuilder`
 ///
+/// Files missing in the profile don't fail the build,
+/// see [`ChromiumGetter::artifacts`] for what is available.
+///
 /// # Example
 /// ```rust, ignore
 /// let getter = ChromiumBuilder::new(Chromium::new())
@@ -79,19 +194,32 @@ type Result<T> = std::result::Result<T, ChromiumError>;
 #[derive(Debug)]
 #[derive(Default)]
 pub struct ChromiumGetter<T: ChromiumPath> {
-    pub(crate) cookies_query: CookiesQuery,
-    pub(crate) login_data_query: LoginDataQuery,
-    pub(crate) login_data_for_account_query: Option<LoginDataQuery>,
-    pub(crate) crypto: Decrypter,
+    pub(crate) cookies_query: Artifact<CookiesQuery>,
+    pub(crate) login_data_query: Artifact<LoginDataQuery>,
+    pub(crate) login_data_for_account_query: Artifact<LoginDataQuery>,
+    pub(crate) crypto: Artifact<Arc<Decrypter>>,
     pub(crate) __browser: PhantomData<T>,
 }
 
+impl<B: ChromiumPath> ChromiumGetter<B> {
+    pub fn artifacts(&self) -> ProfileArtifacts {
+        ProfileArtifacts {
+            cookies: self.cookies_query.status(),
+            logins: self.login_data_query.status(),
+            logins_for_account: self
+                .login_data_for_account_query
+                .status(),
+            key: self.crypto.status(),
+        }
+    }
+}
+
 #[derive(Clone)]
 #[derive(Debug)]
 #[derive(Default)]
 pub struct ChromiumCookieGetter<T: ChromiumPath> {
     pub(crate) cookies_query: CookiesQuery,
-    pub(crate) crypto: Decrypter,
+    pub(crate) crypto: Arc<Decrypter>,
     pub(crate) __browser: PhantomData<T>,
 }
 
@@ -101,7 +229,7 @@ pub struct ChromiumCookieGetter<T: ChromiumPath> {
 pub struct ChromiumLoginGetter<T: ChromiumPath> {
     pub(crate) login_data_query: LoginDataQuery,
     pub(crate) login_data_for_account_query: Option<LoginDataQuery>,
-    pub(crate) crypto: Decrypter,
+    pub(crate) crypto: Arc<Decrypter>,
     pub(crate) __browser: PhantomData<T>,
 }
 
@@ -116,56 +244,337 @@ macro_rules! impl_display {
         )*
     };
 }
-impl_display![ChromiumGetter, ChromiumCookieGetter, ChromiumLoginGetter,];
+/// Reads the `History` database, no decryption needed
+///
+/// Initialize it with [`ChromiumBuilder::build_history`](builder::ChromiumBuilder::build_history)
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct ChromiumHistoryGetter<T: ChromiumPath> {
+    pub(crate) history_query: HistoryQuery,
+    pub(crate) __browser: PhantomData<T>,
+}
+
+/// Reads the `Web Data` database, autofill entries, addresses, credit cards and IBANs
+///
+/// Initialize it with [`ChromiumBuilder::build_web_data`](builder::ChromiumBuilder::build_web_data)
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct ChromiumWebDataGetter<T: ChromiumPath> {
+    pub(crate) web_data_query: WebDataQuery,
+    pub(crate) crypto: Arc<Decrypter>,
+    pub(crate) accounts: Vec<ProfileAccount>,
+    pub(crate) __browser: PhantomData<T>,
+}
+
+impl<B: ChromiumPath> ChromiumWebDataGetter<B> {
+    /// Accounts signed in to the profile, from `Preferences` and `Local State`
+    pub fn accounts(&self) -> &[ProfileAccount] {
+        &self.accounts
+    }
+}
+
+/// `localStorage` of every origin, read from the `Local Storage` LevelDB
+///
+/// Initialize it with [`ChromiumBuilder::build_local_storage`](builder::ChromiumBuilder::build_local_storage)
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct ChromiumLocalStorageGetter<T: ChromiumPath> {
+    pub(crate) items: Vec<LocalStorageItem>,
+    pub(crate) __browser: PhantomData<T>,
+}
+
+/// IndexedDB of every origin, a LevelDB per origin
+///
+/// Initialize it with [`ChromiumBuilder::build_indexed_db`](builder::ChromiumBuilder::build_indexed_db)
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct ChromiumIndexedDbGetter<T: ChromiumPath> {
+    /// Origins and the copies of their databases
+    pub(crate) databases: Vec<(String, PathBuf)>,
+    pub(crate) __browser: PhantomData<T>,
+}
+
+/// Writes cookies into the `Cookies` of the profile, not a copy
+///
+/// Initialize it with [`ChromiumBuilder::build_cookie_writer`](builder::ChromiumBuilder::build_cookie_writer)
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct ChromiumCookieWriter<T: ChromiumPath> {
+    pub(crate) base: PathBuf,
+    pub(crate) crypto: Arc<Decrypter>,
+    pub(crate) __browser: PhantomData<T>,
+}
+
+impl<B: ChromiumPath + Sync> ChromiumCookieWriter<B> {
+    /// The `Cookies` database that is written
+    pub fn cookies_path(&self) -> PathBuf {
+        B::cookies(self.base.clone())
+    }
+
+    /// Insert `cookies`, replacing the ones with the same host, name and path.
+    ///
+    /// Fails with [`ChromiumError::BrowserRunning`] while the browser holds the profile,
+    /// a lock left by a crash must be removed by hand.
+    /// `Cookies` is copied to `Cookies.<UTC time>.bak` first, the path of the copy is returned.
+    pub async fn write_cookies<I>(&self, cookies: I) -> Result<PathBuf>
+    where
+        I: IntoIterator<Item = SetCookie> + Send,
+        I::IntoIter: Send,
+    {
+        ensure_closed::<B>(&self.base).await?;
+        let now = Utc::now();
+        let path = self.cookies_path();
+        let backup = backup(&path, now).await?;
+
+        let writer = CookiesWriter::new(&path)
+            .await
+            .context(DbSnafu)?;
+        let host_key_digest = writer
+            .version()
+            .await
+            .context(DbSnafu)?
+            >= HOST_KEY_DIGEST_VERSION;
+        let rows = cookies
+            .into_iter()
+            .map(|cookie| {
+                let encrypted = self
+                    .crypto
+                    .encrypt_cookie(cookie.value(), host_key_digest.then_some(cookie.host_key()))
+                    .context(EncryptSnafu)?;
+                Ok(cookie.into_model(encrypted, now))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        writer
+            .upsert(rows)
+            .await
+            .context(DbSnafu)?;
+        writer
+            .close()
+            .await
+            .context(DbSnafu)?;
+
+        Ok(backup)
+    }
+}
+
+/// Imports logins into the `Login Data` of the profile, not a copy
+///
+/// Initialize it with [`ChromiumBuilder::build_login_importer`](builder::ChromiumBuilder::build_login_importer)
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct ChromiumLoginImporter<T: ChromiumPath> {
+    pub(crate) base: PathBuf,
+    /// A copy taken at build time, read by dry runs
+    pub(crate) login_data_query: LoginDataQuery,
+    pub(crate) crypto: Arc<Decrypter>,
+    pub(crate) __browser: PhantomData<T>,
+}
+
+impl<B: ChromiumPath + Sync> ChromiumLoginImporter<B> {
+    /// The `Login Data` database that is written
+    pub fn login_data_path(&self) -> PathBuf {
+        B::login_data(self.base.clone())
+    }
+
+    /// Encrypt the passwords of `logins` with the key of the profile and insert them.
+    ///
+    /// Logins whose [`LoginKey`] is already stored, or appeared earlier in `logins`, are skipped.
+    /// Empty `signon_realm`s are filled in from `origin_url`, missing dates are now.
+    /// A dry run only reports, reading the copy taken at build time.
+    /// Otherwise it fails like [`ChromiumCookieWriter::write_cookies`] while the browser is running,
+    /// and `Login Data` is copied to `Login Data.<UTC time>.bak` first.
+    ///
+    /// Profiles with a `Ya Passman Data` fail with [`YandexError::Import`].
+    pub async fn import_logins<I>(&self, logins: I, dry_run: bool) -> Result<ImportReport>
+    where
+        I: IntoIterator<Item = LoginData> + Send,
+        I::IntoIter: Send,
+    {
+        if self
+            .login_data_query
+            .query_local_encryptor_data()
+            .await
+            .context(DbSnafu)?
+            .is_some()
+        {
+            return ImportSnafu
+                .fail()
+                .context(YandexSnafu);
+        }
+
+        if dry_run {
+            let mut stored = self
+                .login_data_query
+                .query_keys()
+                .await
+                .context(DbSnafu)?;
+            let mut report = ImportReport { dry_run, ..Default::default() };
+            for login in logins {
+                let key = LoginKey::new(&login);
+                if stored.insert(key.clone()) {
+                    report.imported.push(key);
+                }
+                else {
+                    report.duplicates.push(key);
+                }
+            }
+            return Ok(report);
+        }
+
+        ensure_closed::<B>(&self.base).await?;
+        let now = Utc::now();
+        let path = self.login_data_path();
+        let backup = backup(&path, now).await?;
+
+        let writer = LoginDataWriter::new(&path)
+            .await
+            .context(DbSnafu)?;
+        let mut stored = writer
+            .keys()
+            .await
+            .context(DbSnafu)?;
+        let mut report = ImportReport {
+            backup: Some(backup),
+            ..Default::default()
+        };
+        let mut rows = vec![];
+        for login in logins {
+            let key = LoginKey::new(&login);
+            if !stored.insert(key.clone()) {
+                report.duplicates.push(key);
+                continue;
+            }
+            let password_value = login
+                .password_value
+                .as_ref()
+                .map(|v| {
+                    self.crypto
+                        .encrypt_raw(v.expose_secret().as_bytes())
+                })
+                .transpose()
+                .context(EncryptSnafu)?;
+            rows.push(login_model(login, password_value, now));
+            report.imported.push(key);
+        }
+        writer
+            .insert(rows)
+            .await
+            .context(DbSnafu)?;
+        writer
+            .close()
+            .await
+            .context(DbSnafu)?;
+
+        Ok(report)
+    }
+}
+
+/// Fails with [`ChromiumError::BrowserRunning`] while the browser holds the user data dir `base`
+async fn ensure_closed<B: ChromiumPath>(base: &Path) -> Result<()> {
+    let lock = B::profile_lock(base.to_owned());
+    // `SingletonLock` is a symlink to `<hostname>-<pid>`, it never resolves
+    snafu::ensure!(
+        tokio::fs::symlink_metadata(&lock)
+            .await
+            .is_err(),
+        BrowserRunningSnafu { lock }
+    );
+    Ok(())
+}
+
+/// Copy `path` to `<path>.<now>.bak` and return the copy
+async fn backup(path: &Path, now: DateTime<Utc>) -> Result<PathBuf> {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".{}.bak", now.format("%Y%m%dT%H%M%S%.6f")));
+    let backup = PathBuf::from(backup);
+    tokio::fs::copy(path, &backup)
+        .await
+        .context(IoSnafu { path })?;
+    Ok(backup)
+}
+
+impl_display![
+    ChromiumGetter,
+    ChromiumCookieGetter,
+    ChromiumLoginGetter,
+    ChromiumHistoryGetter,
+    ChromiumWebDataGetter,
+    ChromiumLocalStorageGetter,
+    ChromiumIndexedDbGetter,
+    ChromiumCookieWriter,
+    ChromiumLoginImporter,
+];
 
 impl<B: ChromiumPath> SealedCrypto for ChromiumGetter<B> {
-    fn crypto(&self) -> &Decrypter {
-        &self.crypto
+    fn crypto(&self) -> Result<&Arc<Decrypter>> {
+        self.crypto.get(ArtifactKind::Key)
     }
 }
 impl<B: ChromiumPath> SealedCrypto for ChromiumCookieGetter<B> {
-    fn crypto(&self) -> &Decrypter {
-        &self.crypto
+    fn crypto(&self) -> Result<&Arc<Decrypter>> {
+        Ok(&self.crypto)
     }
 }
 impl<B: ChromiumPath> SealedCrypto for ChromiumLoginGetter<B> {
-    fn crypto(&self) -> &Decrypter {
-        &self.crypto
+    fn crypto(&self) -> Result<&Arc<Decrypter>> {
+        Ok(&self.crypto)
+    }
+}
+
+impl<B: ChromiumPath> SealedCrypto for ChromiumWebDataGetter<B> {
+    fn crypto(&self) -> Result<&Arc<Decrypter>> {
+        Ok(&self.crypto)
     }
 }
 
 impl<B: ChromiumPath> SealedCookies for ChromiumCookieGetter<B> {
-    fn cookies_query(&self) -> &CookiesQuery {
-        &self.cookies_query
+    fn cookies_query(&self) -> Result<&CookiesQuery> {
+        Ok(&self.cookies_query)
     }
 }
 
 impl<B: ChromiumPath> SealedCookies for ChromiumGetter<B> {
-    fn cookies_query(&self) -> &CookiesQuery {
-        &self.cookies_query
+    fn cookies_query(&self) -> Result<&CookiesQuery> {
+        self.cookies_query
+            .get(ArtifactKind::Cookies)
     }
 }
 
 impl<B: ChromiumPath> SealedLogins for ChromiumGetter<B> {
-    fn login_data_query(&self) -> &LoginDataQuery {
-        &self.login_data_query
+    fn login_data_query(&self) -> Result<&LoginDataQuery> {
+        self.login_data_query
+            .get(ArtifactKind::Logins)
     }
 
     fn login_data_for_account_query(&self) -> Option<&LoginDataQuery> {
         self.login_data_for_account_query
-            .as_ref()
+            .as_option()
+    }
+
+    fn is_edge(&self) -> bool {
+        B::NAME == Edge::NAME
     }
 }
 
 impl<B: ChromiumPath> SealedLogins for ChromiumLoginGetter<B> {
-    fn login_data_query(&self) -> &LoginDataQuery {
-        &self.login_data_query
+    fn login_data_query(&self) -> Result<&LoginDataQuery> {
+        Ok(&self.login_data_query)
     }
 
     fn login_data_for_account_query(&self) -> Option<&LoginDataQuery> {
         self.login_data_for_account_query
             .as_ref()
     }
+
+    fn is_edge(&self) -> bool {
+        B::NAME == Edge::NAME
+    }
 }
 
 impl<B: ChromiumPath> GetCookies for ChromiumGetter<B> {}
@@ -176,31 +585,75 @@ impl<B: ChromiumPath> GetLogins for ChromiumLoginGetter<B> {}
 
 impl<B: ChromiumPath> GetCookiesLogins for ChromiumGetter<B> {}
 
+impl<B: ChromiumPath> SealedHistory for ChromiumHistoryGetter<B> {
+    fn history_query(&self) -> &HistoryQuery {
+        &self.history_query
+    }
+}
+
+impl<B: ChromiumPath> GetHistory for ChromiumHistoryGetter<B> {}
+
+impl<B: ChromiumPath> SealedWebData for ChromiumWebDataGetter<B> {
+    fn web_data_query(&self) -> &WebDataQuery {
+        &self.web_data_query
+    }
+
+    fn profile_accounts(&self) -> &[ProfileAccount] {
+        &self.accounts
+    }
+}
+
+impl<B: ChromiumPath> GetWebData for ChromiumWebDataGetter<B> {}
+
+impl<B: ChromiumPath> SealedLocalStorage for ChromiumLocalStorageGetter<B> {
+    fn local_storage_items(&self) -> &[LocalStorageItem] {
+        &self.items
+    }
+}
+
+impl<B: ChromiumPath> GetLocalStorage for ChromiumLocalStorageGetter<B> {}
+
+impl<B: ChromiumPath> SealedIndexedDb for ChromiumIndexedDbGetter<B> {
+    fn indexed_db_dirs(&self) -> &[(String, PathBuf)] {
+        &self.databases
+    }
+}
+
+impl<B: ChromiumPath> GetIndexedDb for ChromiumIndexedDbGetter<B> {}
+
 #[async_trait::async_trait]
 trait SealedCrypto {
-    fn crypto(&self) -> &Decrypter;
-    async fn par_decrypt_logins(&self, raw: Vec<logins::Model>) -> Result<Vec<LoginData>>
+    fn crypto(&self) -> Result<&Arc<Decrypter>>;
+
+    fn lazy_logins(&self, raw: Vec<RawLogin>) -> Result<Vec<LazyLoginData>> {
+        let crypto = self.crypto()?;
+        Ok(raw
+            .into_iter()
+            .map(|v| LazyLoginData::new(v, Arc::clone(crypto)))
+            .collect())
+    }
+
+    fn lazy_ck(
+        &self,
+        raw: Vec<cookies::Model>,
+        host_key_digest: bool,
+    ) -> Result<Vec<LazyChromiumCookie>> {
+        let crypto = self.crypto()?;
+        Ok(raw
+            .into_iter()
+            .map(|v| LazyChromiumCookie::new(v, Arc::clone(crypto), host_key_digest))
+            .collect())
+    }
+
+    async fn par_decrypt_logins(&self, raw: Vec<RawLogin>) -> Result<Vec<LoginData>>
     where
         Self: Sync,
     {
-        let crypto = self.crypto().clone();
+        let crypto = Arc::clone(self.crypto()?);
 
         task::spawn_blocking(move || {
             raw.into_par_iter()
-                .map(|mut v| {
-                    let res = v
-                        .password_value
-                        .as_mut()
-                        .and_then(|v| {
-                            crypto
-                                .decrypt(v, Which::Login)
-                                .ok()
-                        });
-
-                    let mut login_data = LoginData::from(v);
-                    login_data.password_value = res;
-                    login_data
-                })
+                .map(|v| v.decrypt(&crypto))
                 .collect()
         })
         .await
@@ -209,20 +662,35 @@ trait SealedCrypto {
 
     /// parallel decrypt cookies
     /// and not blocking scheduling
-    async fn par_decrypt_ck(&self, raw: Vec<cookies::Model>) -> Result<Vec<ChromiumCookie>>
+    ///
+    /// `host_key_digest`, see [`CookiesQuery::host_key_digest`]
+    async fn par_decrypt_ck(
+        &self,
+        raw: Vec<cookies::Model>,
+        host_key_digest: bool,
+    ) -> Result<Vec<ChromiumCookie>>
     where
         Self: Sync,
     {
-        let crypto = self.crypto().clone();
+        let crypto = Arc::clone(self.crypto()?);
 
         let decrypted_ck = task::spawn_blocking(move || {
             raw.into_par_iter()
                 .map(|mut v| {
                     let res = crypto
-                        .decrypt(&mut v.encrypted_value, Which::Cookie)
-                        .ok();
+                        .decrypt_cookie(
+                            &mut v.encrypted_value,
+                            host_key_digest.then_some(v.host_key.as_str()),
+                        )
+                        .inspect_err(|_e| {
+                            #[cfg(feature = "tracing")]
+                            tracing::warn!(error = %_e, name = v.name, "decrypt cookie failed");
+                        });
                     let mut cookies = ChromiumCookie::from(v);
-                    cookies.decrypted_value = res;
+                    match res {
+                        Ok(value) => cookies.decrypted_value = Some(value),
+                        Err(e) => cookies.decrypt_error = Some(DecryptError::from(&e)),
+                    }
                     cookies
                 })
                 .collect()
@@ -234,12 +702,197 @@ trait SealedCrypto {
 }
 
 trait SealedCookies {
-    fn cookies_query(&self) -> &CookiesQuery;
+    fn cookies_query(&self) -> Result<&CookiesQuery>;
 }
 
-trait SealedLogins {
-    fn login_data_query(&self) -> &LoginDataQuery;
+#[async_trait::async_trait]
+trait SealedLogins: SealedCrypto {
+    fn login_data_query(&self) -> Result<&LoginDataQuery>;
     fn login_data_for_account_query(&self) -> Option<&LoginDataQuery>;
+
+    /// The account store alone is enough when `Login Data` is missing
+    fn primary_login_query(&self) -> Result<Option<&LoginDataQuery>> {
+        match self.login_data_query() {
+            Ok(q) => Ok(Some(q)),
+            Err(_)
+                if self
+                    .login_data_for_account_query()
+                    .is_some() =>
+            {
+                Ok(None)
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Microsoft Edge extends the logins with `logins_edge_extended`
+    fn is_edge(&self) -> bool;
+
+    /// Both stores, the account store is `None` when it is missing
+    fn login_stores(&self) -> Result<[(LoginStore, Option<&LoginDataQuery>); 2]> {
+        Ok([
+            (LoginStore::Local, self.primary_login_query()?),
+            (LoginStore::Account, self.login_data_for_account_query()),
+        ])
+    }
+
+    /// Join the rows extending the logins of each store,
+    /// a profile too old to have these tables still gets its logins
+    async fn join_login_rows(
+        &self,
+        merged: Vec<(logins::Model, LoginStore)>,
+    ) -> Result<Vec<RawLogin>>
+    where
+        Self: Sync,
+    {
+        let mut rows = LoginRows::default();
+        for (store, query) in self.login_stores()? {
+            let Some(query) = query
+            else {
+                continue;
+            };
+            let ids: Vec<i32> = merged
+                .iter()
+                .filter(|(_, s)| *s == store)
+                .map(|(v, _)| v.id)
+                .collect();
+            if ids.is_empty() {
+                continue;
+            }
+
+            rows.notes.extend(optional_rows(
+                query
+                    .query_password_notes(&ids)
+                    .await,
+                store,
+                "password_notes",
+            ));
+            if self.is_edge() {
+                rows.edge.extend(optional_rows(
+                    query
+                        .query_edge_extended(&ids)
+                        .await,
+                    store,
+                    "logins_edge_extended",
+                ));
+            }
+            rows.insecure_credentials
+                .extend(optional_rows(
+                    query
+                        .query_insecure_credentials(&ids)
+                        .await,
+                    store,
+                    "insecure_credentials",
+                ));
+        }
+        let mut rows = join_login_rows(merged, rows);
+
+        for (store, query) in self.login_stores()? {
+            let Some(query) = query
+            else {
+                continue;
+            };
+            let Some(yandex) = self
+                .yandex_encryptor(query)
+                .await?
+            else {
+                continue;
+            };
+            for row in rows
+                .iter_mut()
+                .filter(|v| v.store == store)
+            {
+                row.yandex = Some(Arc::clone(&yandex));
+            }
+        }
+        Ok(rows)
+    }
+
+    /// The key of the extra password layer of a `Ya Passman Data`, `None` for other browsers
+    async fn yandex_encryptor(&self, query: &LoginDataQuery) -> Result<Option<Arc<YandexEncryptor>>>
+    where
+        Self: Sync,
+    {
+        let Some(data) = query
+            .query_local_encryptor_data()
+            .await
+            .context(DbSnafu)?
+        else {
+            return Ok(None);
+        };
+        if query
+            .has_master_password()
+            .await
+            .context(DbSnafu)?
+        {
+            return MasterPasswordSnafu
+                .fail()
+                .context(YandexSnafu);
+        }
+        YandexEncryptor::unseal(self.crypto()?, &data)
+            .map(|v| Some(Arc::new(v)))
+            .context(YandexSnafu)
+    }
+
+    /// Rows of `Login Data` and `Login Data For Account`, see [`merge_stores`]
+    async fn raw_logins_filter(&self, filter: Condition) -> Result<Vec<RawLogin>>
+    where
+        Self: Sync,
+    {
+        let local = match self.primary_login_query()? {
+            Some(q) => q
+                .query_login_dt_filter(filter.clone())
+                .await
+                .context(DbSnafu)?,
+            None => vec![],
+        };
+        let account = match self.login_data_for_account_query() {
+            Some(q) => q
+                .query_login_dt_filter(filter)
+                .await
+                .context(DbSnafu)?,
+            None => vec![],
+        };
+        self.join_login_rows(merge_stores(local, account))
+            .await
+    }
+
+    async fn raw_logins_all(&self) -> Result<Vec<RawLogin>>
+    where
+        Self: Sync,
+    {
+        let local = match self.primary_login_query()? {
+            Some(q) => q
+                .query_all_login_dt()
+                .await
+                .context(DbSnafu)?,
+            None => vec![],
+        };
+        let account = match self.login_data_for_account_query() {
+            Some(q) => q
+                .query_all_login_dt()
+                .await
+                .context(DbSnafu)?,
+            None => vec![],
+        };
+        self.join_login_rows(merge_stores(local, account))
+            .await
+    }
+}
+
+/// Rows of a table a profile may lack, tagged with their store
+fn optional_rows<T>(
+    rows: std::result::Result<Vec<T>, DbErr>,
+    store: LoginStore,
+    _table: &str,
+) -> impl Iterator<Item = (T, LoginStore)> {
+    rows.inspect_err(|_e| {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %_e, %store, table = _table, "query login rows failed");
+    })
+    .unwrap_or_default()
+    .into_iter()
+    .map(move |v| (v, store))
 }
 
 #[expect(private_bounds, reason = "impl details")]
@@ -273,19 +926,9 @@ pub trait GetLogins: SealedCrypto + SealedLogins + Display {
     where
         Self: Sync,
     {
-        let mut raw_login = self
-            .login_data_query()
-            .query_login_dt_filter(filter.clone())
-            .await
-            .context(DbSnafu)?;
-        if raw_login.is_empty()
-            && let Some(query) = &self.login_data_for_account_query()
-        {
-            raw_login = query
-                .query_login_dt_filter(filter)
-                .await
-                .context(DbSnafu)?;
-        }
+        let raw_login = self
+            .raw_logins_filter(filter)
+            .await?;
         self.par_decrypt_logins(raw_login)
             .await
     }
@@ -296,19 +939,13 @@ pub trait GetLogins: SealedCrypto + SealedLogins + Display {
     where
         Self: Sync,
     {
-        let mut raw_login = self
-            .login_data_query()
-            .query_login_dt_filter(ChromiumLoginCol::OriginUrl.contains(host))
-            .await
-            .context(DbSnafu)?;
-        if raw_login.is_empty()
-            && let Some(query) = &self.login_data_for_account_query()
-        {
-            raw_login = query
-                .query_login_dt_filter(ChromiumLoginCol::OriginUrl.contains(host))
-                .await
-                .context(DbSnafu)?;
-        }
+        let raw_login = self
+            .raw_logins_filter(
+                ChromiumLoginCol::OriginUrl
+                    .contains(host)
+                    .into_condition(),
+            )
+            .await?;
         self.par_decrypt_logins(raw_login)
             .await
     }
@@ -318,25 +955,72 @@ pub trait GetLogins: SealedCrypto + SealedLogins + Display {
     where
         Self: Sync,
     {
-        let mut raw_login = self
-            .login_data_query()
-            .query_all_login_dt()
-            .await
-            .context(DbSnafu)?;
-        if raw_login.is_empty()
-            && let Some(query) = &self.login_data_for_account_query()
-        {
-            raw_login = query
-                .query_all_login_dt()
-                .await
-                .context(DbSnafu)?;
-        }
+        let raw_login = self.raw_logins_all().await?;
         self.par_decrypt_logins(raw_login)
             .await
     }
-}
 
-#[expect(private_bounds, reason = "impl details")]
+    /// Like [`GetLogins::logins_filter`], but the passwords are decrypted on demand
+    async fn logins_filter_lazy(&self, filter: Condition) -> Result<Vec<LazyLoginData>>
+    where
+        Self: Sync,
+    {
+        let raw_login = self
+            .raw_logins_filter(filter)
+            .await?;
+        self.lazy_logins(raw_login)
+    }
+
+    /// Like [`GetLogins::logins_by_host`], but the passwords are decrypted on demand
+    async fn logins_by_host_lazy(&self, host: &str) -> Result<Vec<LazyLoginData>>
+    where
+        Self: Sync,
+    {
+        let raw_login = self
+            .raw_logins_filter(
+                ChromiumLoginCol::OriginUrl
+                    .contains(host)
+                    .into_condition(),
+            )
+            .await?;
+        self.lazy_logins(raw_login)
+    }
+
+    /// Like [`GetLogins::logins_all`], but the passwords are decrypted on demand
+    async fn logins_all_lazy(&self) -> Result<Vec<LazyLoginData>>
+    where
+        Self: Sync,
+    {
+        let raw_login = self.raw_logins_all().await?;
+        self.lazy_logins(raw_login)
+    }
+
+    /// The save prompt dismissals per origin of both stores
+    async fn login_stats(&self) -> Result<Vec<LoginStats>>
+    where
+        Self: Sync,
+    {
+        let mut res = vec![];
+        for (store, query) in self.login_stores()? {
+            let Some(query) = query
+            else {
+                continue;
+            };
+            let stats = query
+                .query_all_stats()
+                .await
+                .context(DbSnafu)?;
+            res.extend(
+                stats
+                    .into_iter()
+                    .map(|v| LoginStats::new(v, store)),
+            );
+        }
+        Ok(res)
+    }
+}
+
+#[expect(private_bounds, reason = "impl details")]
 #[async_trait::async_trait]
 pub trait GetCookies: SealedCrypto + SealedCookies + Display {
     /// filter cookies
@@ -367,12 +1051,13 @@ pub trait GetCookies: SealedCrypto + SealedCookies + Display {
     where
         Self: Sync,
     {
-        let raw_ck = self
-            .cookies_query()
+        let query = self.cookies_query()?;
+        let raw_ck = query
             .cookies_filter(filter)
             .await
             .context(DbSnafu)?;
-        self.par_decrypt_ck(raw_ck).await
+        self.par_decrypt_ck(raw_ck, query.host_key_digest())
+            .await
     }
 
     /// Filter by host
@@ -381,12 +1066,13 @@ pub trait GetCookies: SealedCrypto + SealedCookies + Display {
     where
         Self: Sync,
     {
-        let raw_ck = self
-            .cookies_query()
+        let query = self.cookies_query()?;
+        let raw_ck = query
             .cookies_by_host(host.as_ref())
             .await
             .context(DbSnafu)?;
-        self.par_decrypt_ck(raw_ck).await
+        self.par_decrypt_ck(raw_ck, query.host_key_digest())
+            .await
     }
 
     /// Return all cookies
@@ -394,12 +1080,82 @@ pub trait GetCookies: SealedCrypto + SealedCookies + Display {
     where
         Self: Sync,
     {
-        let raw_ck = self
-            .cookies_query()
+        let query = self.cookies_query()?;
+        let raw_ck = query
+            .cookies_all()
+            .await
+            .context(DbSnafu)?;
+        self.par_decrypt_ck(raw_ck, query.host_key_digest())
+            .await
+    }
+
+    /// Like [`GetCookies::cookies_filter`], only the cookies sent under `top_level_site`,
+    /// see [`CookiesInfo::in_partition`]
+    async fn cookies_filter_in(
+        &self,
+        filter: Condition,
+        top_level_site: Option<&str>,
+    ) -> Result<Vec<ChromiumCookie>>
+    where
+        Self: Sync,
+    {
+        let mut cookies = self.cookies_filter(filter).await?;
+        cookies.retain(|v| v.in_partition(top_level_site));
+        Ok(cookies)
+    }
+
+    /// Like [`GetCookies::cookies_by_host`], only the cookies sent under `top_level_site`,
+    /// see [`CookiesInfo::in_partition`]
+    async fn cookies_by_host_in(
+        &self,
+        host: &str,
+        top_level_site: Option<&str>,
+    ) -> Result<Vec<ChromiumCookie>>
+    where
+        Self: Sync,
+    {
+        let mut cookies = self.cookies_by_host(host).await?;
+        cookies.retain(|v| v.in_partition(top_level_site));
+        Ok(cookies)
+    }
+
+    /// Like [`GetCookies::cookies_filter`], but the values are decrypted on demand
+    async fn cookies_filter_lazy(&self, filter: Condition) -> Result<Vec<LazyChromiumCookie>>
+    where
+        Self: Sync,
+    {
+        let query = self.cookies_query()?;
+        let raw_ck = query
+            .cookies_filter(filter)
+            .await
+            .context(DbSnafu)?;
+        self.lazy_ck(raw_ck, query.host_key_digest())
+    }
+
+    /// Like [`GetCookies::cookies_by_host`], but the values are decrypted on demand
+    async fn cookies_by_host_lazy(&self, host: &str) -> Result<Vec<LazyChromiumCookie>>
+    where
+        Self: Sync,
+    {
+        let query = self.cookies_query()?;
+        let raw_ck = query
+            .cookies_by_host(host)
+            .await
+            .context(DbSnafu)?;
+        self.lazy_ck(raw_ck, query.host_key_digest())
+    }
+
+    /// Like [`GetCookies::cookies_all`], but the values are decrypted on demand
+    async fn cookies_all_lazy(&self) -> Result<Vec<LazyChromiumCookie>>
+    where
+        Self: Sync,
+    {
+        let query = self.cookies_query()?;
+        let raw_ck = query
             .cookies_all()
             .await
             .context(DbSnafu)?;
-        self.par_decrypt_ck(raw_ck).await
+        self.lazy_ck(raw_ck, query.host_key_digest())
     }
 
     /// get `LEETCODE_SESSION` and `csrftoken` for leetcode
@@ -407,8 +1163,9 @@ pub trait GetCookies: SealedCrypto + SealedCookies + Display {
     where
         Self: Sync,
     {
-        let cookies = self
-            .cookies_query()
+        let query = self.cookies_query()?;
+        let host_key_digest = query.host_key_digest();
+        let cookies = query
             .cookies_filter(
                 ChromiumCookieCol::HostKey
                     .contains(host)
@@ -431,7 +1188,9 @@ pub trait GetCookies: SealedCrypto + SealedCookies + Display {
         }
 
         // # Safety: scope task
-        let cy = unsafe { std::mem::transmute::<&Decrypter, &'static Decrypter>(self.crypto()) };
+        let cy = unsafe {
+            std::mem::transmute::<&Decrypter, &'static Decrypter>(self.crypto()?.as_ref())
+        };
 
         for mut cookie in cookies {
             if cookie.name == "csrftoken" {
@@ -446,12 +1205,16 @@ pub trait GetCookies: SealedCrypto + SealedCookies + Display {
                 }
 
                 let csrf_hd = task::spawn_blocking(move || {
-                    cy.decrypt(&mut cookie.encrypted_value, Which::Cookie)
-                        .inspect_err(|_e| {
-                            #[cfg(feature = "tracing")]
-                            tracing::warn!(error = %_e, "decrypt csrf failed");
-                        })
-                        .unwrap_or_default()
+                    cy.decrypt_cookie(
+                        &mut cookie.encrypted_value,
+                        host_key_digest.then_some(cookie.host_key.as_str()),
+                    )
+                    .inspect_err(|_e| {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(error = %_e, "decrypt csrf failed");
+                    })
+                    .map(SecretString::into_unprotected)
+                    .unwrap_or_default()
                 });
                 hds.push((csrf_hd, CsrfSession::Csrf));
             }
@@ -467,12 +1230,16 @@ pub trait GetCookies: SealedCrypto + SealedCookies + Display {
                 }
 
                 let session_hd = task::spawn_blocking(move || {
-                    cy.decrypt(&mut cookie.encrypted_value, Which::Cookie)
-                        .inspect_err(|_e| {
-                            #[cfg(feature = "tracing")]
-                            tracing::warn!(error = %_e, "decrypt session failed");
-                        })
-                        .unwrap_or_default()
+                    cy.decrypt_cookie(
+                        &mut cookie.encrypted_value,
+                        host_key_digest.then_some(cookie.host_key.as_str()),
+                    )
+                    .inspect_err(|_e| {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(error = %_e, "decrypt session failed");
+                    })
+                    .map(SecretString::into_unprotected)
+                    .unwrap_or_default()
                 });
                 hds.push((session_hd, CsrfSession::Session));
             }
@@ -490,3 +1257,507 @@ pub trait GetCookies: SealedCrypto + SealedCookies + Display {
 }
 
 pub trait GetCookiesLogins: GetLogins + GetCookies {}
+
+#[async_trait::async_trait]
+trait SealedHistory {
+    fn history_query(&self) -> &HistoryQuery;
+
+    /// Load the referring visits and the URLs of `raw`
+    async fn resolve_visits(&self, raw: Vec<visits::Model>) -> Result<Vec<HistoryVisit>>
+    where
+        Self: Sync,
+    {
+        /// Give up on longer referrer chains
+        const MAX_DEPTH: usize = 64;
+
+        let query = self.history_query();
+        let mut all: HashMap<i64, visits::Model> = raw
+            .iter()
+            .map(|v| (v.id, v.clone()))
+            .collect();
+
+        let mut pending: Vec<i64> = raw
+            .iter()
+            .filter_map(|v| v.from_visit)
+            .collect();
+        for _ in 0..MAX_DEPTH {
+            pending.retain(|id| *id != 0 && !all.contains_key(id));
+            pending.sort_unstable();
+            pending.dedup();
+            if pending.is_empty() {
+                break;
+            }
+            let found = query
+                .visits_by_ids(&pending)
+                .await
+                .context(DbSnafu)?;
+            pending = found
+                .iter()
+                .filter_map(|v| v.from_visit)
+                .collect();
+            all.extend(
+                found
+                    .into_iter()
+                    .map(|v| (v.id, v)),
+            );
+        }
+
+        let mut url_ids: Vec<i64> = all
+            .values()
+            .map(|v| v.url)
+            .collect();
+        url_ids.sort_unstable();
+        url_ids.dedup();
+        let urls: HashMap<i64, urls::Model> = query
+            .urls_by_ids(&url_ids)
+            .await
+            .context(DbSnafu)?
+            .into_iter()
+            .map(|v| (v.id, v))
+            .collect();
+
+        Ok(raw
+            .into_iter()
+            .map(|v| HistoryVisit::new(&v, &urls, &all))
+            .collect())
+    }
+}
+
+#[expect(private_bounds, reason = "impl details")]
+#[async_trait::async_trait]
+pub trait GetHistory: SealedHistory + Display {
+    async fn urls_filter(&self, filter: Condition) -> Result<Vec<HistoryUrl>>
+    where
+        Self: Sync,
+    {
+        let raw = self
+            .history_query()
+            .urls_filter(filter)
+            .await
+            .context(DbSnafu)?;
+        Ok(raw
+            .into_iter()
+            .map(HistoryUrl::from)
+            .collect())
+    }
+
+    /// Filter by host
+    #[doc(alias = "urls_by_domain")]
+    async fn urls_by_host(&self, host: &str) -> Result<Vec<HistoryUrl>>
+    where
+        Self: Sync,
+    {
+        self.urls_filter(
+            HistoryUrlCol::Url
+                .contains(host)
+                .into_condition(),
+        )
+        .await
+    }
+
+    async fn urls_all(&self) -> Result<Vec<HistoryUrl>>
+    where
+        Self: Sync,
+    {
+        self.urls_filter(Condition::all())
+            .await
+    }
+
+    /// Filter by the columns of `visits`, ordered by visit time
+    async fn visits_filter(&self, filter: Condition) -> Result<Vec<HistoryVisit>>
+    where
+        Self: Sync,
+    {
+        let raw = self
+            .history_query()
+            .visits_filter(filter)
+            .await
+            .context(DbSnafu)?;
+        self.resolve_visits(raw).await
+    }
+
+    /// Visits of the URLs that contain `host`
+    #[doc(alias = "visits_by_domain")]
+    async fn visits_by_host(&self, host: &str) -> Result<Vec<HistoryVisit>>
+    where
+        Self: Sync,
+    {
+        let query = self.history_query();
+        let url_ids: Vec<i64> = query
+            .urls_filter(HistoryUrlCol::Url.contains(host))
+            .await
+            .context(DbSnafu)?
+            .into_iter()
+            .map(|v| v.id)
+            .collect();
+        let raw = query
+            .visits_by_url_ids(&url_ids)
+            .await
+            .context(DbSnafu)?;
+        self.resolve_visits(raw).await
+    }
+
+    async fn visits_all(&self) -> Result<Vec<HistoryVisit>>
+    where
+        Self: Sync,
+    {
+        self.visits_filter(Condition::all())
+            .await
+    }
+
+    /// Filter by the columns of `downloads`, ordered by start time
+    async fn downloads_filter(&self, filter: Condition) -> Result<Vec<Download>>
+    where
+        Self: Sync,
+    {
+        let query = self.history_query();
+        let raw = query
+            .downloads_filter(filter)
+            .await
+            .context(DbSnafu)?;
+        let ids: Vec<i64> = raw.iter().map(|v| v.id).collect();
+        let mut chains: HashMap<i64, Vec<String>> = HashMap::new();
+        for link in query
+            .url_chains(&ids)
+            .await
+            .context(DbSnafu)?
+        {
+            chains
+                .entry(link.id)
+                .or_default()
+                .push(link.url);
+        }
+
+        Ok(raw
+            .into_iter()
+            .map(|v: downloads::Model| {
+                let id = v.id;
+                let mut download = Download::from(v);
+                download.url_chain = chains
+                    .remove(&id)
+                    .unwrap_or_default();
+                download
+            })
+            .collect())
+    }
+
+    async fn downloads_all(&self) -> Result<Vec<Download>>
+    where
+        Self: Sync,
+    {
+        self.downloads_filter(Condition::all())
+            .await
+    }
+
+    async fn keyword_search_terms_filter(&self, filter: Condition) -> Result<Vec<KeywordSearchTerm>>
+    where
+        Self: Sync,
+    {
+        let query = self.history_query();
+        let raw = query
+            .keyword_search_terms_filter(filter)
+            .await
+            .context(DbSnafu)?;
+        let mut url_ids: Vec<i64> = raw
+            .iter()
+            .map(|v| v.url_id)
+            .collect();
+        url_ids.sort_unstable();
+        url_ids.dedup();
+        let urls: HashMap<i64, String> = query
+            .urls_by_ids(&url_ids)
+            .await
+            .context(DbSnafu)?
+            .into_iter()
+            .map(|v| (v.id, v.url))
+            .collect();
+
+        Ok(raw
+            .into_iter()
+            .map(|v: keyword_search_terms::Model| {
+                let mut term = KeywordSearchTerm::from(v);
+                term.url = urls.get(&term.url_id).cloned();
+                term
+            })
+            .collect())
+    }
+
+    async fn keyword_search_terms_all(&self) -> Result<Vec<KeywordSearchTerm>>
+    where
+        Self: Sync,
+    {
+        self.keyword_search_terms_filter(Condition::all())
+            .await
+    }
+}
+
+trait SealedWebData {
+    fn web_data_query(&self) -> &WebDataQuery;
+    fn profile_accounts(&self) -> &[ProfileAccount];
+}
+
+#[expect(private_bounds, reason = "impl details")]
+#[async_trait::async_trait]
+pub trait GetWebData: SealedCrypto + SealedWebData + Display {
+    /// Values typed into form fields
+    async fn autofill_filter(&self, filter: Condition) -> Result<Vec<AutofillEntry>>
+    where
+        Self: Sync,
+    {
+        let raw = self
+            .web_data_query()
+            .autofill_filter(filter)
+            .await
+            .context(DbSnafu)?;
+        Ok(raw
+            .into_iter()
+            .map(AutofillEntry::from)
+            .collect())
+    }
+
+    async fn autofill_all(&self) -> Result<Vec<AutofillEntry>>
+    where
+        Self: Sync,
+    {
+        self.autofill_filter(Condition::all())
+            .await
+    }
+
+    /// Contains the card numbers, see [`CreditCard::masked`]
+    async fn credit_cards_filter(&self, filter: Condition) -> Result<Vec<CreditCard>>
+    where
+        Self: Sync,
+    {
+        let raw = self
+            .web_data_query()
+            .credit_cards_filter(filter)
+            .await
+            .context(DbSnafu)?;
+        let crypto = Arc::clone(self.crypto()?);
+
+        task::spawn_blocking(move || {
+            raw.into_iter()
+                .map(|mut v| {
+                    let number = v
+                        .card_number_encrypted
+                        .take()
+                        .and_then(|mut v| crypto.decrypt(&mut v).ok());
+                    let mut card = CreditCard::from(v);
+                    card.card_number = number;
+                    card
+                })
+                .collect()
+        })
+        .await
+        .context(TaskSnafu)
+    }
+
+    async fn credit_cards_all(&self) -> Result<Vec<CreditCard>>
+    where
+        Self: Sync,
+    {
+        self.credit_cards_filter(Condition::all())
+            .await
+    }
+
+    /// Saved addresses with their fields
+    async fn addresses_all(&self) -> Result<Vec<Address>>
+    where
+        Self: Sync,
+    {
+        let (addresses, tokens) = self
+            .web_data_query()
+            .addresses()
+            .await
+            .context(DbSnafu)?;
+        let mut tokens_of: HashMap<String, Vec<address_type_tokens::Model>> = HashMap::new();
+        for token in tokens {
+            tokens_of
+                .entry(token.guid.clone())
+                .or_default()
+                .push(token);
+        }
+
+        Ok(addresses
+            .into_iter()
+            .map(|v| {
+                let tokens = tokens_of
+                    .remove(&v.guid)
+                    .unwrap_or_default();
+                Address::new(v, tokens)
+            })
+            .collect())
+    }
+
+    /// Contains the account numbers, see [`Iban::masked`]
+    async fn ibans_all(&self) -> Result<Vec<Iban>>
+    where
+        Self: Sync,
+    {
+        let raw = self
+            .web_data_query()
+            .ibans()
+            .await
+            .context(DbSnafu)?;
+        let crypto = Arc::clone(self.crypto()?);
+
+        task::spawn_blocking(move || {
+            raw.into_iter()
+                .map(|mut v| {
+                    let value = v
+                        .value_encrypted
+                        .take()
+                        .and_then(|mut v| crypto.decrypt(&mut v).ok());
+                    let mut iban = Iban::from(v);
+                    iban.value = value;
+                    iban
+                })
+                .collect()
+        })
+        .await
+        .context(TaskSnafu)
+    }
+
+    /// OAuth refresh tokens with the account they belong to
+    async fn account_tokens_all(&self) -> Result<Vec<AccountToken>>
+    where
+        Self: Sync,
+    {
+        let raw = self
+            .web_data_query()
+            .token_service()
+            .await
+            .context(DbSnafu)?;
+        let mut tokens: Vec<_> = raw
+            .iter()
+            .map(|v| AccountToken::new(v, self.profile_accounts()))
+            .collect();
+        let crypto = Arc::clone(self.crypto()?);
+
+        task::spawn_blocking(move || {
+            for (token, v) in tokens.iter_mut().zip(raw) {
+                token.token = v
+                    .encrypted_token
+                    .and_then(|mut v| {
+                        let raw = crypto.decrypt_raw(&mut v).ok()?;
+                        str::from_utf8(raw.expose_secret())
+                            .ok()
+                            .map(SecretString::from)
+                    });
+            }
+            tokens
+        })
+        .await
+        .context(TaskSnafu)
+    }
+}
+
+trait SealedLocalStorage {
+    fn local_storage_items(&self) -> &[LocalStorageItem];
+}
+
+/// Items are sorted by origin
+#[expect(private_bounds, reason = "impl details")]
+pub trait GetLocalStorage: SealedLocalStorage + Display {
+    fn local_storage_filter<F>(&self, mut filter: F) -> Vec<&LocalStorageItem>
+    where
+        F: FnMut(&LocalStorageItem) -> bool,
+    {
+        self.local_storage_items()
+            .iter()
+            .filter(|v| filter(v))
+            .collect()
+    }
+
+    /// Items of exactly the origin, like `https://app.slack.com`
+    fn local_storage_by_origin(&self, origin: &str) -> Vec<&LocalStorageItem> {
+        self.local_storage_filter(|v| v.origin == origin)
+    }
+
+    /// Items of the host and its subdomains, whatever the scheme and port
+    fn local_storage_by_host(&self, host: &str) -> Vec<&LocalStorageItem> {
+        self.local_storage_filter(|v| v.match_host(host))
+    }
+
+    fn local_storage_all(&self) -> &[LocalStorageItem] {
+        self.local_storage_items()
+    }
+
+    /// Origins that have items
+    fn local_storage_origins(&self) -> Vec<&str> {
+        let mut res: Vec<&str> = self
+            .local_storage_items()
+            .iter()
+            .map(|v| v.origin.as_str())
+            .collect();
+        res.dedup();
+        res
+    }
+}
+
+trait SealedIndexedDb {
+    fn indexed_db_dirs(&self) -> &[(String, PathBuf)];
+}
+
+/// Databases are read when asked, values are deserialized into [`JsValue`]
+#[expect(private_bounds, reason = "impl details")]
+#[async_trait::async_trait]
+pub trait GetIndexedDb: SealedIndexedDb + Display {
+    /// Origins that have databases
+    fn indexed_db_origins(&self) -> Vec<&str> {
+        self.indexed_db_dirs()
+            .iter()
+            .map(|(origin, _)| origin.as_str())
+            .collect()
+    }
+
+    async fn indexed_db_filter<F>(&self, filter: F) -> Result<Vec<IndexedDb>>
+    where
+        F: Fn(&str) -> bool + Send,
+        Self: Sync,
+    {
+        let dirs: Vec<_> = self
+            .indexed_db_dirs()
+            .iter()
+            .filter(|(origin, _)| filter(origin))
+            .cloned()
+            .collect();
+
+        task::spawn_blocking(move || {
+            dirs.into_iter()
+                .map(|(origin, dir)| {
+                    let db = LevelDb::open(dir).context(LevelDbSnafu)?;
+                    Ok(IndexedDb::from_leveldb(origin, &db))
+                })
+                .collect()
+        })
+        .await
+        .context(TaskSnafu)?
+    }
+
+    /// Exactly the origin, like `https://app.slack.com`
+    async fn indexed_db_by_origin(&self, origin: &str) -> Result<Vec<IndexedDb>>
+    where
+        Self: Sync,
+    {
+        self.indexed_db_filter(|v| v == origin)
+            .await
+    }
+
+    /// The host and its subdomains, whatever the scheme and port
+    async fn indexed_db_by_host(&self, host: &str) -> Result<Vec<IndexedDb>>
+    where
+        Self: Sync,
+    {
+        self.indexed_db_filter(|v| items::match_origin_host(v, host))
+            .await
+    }
+
+    async fn indexed_db_all(&self) -> Result<Vec<IndexedDb>>
+    where
+        Self: Sync,
+    {
+        self.indexed_db_filter(|_| true)
+            .await
+    }
+}
@@ -1,14 +1,12 @@
 use std::path::Path;
 
-use sea_orm::{
-    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, sea_query::IntoCondition,
-};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, QueryFilter, sea_query::IntoCondition};
 
-use super::entities::{
-    moz_cookies::{self, Model},
-    prelude::*,
+use super::entities::moz_cookies::{self, Model};
+use crate::utils::{
+    connect_db,
+    schema::{Schema, user_version},
 };
-use crate::utils::connect_db;
 
 type Result<T> = std::result::Result<T, DbErr>;
 
@@ -18,6 +16,8 @@ type Result<T> = std::result::Result<T, DbErr>;
 #[derive(Default)]
 pub struct CookiesQuery {
     conn: DatabaseConnection,
+    version: i64,
+    schema: Schema<moz_cookies::Entity>,
 }
 
 impl CookiesQuery {
@@ -26,14 +26,23 @@ impl CookiesQuery {
         P: AsRef<Path> + Send,
     {
         let db = connect_db(&path).await?;
-        Ok(Self { conn: db })
+        let version = user_version(&db).await?;
+        let schema = Schema::read(&db).await?;
+        Ok(Self { conn: db, version, schema })
+    }
+
+    /// `PRAGMA user_version` of `cookies.sqlite`
+    pub const fn version(&self) -> i64 {
+        self.version
     }
 
     pub async fn query_cookie_filter<F>(&self, filter: F) -> Result<Vec<Model>>
     where
         F: IntoCondition + Send,
     {
-        let res = MozCookies::find()
+        let res = self
+            .schema
+            .find()
             .filter(filter)
             .all(&self.conn)
             .await?;
@@ -42,7 +51,9 @@ impl CookiesQuery {
     }
 
     pub async fn query_cookie_by_host(&self, host: &str) -> Result<Vec<Model>> {
-        let res = MozCookies::find()
+        let res = self
+            .schema
+            .find()
             .filter(moz_cookies::Column::Host.contains(host))
             .all(&self.conn)
             .await?;
@@ -50,10 +61,76 @@ impl CookiesQuery {
         Ok(res)
     }
     pub async fn query_all_cookie(&self) -> Result<Vec<Model>> {
-        let res = MozCookies::find()
+        let res = self
+            .schema
+            .find()
             .all(&self.conn)
             .await?;
 
         Ok(res)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        browser::cookies::{CookiesInfo, PartitionKey, SameSite},
+        firefox::{MozCookie, SchemeMap},
+        utils::schema::fixture,
+    };
+
+    #[tokio::test]
+    async fn version_matrix() {
+        for version in [10, 12, 14] {
+            let name = format!("firefox_cookies_v{version}");
+            let query = CookiesQuery::new(fixture(&name).await)
+                .await
+                .unwrap();
+            assert_eq!(query.version(), version, "{name}");
+
+            let model = query
+                .query_all_cookie()
+                .await
+                .unwrap()
+                .remove(0);
+            let cookie = MozCookie::from(model.clone());
+            assert_eq!(cookie.value, name[name.len() - 3..], "{name}");
+            assert!(cookie.is_secure, "{name}");
+            let since = |v: i64| version >= v;
+            assert_eq!(cookie.raw_same_site, since(12).then_some(1), "{name}");
+            assert_eq!(
+                cookie.scheme_map,
+                if since(12) {
+                    SchemeMap::HTTPS
+                }
+                else {
+                    SchemeMap::empty()
+                },
+                "{name}"
+            );
+            assert_eq!(
+                cookie.is_partitioned_attribute_set,
+                since(14).then_some(true),
+                "{name}"
+            );
+            assert_eq!(
+                cookie.partition_key,
+                since(14).then(|| PartitionKey::new("https://b.com")),
+                "{name}"
+            );
+            assert_eq!(
+                cookie
+                    .set_cookie_header()
+                    .ends_with("; Partitioned"),
+                since(14),
+                "{name}"
+            );
+            assert_eq!(
+                MozCookie::from(Model { same_site: None, ..model }).same_site,
+                SameSite::Unspecified,
+                "{name}"
+            );
+        }
+    }
+}
@@ -2,11 +2,26 @@ use chrono::{DateTime, Utc};
 
 use self::entities::moz_cookies;
 use super::I64ToMozTime;
-use crate::browser::cookies::{CookiesInfo, SameSite};
+use crate::browser::cookies::{CookiesInfo, PartitionKey, SameSite};
 
 pub mod dao;
 pub mod entities;
 
+bitflags::bitflags! {
+    /// `schemeMap`, the schemes the cookie was set or sent over, unknown bits are kept
+    #[derive(Clone, Copy)]
+    #[derive(Debug)]
+    #[derive(Default)]
+    #[derive(PartialEq, Eq, PartialOrd, Ord)]
+    #[derive(Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct SchemeMap: i32 {
+        const HTTP = 1;
+        const HTTPS = 2;
+        const FILE = 4;
+    }
+}
+
 #[derive(Clone)]
 #[derive(Debug)]
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
@@ -26,23 +41,25 @@ pub struct MozCookie {
     pub creation_time: Option<DateTime<Utc>>,
     pub is_secure: bool,
     pub is_http_only: bool,
-    pub in_browser_element: i32,
+    pub in_browser_element: bool,
     pub same_site: SameSite,
-    // pub raw_same_site: i32,
-    pub scheme_map: i32,
+    /// `SameSite` as the site sent it, `None` for databases without `rawSameSite`
+    pub raw_same_site: Option<i32>,
+    pub scheme_map: SchemeMap,
+    /// Set with the `Partitioned` attribute, `None` for databases without `isPartitionedAttributeSet`
+    pub is_partitioned_attribute_set: Option<bool>,
+    /// The CHIPS partition from the `partitionKey` of `origin_attributes`, `None` for an unpartitioned cookie
+    pub partition_key: Option<PartitionKey>,
 }
 
+/// Add the unpartitioned cookies to `jar`,
+/// see [`jar_extend`](crate::browser::cookies::jar_extend) for the partitioned ones
 #[cfg(feature = "reqwest")]
 pub fn jar_extend_firefox<'a, I>(jar: &reqwest::cookie::Jar, cks: I)
 where
     I: IntoIterator<Item = &'a MozCookie>,
 {
-    for cookie in cks {
-        let set_cookie = cookie.set_cookie_header();
-        if let Ok(url) = reqwest::Url::parse(&cookie.url()) {
-            jar.add_cookie_str(&set_cookie, &url);
-        }
-    }
+    crate::browser::cookies::jar_extend(jar, cks, None);
 }
 
 #[cfg(feature = "reqwest")]
@@ -96,10 +113,15 @@ impl CookiesInfo for MozCookie {
     fn expires(&self) -> Option<DateTime<Utc>> {
         self.expiry
     }
+
+    fn partition_key(&self) -> Option<&PartitionKey> {
+        self.partition_key.as_ref()
+    }
 }
 
 impl From<moz_cookies::Model> for MozCookie {
     fn from(value: moz_cookies::Model) -> Self {
+        let partition_key = PartitionKey::from_origin_attributes(&value.origin_attributes);
         Self {
             id: value.id,
             origin_attributes: value.origin_attributes,
@@ -127,14 +149,20 @@ impl From<moz_cookies::Model> for MozCookie {
                 .is_some_and(|v| v != 0),
             in_browser_element: value
                 .in_browser_element
-                .unwrap_or_default(),
-            same_site: value.same_site.into(),
-            // raw_same_site: value
-            //     .raw_same_site
-            //     .unwrap_or_default(),
-            scheme_map: value
-                .scheme_map
-                .unwrap_or_default(),
+                .is_some_and(|v| v != 0),
+            same_site: value
+                .same_site
+                .map_or(SameSite::Unspecified, SameSite::from_firefox),
+            raw_same_site: value.raw_same_site,
+            scheme_map: SchemeMap::from_bits_retain(
+                value
+                    .scheme_map
+                    .unwrap_or_default(),
+            ),
+            is_partitioned_attribute_set: value
+                .is_partitioned_attribute_set
+                .map(|v| v != 0),
+            partition_key,
         }
     }
 }
@@ -14,11 +14,14 @@ use snafu::{Location, ResultExt, Snafu};
 #[cfg(feature = "reqwest")]
 pub use self::items::cookie::jar_extend_firefox;
 pub use self::items::cookie::{
-    MozCookie,
+    MozCookie, SchemeMap,
     entities::moz_cookies::{Column as MozCookiesCol, ColumnIter as MozCookiesColIter},
 };
 use self::items::{I64ToMozTime, cookie::dao::CookiesQuery};
-use crate::browser::{FirefoxPath, cookies::LeetCodeCookies};
+use crate::browser::{
+    FirefoxPath,
+    cookies::{CookiesInfo, LeetCodeCookies},
+};
 
 #[derive(Debug)]
 #[derive(Snafu)]
@@ -152,6 +155,36 @@ pub trait GetCookies: SealedCookies + Display {
         Ok(res)
     }
 
+    /// Like [`GetCookies::cookies_filter`], only the cookies sent under `top_level_site`,
+    /// see [`CookiesInfo::in_partition`]
+    async fn cookies_filter_in(
+        &self,
+        filter: Condition,
+        top_level_site: Option<&str>,
+    ) -> Result<Vec<MozCookie>>
+    where
+        Self: Sync,
+    {
+        let mut cookies = self.cookies_filter(filter).await?;
+        cookies.retain(|v| v.in_partition(top_level_site));
+        Ok(cookies)
+    }
+
+    /// Like [`GetCookies::cookies_by_host`], only the cookies sent under `top_level_site`,
+    /// see [`CookiesInfo::in_partition`]
+    async fn cookies_by_host_in(
+        &self,
+        host: &str,
+        top_level_site: Option<&str>,
+    ) -> Result<Vec<MozCookie>>
+    where
+        Self: Sync,
+    {
+        let mut cookies = self.cookies_by_host(host).await?;
+        cookies.retain(|v| v.in_partition(top_level_site));
+        Ok(cookies)
+    }
+
     /// get session csrf for leetcode
     async fn get_session_csrf(&self, host: &str) -> Result<LeetCodeCookies>
     where
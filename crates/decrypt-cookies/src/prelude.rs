@@ -1,12 +1,20 @@
 #[cfg(any(feature = "chromium", feature = "firefox"))]
 pub use sea_orm::{prelude::ColumnTrait, sea_query::IntoCondition};
 
-pub use crate::browser::{cookies::LeetCodeCookies, *};
+pub use crate::browser::{
+    cookies::{LeetCodeCookies, PartitionKey},
+    *,
+};
 #[cfg(feature = "chromium")]
 pub use crate::chromium::{
-    ChromiumCookieCol, ChromiumCookieColIter, ChromiumCookieGetter, ChromiumGetter,
-    ChromiumLoginCol, ChromiumLoginColIter, ChromiumLoginGetter, builder::ChromiumBuilder,
+    AutofillCol, ChromiumCookieCol, ChromiumCookieColIter, ChromiumCookieGetter,
+    ChromiumCookieWriter, ChromiumGetter, ChromiumHistoryGetter, ChromiumIndexedDbGetter,
+    ChromiumLocalStorageGetter, ChromiumLoginCol, ChromiumLoginColIter, ChromiumLoginGetter,
+    ChromiumLoginImporter, ChromiumWebDataGetter, CreditCardCol, DownloadCol, HistoryUrlCol,
+    HistoryVisitCol, KeywordSearchTermCol, SecretString, SetCookie, builder::ChromiumBuilder,
 };
+#[cfg(feature = "key-cache")]
+pub use crate::chromium::{CacheSecret, KeyCache};
 #[cfg(feature = "firefox")]
 pub use crate::firefox::{
     FirefoxCookieGetter, FirefoxGetter, MozCookiesCol, MozCookiesColIter, builder::FirefoxBuilder,
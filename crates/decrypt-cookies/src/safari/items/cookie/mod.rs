@@ -6,7 +6,7 @@ use snafu::{OptionExt, ResultExt};
 
 use super::super::Result;
 use crate::{
-    browser::cookies::{CookiesInfo, LeetCodeCookies},
+    browser::cookies::{CookiesInfo, LeetCodeCookies, PartitionKey},
     prelude::cookies::SameSite,
     safari::{self, HomeSnafu},
 };
@@ -94,6 +94,11 @@ impl CookiesInfo for SafariCookie {
     fn expires(&self) -> Option<DateTime<Utc>> {
         self.expires
     }
+
+    /// `Cookies.binarycookies` has no partitions
+    fn partition_key(&self) -> Option<&PartitionKey> {
+        None
+    }
 }
 
 #[non_exhaustive]
@@ -1,3 +1,6 @@
+#[cfg(any(feature = "chromium", feature = "firefox"))]
+pub mod schema;
+
 #[cfg(any(feature = "chromium", feature = "firefox"))]
 use std::path::Path;
 
@@ -11,9 +14,25 @@ pub fn need_sep(path: &Path) -> bool {
 #[cfg(any(feature = "chromium", feature = "firefox"))]
 pub fn connect_db<P: AsRef<Path>>(
     path: &P,
+) -> impl std::future::Future<Output = Result<sea_orm::DatabaseConnection, sea_orm::DbErr>> {
+    connect_db_mode(path, "ro")
+}
+
+/// Open the database for writing, it must exist
+#[cfg(feature = "chromium")]
+pub fn connect_db_rw<P: AsRef<Path>>(
+    path: &P,
+) -> impl std::future::Future<Output = Result<sea_orm::DatabaseConnection, sea_orm::DbErr>> {
+    connect_db_mode(path, "rw")
+}
+
+#[cfg(any(feature = "chromium", feature = "firefox"))]
+fn connect_db_mode<P: AsRef<Path>>(
+    path: &P,
+    mode: &str,
 ) -> impl std::future::Future<Output = Result<sea_orm::DatabaseConnection, sea_orm::DbErr>> {
     use sea_orm::{ConnectOptions, Database};
-    let db_url = format!("sqlite:{}?mode=ro", path.as_ref().display());
+    let db_url = format!("sqlite:{}?mode={mode}", path.as_ref().display());
     let mut opt = ConnectOptions::new(db_url);
 
     // TODO: make sqlx opt configurable
@@ -0,0 +1,165 @@
+//! The columns a table has, they come and go between browser versions
+
+use sea_orm::{
+    ColumnTrait, ColumnType, ConnectionTrait, DbErr, EntityTrait, IdenStatic, Iterable,
+    QuerySelect, Select, Statement,
+    sea_query::{Expr, SimpleExpr},
+};
+
+type Result<T> = std::result::Result<T, DbErr>;
+
+/// A row of `PRAGMA table_info`
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub struct ColumnInfo {
+    pub name: String,
+    pub not_null: bool,
+    pub has_default: bool,
+    /// Part of the primary key
+    pub pk: bool,
+}
+
+/// The columns of `table`, empty when it doesn't exist
+pub async fn table_info<C: ConnectionTrait>(conn: &C, table: &str) -> Result<Vec<ColumnInfo>> {
+    conn.query_all(Statement::from_string(
+        conn.get_database_backend(),
+        format!("PRAGMA table_info({table})"),
+    ))
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(ColumnInfo {
+            name: row.try_get("", "name")?,
+            not_null: row.try_get::<i32>("", "notnull")? != 0,
+            has_default: row
+                .try_get::<Option<String>>("", "dflt_value")?
+                .is_some(),
+            pk: row.try_get::<i32>("", "pk")? != 0,
+        })
+    })
+    .collect()
+}
+
+/// `PRAGMA user_version`, the schema version of a Firefox database
+#[cfg(feature = "firefox")]
+pub async fn user_version<C: ConnectionTrait>(conn: &C) -> Result<i64> {
+    let row = conn
+        .query_one(Statement::from_string(
+            conn.get_database_backend(),
+            "PRAGMA user_version",
+        ))
+        .await?;
+    Ok(row
+        .and_then(|v| v.try_get_by_index(0).ok())
+        .unwrap_or_default())
+}
+
+/// `meta.version`, the schema version of a Chromium database, 0 when it is missing
+#[cfg(feature = "chromium")]
+pub async fn meta_version<C: ConnectionTrait>(conn: &C) -> Result<i64> {
+    let row = conn
+        .query_one(Statement::from_string(
+            conn.get_database_backend(),
+            "SELECT value FROM meta WHERE key = 'version'",
+        ))
+        .await?;
+    Ok(row
+        .and_then(|v| {
+            v.try_get_by_index::<String>(0)
+                .ok()
+        })
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_default())
+}
+
+/// Which columns of `E` the table has.
+///
+/// The entities list the columns of the newest schema known,
+/// [`Schema::find`] selects a default in place of the ones an older or newer browser lacks.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Schema<E: EntityTrait> {
+    missing: Vec<E::Column>,
+}
+
+impl<E: EntityTrait> Default for Schema<E> {
+    fn default() -> Self {
+        Self { missing: vec![] }
+    }
+}
+
+impl<E: EntityTrait> Schema<E> {
+    pub async fn read<C: ConnectionTrait>(conn: &C) -> Result<Self> {
+        let entity = E::default();
+        let columns = table_info(conn, entity.table_name()).await?;
+        let missing = E::Column::iter()
+            .filter(|v| {
+                !columns
+                    .iter()
+                    .any(|c| c.name == v.as_str())
+            })
+            .collect();
+        Ok(Self { missing })
+    }
+
+    /// Whether the table has `column`
+    pub fn has(&self, column: E::Column) -> bool {
+        !self
+            .missing
+            .iter()
+            .any(|v| v.as_str() == column.as_str())
+    }
+
+    /// Like `E::find()`, `NULL`, 0 or empty for the missing columns
+    pub fn find(&self) -> Select<E> {
+        if self.missing.is_empty() {
+            return E::find();
+        }
+        E::Column::iter().fold(E::find().select_only(), |select, column| {
+            if self.has(column) {
+                select.column(column)
+            }
+            else {
+                select.expr_as(default_of(column), column.as_str())
+            }
+        })
+    }
+}
+
+/// The value a missing column reads as
+fn default_of<C: ColumnTrait>(column: C) -> SimpleExpr {
+    let def = column.def();
+    if def.is_null() {
+        return Expr::cust("NULL");
+    }
+    match def.get_column_type() {
+        ColumnType::Char(_) | ColumnType::String(_) | ColumnType::Text => Expr::val("").into(),
+        ColumnType::Blob | ColumnType::Binary(_) | ColumnType::VarBinary(_) => Expr::cust("X''"),
+        _ => Expr::val(0).into(),
+    }
+}
+
+/// A database at `<temp dir>/decrypt-cookies-schema-test/<name>` made from `tests/schemas/<name>.sql`
+#[cfg(test)]
+pub async fn fixture(name: &str) -> std::path::PathBuf {
+    use sea_orm::Database;
+
+    let sql = std::fs::read_to_string(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("tests/schemas/{name}.sql")),
+    )
+    .unwrap();
+    let dir = std::env::temp_dir().join("decrypt-cookies-schema-test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    _ = std::fs::remove_file(&path);
+
+    let conn = Database::connect(format!("sqlite:{}?mode=rwc", path.display()))
+        .await
+        .unwrap();
+    conn.execute_unprepared(&sql)
+        .await
+        .unwrap();
+    conn.close().await.unwrap();
+    path
+}
@@ -29,7 +29,8 @@ async fn can_compile() {
                             .unwrap_or_default(),
                             first
                             .password_value
-                            .as_deref()
+                            .as_ref()
+                            .map(SecretString::expose_secret)
                             .unwrap_or_default()
                         );
                     },
@@ -35,7 +35,8 @@ async fn passwd_browsers() {
                                 .unwrap_or_default(),
                             first
                                 .password_value
-                                .as_deref()
+                                .as_ref()
+                                .map(SecretString::expose_secret)
                                 .unwrap_or_default()
                         );
                     },
@@ -36,7 +36,7 @@ async fn chromium_cookies_test() {
                             i.name,
                             i.expires_utc.unwrap(),
                             i.creation_utc.unwrap(),
-                            i.decrypted_value.as_ref().unwrap(),
+                            i.decrypted_value.as_ref().unwrap().expose_secret(),
                         );
                     },
                     None => println!("None ============= {}",$browser),
@@ -0,0 +1,26 @@
+-- `Cookies` before `last_update_utc`, with the since removed `is_same_party`
+CREATE TABLE meta (key LONGVARCHAR NOT NULL UNIQUE PRIMARY KEY, value LONGVARCHAR);
+INSERT INTO meta VALUES ('version', '18'), ('last_compatible_version', '18');
+CREATE TABLE cookies (
+    creation_utc INTEGER NOT NULL,
+    host_key TEXT NOT NULL,
+    top_frame_site_key TEXT NOT NULL,
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    encrypted_value BLOB NOT NULL,
+    path TEXT NOT NULL,
+    expires_utc INTEGER NOT NULL,
+    is_secure INTEGER NOT NULL,
+    is_httponly INTEGER NOT NULL,
+    last_access_utc INTEGER NOT NULL,
+    has_expires INTEGER NOT NULL,
+    is_persistent INTEGER NOT NULL,
+    priority INTEGER NOT NULL,
+    samesite INTEGER NOT NULL,
+    source_scheme INTEGER NOT NULL,
+    source_port INTEGER NOT NULL,
+    is_same_party INTEGER NOT NULL,
+    UNIQUE (host_key, top_frame_site_key, name, path)
+);
+INSERT INTO cookies VALUES
+    (13300000000000000, '.a.com', '', 'sid', 'v18', X'', '/', 0, 1, 1, 13300000000000000, 0, 0, 1, 0, 2, 443, 0);
@@ -0,0 +1,29 @@
+-- `Cookies` with `source_type` and `has_cross_site_ancestor`, and a column no entity knows
+CREATE TABLE meta (key LONGVARCHAR NOT NULL UNIQUE PRIMARY KEY, value LONGVARCHAR);
+INSERT INTO meta VALUES ('version', '24'), ('last_compatible_version', '24');
+CREATE TABLE cookies (
+    creation_utc INTEGER NOT NULL,
+    host_key TEXT NOT NULL,
+    top_frame_site_key TEXT NOT NULL,
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    encrypted_value BLOB NOT NULL,
+    path TEXT NOT NULL,
+    expires_utc INTEGER NOT NULL,
+    is_secure INTEGER NOT NULL,
+    is_httponly INTEGER NOT NULL,
+    last_access_utc INTEGER NOT NULL,
+    has_expires INTEGER NOT NULL,
+    is_persistent INTEGER NOT NULL,
+    priority INTEGER NOT NULL,
+    samesite INTEGER NOT NULL,
+    source_scheme INTEGER NOT NULL,
+    source_port INTEGER NOT NULL,
+    last_update_utc INTEGER NOT NULL,
+    source_type INTEGER NOT NULL,
+    has_cross_site_ancestor INTEGER NOT NULL,
+    browser_provenance INTEGER NOT NULL DEFAULT 0,
+    UNIQUE (host_key, top_frame_site_key, name, path)
+);
+INSERT INTO cookies VALUES
+    (13300000000000000, '.a.com', 'https://b.com', 'sid', 'v24', X'', '/', 0, 1, 1, 13300000000000000, 0, 0, 1, 0, 2, 443, 13300000000000000, 1, 1, 0);
@@ -0,0 +1,22 @@
+-- `moz_cookies` with `rawSameSite` and `schemeMap`
+PRAGMA user_version = 12;
+CREATE TABLE moz_cookies (
+    id INTEGER PRIMARY KEY,
+    originAttributes TEXT NOT NULL DEFAULT '',
+    name TEXT,
+    value TEXT,
+    host TEXT,
+    path TEXT,
+    expiry INTEGER,
+    lastAccessed INTEGER,
+    creationTime INTEGER,
+    isSecure INTEGER,
+    isHttpOnly INTEGER,
+    inBrowserElement INTEGER DEFAULT 0,
+    sameSite INTEGER DEFAULT 0,
+    rawSameSite INTEGER DEFAULT 0,
+    schemeMap INTEGER DEFAULT 0,
+    CONSTRAINT moz_uniqueid UNIQUE (name, host, path, originAttributes)
+);
+INSERT INTO moz_cookies VALUES
+    (1, '', 'sid', 'v12', '.a.com', '/', 1900000000, 1700000000000000, 1700000000000000, 1, 1, 0, 1, 1, 2);
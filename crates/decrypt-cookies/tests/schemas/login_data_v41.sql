@@ -0,0 +1,44 @@
+-- `Login Data` with the move blocking, password sharing and Keychain columns
+CREATE TABLE meta (key LONGVARCHAR NOT NULL UNIQUE PRIMARY KEY, value LONGVARCHAR);
+INSERT INTO meta VALUES ('version', '41'), ('last_compatible_version', '40');
+CREATE TABLE logins (
+    origin_url VARCHAR NOT NULL,
+    action_url VARCHAR,
+    username_element VARCHAR,
+    username_value VARCHAR,
+    password_element VARCHAR,
+    password_value BLOB,
+    submit_element VARCHAR,
+    signon_realm VARCHAR NOT NULL,
+    date_created INTEGER NOT NULL,
+    blacklisted_by_user INTEGER NOT NULL,
+    scheme INTEGER NOT NULL,
+    password_type INTEGER,
+    times_used INTEGER,
+    form_data BLOB,
+    display_name VARCHAR,
+    icon_url VARCHAR,
+    federation_url VARCHAR,
+    skip_zero_click INTEGER,
+    generation_upload_status INTEGER,
+    possible_username_pairs BLOB,
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    date_last_used INTEGER NOT NULL DEFAULT 0,
+    moving_blocked_for BLOB,
+    date_password_modified INTEGER NOT NULL DEFAULT 0,
+    sender_email VARCHAR,
+    sender_name VARCHAR,
+    date_received INTEGER,
+    sharing_notification_displayed INTEGER NOT NULL DEFAULT 0,
+    keychain_identifier BLOB,
+    sender_profile_image_url VARCHAR,
+    UNIQUE (origin_url, username_element, username_value, password_element, signon_realm)
+);
+INSERT INTO logins (
+    origin_url, username_value, signon_realm, date_created, blacklisted_by_user, scheme,
+    date_password_modified, sender_email, sender_name, date_received, sharing_notification_displayed
+)
+VALUES (
+    'https://a.com/', 'alice', 'https://a.com/', 13300000000000000, 0, 0,
+    13300000000000000, 'bob@b.com', 'Bob', 13300000000000000, 1
+);
@@ -0,0 +1,87 @@
+//! <https://github.com/google/leveldb/blob/main/util/coding.h>
+
+pub fn varint64(input: &mut &[u8]) -> Option<u64> {
+    let mut res = 0_u64;
+    for (i, &byte) in input.iter().enumerate().take(10) {
+        res |= u64::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            *input = &input[i + 1..];
+            return Some(res);
+        }
+    }
+    None
+}
+
+pub fn varint32(input: &mut &[u8]) -> Option<u32> {
+    varint64(input).and_then(|v| u32::try_from(v).ok())
+}
+
+pub fn length_prefixed<'a>(input: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = varint32(input)? as usize;
+    let (res, rest) = input.split_at_checked(len)?;
+    *input = rest;
+    Some(res)
+}
+
+pub fn fixed32(input: &[u8]) -> Option<u32> {
+    let bytes = input.first_chunk()?;
+    Some(u32::from_le_bytes(*bytes))
+}
+
+pub fn fixed64(input: &[u8]) -> Option<u64> {
+    let bytes = input.first_chunk()?;
+    Some(u64::from_le_bytes(*bytes))
+}
+
+const CRC32C_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0x82F6_3B78
+            }
+            else {
+                crc >> 1
+            };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Castagnoli crc of the parts, as if they were one slice
+pub fn crc32c(parts: &[&[u8]]) -> u32 {
+    let mut crc = !0_u32;
+    for part in parts {
+        for &byte in *part {
+            crc = CRC32C_TABLE[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8);
+        }
+    }
+    !crc
+}
+
+/// The stored crcs are masked, see `leveldb::crc32c::Unmask`
+pub const fn unmask_crc(masked: u32) -> u32 {
+    let rot = masked.wrapping_sub(0xA282_EAD8);
+    rot.rotate_left(15)
+}
+
+#[cfg(test)]
+pub const fn mask_crc(crc: u32) -> u32 {
+    crc.rotate_right(15)
+        .wrapping_add(0xA282_EAD8)
+}
+
+#[cfg(test)]
+pub fn put_varint(out: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        out.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
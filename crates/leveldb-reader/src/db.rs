@@ -0,0 +1,222 @@
+use std::{
+    collections::{BTreeMap, btree_map::Entry as MapEntry},
+    fs,
+    path::Path,
+};
+
+use snafu::ResultExt;
+
+use crate::{
+    Entry, EntryKind,
+    error::{IoSnafu, Result, TableSnafu},
+    log::read_log,
+    manifest::Manifest,
+    table::read_table,
+};
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+enum FileKind {
+    Log,
+    Table,
+}
+
+/// A snapshot of a LevelDB directory, the newest write of every key
+///
+/// Keys are iterated in bytewise order,
+/// which is not the order of databases with a custom comparator like IndexedDB.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+pub struct LevelDb {
+    comparator: Option<String>,
+    entries: BTreeMap<Vec<u8>, Entry>,
+}
+
+impl LevelDb {
+    /// Read every live file of the directory, nothing is written
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref();
+        let mut files = vec![];
+        for dir_entry in fs::read_dir(dir).with_context(|_| IoSnafu { path: dir.to_owned() })? {
+            let dir_entry = dir_entry.with_context(|_| IoSnafu { path: dir.to_owned() })?;
+            let name = dir_entry.file_name();
+            let Some((number, kind)) = name
+                .to_str()
+                .and_then(parse_file_name)
+            else {
+                continue;
+            };
+            files.push((number, kind, dir_entry.path()));
+        }
+
+        let manifest = read_manifest(dir);
+        // The copy of a running database may be a mix of two versions, then read them all
+        let manifest = manifest.filter(|m| {
+            m.tables.iter().all(|&(_, n)| {
+                files
+                    .iter()
+                    .any(|&(number, kind, _)| kind == FileKind::Table && number == n)
+            })
+        });
+        if let Some(m) = &manifest {
+            files.retain(|&(number, kind, _)| match kind {
+                FileKind::Log => m.has_log(number),
+                FileKind::Table => m.has_table(number),
+            });
+        }
+
+        let mut res = Self {
+            comparator: manifest.and_then(|m| m.comparator),
+            entries: BTreeMap::new(),
+        };
+        for (_, kind, path) in files {
+            let data = fs::read(&path).with_context(|_| IoSnafu { path: path.clone() })?;
+            let entries = match kind {
+                FileKind::Log => read_log(&data),
+                FileKind::Table => read_table(&data).context(TableSnafu { path })?,
+            };
+            res.extend(entries);
+        }
+        res.entries
+            .retain(|_, v| v.kind == EntryKind::Value);
+
+        Ok(res)
+    }
+
+    /// Merge writes, the newest of every key wins
+    fn extend<I: IntoIterator<Item = Entry>>(&mut self, entries: I) {
+        for entry in entries {
+            match self
+                .entries
+                .entry(entry.key.clone())
+            {
+                MapEntry::Vacant(v) => {
+                    v.insert(entry);
+                },
+                MapEntry::Occupied(mut v) => {
+                    if v.get().seq < entry.seq {
+                        v.insert(entry);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Name of the comparator from the `MANIFEST`, like `leveldb.BytewiseComparator` or `idb_cmp1`
+    pub fn comparator(&self) -> Option<&str> {
+        self.comparator.as_deref()
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.entries
+            .get(key)
+            .map(|v| v.value.as_slice())
+    }
+
+    /// The entry of a key, with its sequence number
+    pub fn entry(&self, key: &[u8]) -> Option<&Entry> {
+        self.entries.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All keys and values
+    pub fn entries(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        self.entries
+            .iter()
+            .map(|(k, v)| (k.as_slice(), v.value.as_slice()))
+    }
+
+    /// Keys starting with `prefix`
+    pub fn prefix<'a>(&'a self, prefix: &'a [u8]) -> impl Iterator<Item = (&'a [u8], &'a [u8])> {
+        self.entries
+            .range(prefix.to_vec()..)
+            .take_while(move |(k, _)| k.starts_with(prefix))
+            .map(|(k, v)| (k.as_slice(), v.value.as_slice()))
+    }
+}
+
+/// `000005.log`, `000007.ldb` and the older `000007.sst`
+fn parse_file_name(name: &str) -> Option<(u64, FileKind)> {
+    let (number, ext) = name.split_once('.')?;
+    let kind = match ext {
+        "log" => FileKind::Log,
+        "ldb" | "sst" => FileKind::Table,
+        _ => return None,
+    };
+    Some((number.parse().ok()?, kind))
+}
+
+/// `CURRENT` names the `MANIFEST`
+fn read_manifest(dir: &Path) -> Option<Manifest> {
+    let current = fs::read_to_string(dir.join("CURRENT")).ok()?;
+    let data = fs::read(dir.join(current.trim())).ok()?;
+    Manifest::parse(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::{
+        coding::put_varint,
+        log::tests::{build_log, push_record},
+        table::tests::build_table,
+    };
+
+    #[test]
+    fn newest_wins() {
+        let dir = env::temp_dir().join(format!("leveldb-reader-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let table = build_table(&[&[
+            (b"a", 1, Some(b"old")),
+            (b"b", 2, Some(b"deleted later")),
+            (b"c", 3, Some(b"kept")),
+        ]]);
+        fs::write(dir.join("000005.ldb"), table).unwrap();
+        let log = build_log(&[(10, &[(b"a", Some(b"new")), (b"b", None)])]);
+        fs::write(dir.join("000006.log"), log).unwrap();
+        fs::write(dir.join("LOCK"), b"").unwrap();
+        // compacted into 000005.ldb but not deleted yet
+        let obsolete = build_table(&[&[(b"z", 1, Some(b"obsolete"))]]);
+        fs::write(dir.join("000004.ldb"), obsolete).unwrap();
+        fs::write(
+            dir.join("000003.log"),
+            build_log(&[(1, &[(b"y", Some(b"obsolete"))])]),
+        )
+        .unwrap();
+
+        let mut edit = vec![];
+        edit.push(1);
+        put_varint(&mut edit, 8);
+        edit.extend_from_slice(b"idb_cmp1");
+        edit.extend_from_slice(&[7, 0, 4, 10, 1, b'z', 1, b'z']);
+        edit.extend_from_slice(&[7, 0, 5, 10, 1, b'a', 1, b'c']);
+        edit.extend_from_slice(&[6, 0, 4, 2, 6]);
+        let mut manifest = vec![];
+        push_record(&mut manifest, &edit);
+        fs::write(dir.join("MANIFEST-000002"), manifest).unwrap();
+        fs::write(dir.join("CURRENT"), "MANIFEST-000002\n").unwrap();
+
+        let db = LevelDb::open(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(db.len(), 2);
+        assert_eq!(db.get(b"a"), Some(&b"new"[..]));
+        assert_eq!(db.get(b"b"), None);
+        assert_eq!(db.entry(b"c").unwrap().seq, 3);
+        assert_eq!(db.prefix(b"c").count(), 1);
+        assert_eq!(db.comparator(), Some("idb_cmp1"));
+    }
+}
@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use snafu::{Location, Snafu};
+
+#[derive(Debug)]
+#[derive(Snafu)]
+#[snafu(visibility(pub))]
+#[non_exhaustive]
+pub enum LevelDbError {
+    #[snafu(display("{source}, path: {}\n@:{location}", path.display()))]
+    Io {
+        source: std::io::Error,
+        path: PathBuf,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{source}, path: {}\n@:{location}", path.display()))]
+    Table {
+        source: TableError,
+        path: PathBuf,
+        #[snafu(implicit)]
+        location: Location,
+    },
+}
+
+#[derive(Debug)]
+#[derive(Snafu)]
+#[snafu(visibility(pub))]
+#[non_exhaustive]
+pub enum TableError {
+    #[snafu(display("Corrupted table: {reason}\n@:{location}"))]
+    Corrupted {
+        reason: &'static str,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("Unsupported block compression: {compression}\n@:{location}"))]
+    Compression {
+        compression: u8,
+        #[snafu(implicit)]
+        location: Location,
+    },
+    #[snafu(display("{source}\n@:{location}"))]
+    Snappy {
+        source: snap::Error,
+        #[snafu(implicit)]
+        location: Location,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, LevelDbError>;
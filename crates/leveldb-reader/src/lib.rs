@@ -0,0 +1,44 @@
+#![doc = include_str!("../README.md")]
+
+mod coding;
+mod db;
+pub mod error;
+pub mod log;
+mod manifest;
+pub mod table;
+
+pub use db::LevelDb;
+pub use error::{LevelDbError, Result};
+
+/// What a write did to a key
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EntryKind {
+    Deletion,
+    Value,
+}
+
+impl EntryKind {
+    const fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(Self::Deletion),
+            1 => Some(Self::Value),
+            _ => None,
+        }
+    }
+}
+
+/// A single write, from a log or a table
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Entry {
+    pub key: Vec<u8>,
+    /// Bigger is newer
+    pub seq: u64,
+    pub kind: EntryKind,
+    /// Empty for [`EntryKind::Deletion`]
+    pub value: Vec<u8>,
+}
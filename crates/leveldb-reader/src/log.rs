@@ -0,0 +1,197 @@
+//! Write ahead log, the `*.log` files and the `MANIFEST`
+//!
+//! <https://github.com/google/leveldb/blob/main/doc/log_format.md>
+
+use crate::{
+    Entry, EntryKind,
+    coding::{crc32c, fixed32, fixed64, length_prefixed, unmask_crc},
+};
+
+const BLOCK_SIZE: usize = 32 * 1024;
+/// checksum (4), length (2), type (1)
+const HEADER_SIZE: usize = 7;
+
+const FULL: u8 = 1;
+const FIRST: u8 = 2;
+const MIDDLE: u8 = 3;
+const LAST: u8 = 4;
+
+/// The logical records of a log file.
+///
+/// Fragments with a bad checksum are dropped, like a torn write at the end of the file.
+pub fn records(data: &[u8]) -> Vec<Vec<u8>> {
+    let mut res = vec![];
+    let mut pending: Option<Vec<u8>> = None;
+
+    for mut block in data.chunks(BLOCK_SIZE) {
+        while block.len() >= HEADER_SIZE {
+            let (header, rest) = block.split_at(HEADER_SIZE);
+            let &[.., len_lo, len_hi, kind] = header
+            else {
+                break;
+            };
+            let len = usize::from(u16::from_le_bytes([len_lo, len_hi]));
+            // zero filled by preallocation
+            if kind == 0 && len == 0 {
+                break;
+            }
+            let Some((payload, rest)) = rest.split_at_checked(len)
+            else {
+                pending = None;
+                break;
+            };
+            block = rest;
+
+            let expected = fixed32(header).map(unmask_crc);
+            if expected != Some(crc32c(&[&[kind], payload])) {
+                pending = None;
+                break;
+            }
+
+            match kind {
+                FULL => {
+                    pending = None;
+                    res.push(payload.to_vec());
+                },
+                FIRST => pending = Some(payload.to_vec()),
+                MIDDLE => {
+                    if let Some(p) = &mut pending {
+                        p.extend_from_slice(payload);
+                    }
+                },
+                LAST => {
+                    if let Some(mut p) = pending.take() {
+                        p.extend_from_slice(payload);
+                        res.push(p);
+                    }
+                },
+                _ => pending = None,
+            }
+        }
+    }
+
+    res
+}
+
+/// The writes of a `*.log` file, in order.
+///
+/// A write batch is a sequence number (8), a count (4) and the records.
+pub fn read_log(data: &[u8]) -> Vec<Entry> {
+    let mut res = vec![];
+    for batch in records(data) {
+        let (Some(seq), Some(count)) = (fixed64(&batch), batch.get(8..).and_then(fixed32))
+        else {
+            continue;
+        };
+        let mut input = &batch[12..];
+        for seq in (seq..).take(count as usize) {
+            let Some((&tag, rest)) = input.split_first()
+            else {
+                break;
+            };
+            input = rest;
+            let Some(kind) = EntryKind::from_tag(tag)
+            else {
+                break;
+            };
+            let Some(key) = length_prefixed(&mut input)
+            else {
+                break;
+            };
+            let value = match kind {
+                EntryKind::Value => match length_prefixed(&mut input) {
+                    Some(v) => v.to_vec(),
+                    None => break,
+                },
+                EntryKind::Deletion => vec![],
+            };
+            res.push(Entry { key: key.to_vec(), seq, kind, value });
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::coding::{mask_crc, put_varint};
+
+    /// key, `None` for a deletion
+    pub type Write<'a> = (&'a [u8], Option<&'a [u8]>);
+
+    /// A log with one write batch per item
+    pub fn build_log(batches: &[(u64, &[Write<'_>])]) -> Vec<u8> {
+        let mut out = vec![];
+        for (seq, writes) in batches {
+            let mut batch = seq.to_le_bytes().to_vec();
+            batch.extend_from_slice(&(writes.len() as u32).to_le_bytes());
+            for (key, value) in *writes {
+                batch.push(u8::from(value.is_some()));
+                put_varint(&mut batch, key.len() as u64);
+                batch.extend_from_slice(key);
+                if let Some(value) = value {
+                    put_varint(&mut batch, value.len() as u64);
+                    batch.extend_from_slice(value);
+                }
+            }
+            push_record(&mut out, &batch);
+        }
+        out
+    }
+
+    pub fn push_record(out: &mut Vec<u8>, mut data: &[u8]) {
+        let mut first = true;
+        loop {
+            let left = BLOCK_SIZE - out.len() % BLOCK_SIZE;
+            if left < HEADER_SIZE {
+                out.resize(out.len() + left, 0);
+                continue;
+            }
+            let avail = left - HEADER_SIZE;
+            let (frag, rest) = data.split_at(avail.min(data.len()));
+            let kind = match (first, rest.is_empty()) {
+                (true, true) => FULL,
+                (true, false) => FIRST,
+                (false, false) => MIDDLE,
+                (false, true) => LAST,
+            };
+            out.extend_from_slice(&mask_crc(crc32c(&[&[kind], frag])).to_le_bytes());
+            out.extend_from_slice(&(frag.len() as u16).to_le_bytes());
+            out.push(kind);
+            out.extend_from_slice(frag);
+            data = rest;
+            first = false;
+            if data.is_empty() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn fragments_and_torn_tail() {
+        let big = vec![b'x'; BLOCK_SIZE + 100];
+        let mut log = build_log(&[
+            (1, &[(b"a", Some(b"1")), (b"b", Some(&big))]),
+            (3, &[(b"a", None)]),
+        ]);
+        let entries = read_log(&log);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].key, b"b");
+        assert_eq!(entries[1].seq, 2);
+        assert_eq!(entries[1].value.len(), big.len());
+        assert_eq!(entries[2].kind, EntryKind::Deletion);
+        assert_eq!(entries[2].seq, 3);
+
+        // a torn write only loses the last batch
+        let len = log.len();
+        log[len - 1] ^= 0xFF;
+        assert_eq!(read_log(&log).len(), 2);
+    }
+
+    #[test]
+    fn crc() {
+        assert_eq!(crc32c(&[b"123456789"]), 0xE306_9283);
+        assert_eq!(crc32c(&[b"1234", b"56789"]), 0xE306_9283);
+        assert_eq!(unmask_crc(mask_crc(0xE306_9283)), 0xE306_9283);
+    }
+}
@@ -0,0 +1,89 @@
+//! The live files, replayed from the version edits of the `MANIFEST`
+//!
+//! <https://github.com/google/leveldb/blob/main/db/version_edit.cc>
+
+use std::collections::HashSet;
+
+use crate::{
+    coding::{length_prefixed, varint32, varint64},
+    log,
+};
+
+const COMPARATOR: u32 = 1;
+const LOG_NUMBER: u32 = 2;
+const NEXT_FILE_NUMBER: u32 = 3;
+const LAST_SEQUENCE: u32 = 4;
+const COMPACT_POINTER: u32 = 5;
+const DELETED_FILE: u32 = 6;
+const NEW_FILE: u32 = 7;
+const PREV_LOG_NUMBER: u32 = 9;
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct Manifest {
+    pub comparator: Option<String>,
+    /// Older logs are already in tables
+    pub log_number: u64,
+    pub prev_log_number: u64,
+    /// (level, file number)
+    pub tables: HashSet<(u32, u64)>,
+}
+
+impl Manifest {
+    /// `None` when any edit can't be decoded
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut res = Self::default();
+        for edit in log::records(data) {
+            res.apply(&edit)?;
+        }
+        Some(res)
+    }
+
+    fn apply(&mut self, mut input: &[u8]) -> Option<()> {
+        let input = &mut input;
+        while !input.is_empty() {
+            match varint32(input)? {
+                COMPARATOR => {
+                    let name = length_prefixed(input)?;
+                    self.comparator = Some(String::from_utf8_lossy(name).into_owned());
+                },
+                LOG_NUMBER => self.log_number = varint64(input)?,
+                PREV_LOG_NUMBER => self.prev_log_number = varint64(input)?,
+                NEXT_FILE_NUMBER | LAST_SEQUENCE => {
+                    varint64(input)?;
+                },
+                COMPACT_POINTER => {
+                    varint32(input)?;
+                    length_prefixed(input)?;
+                },
+                DELETED_FILE => {
+                    let level = varint32(input)?;
+                    let number = varint64(input)?;
+                    self.tables
+                        .remove(&(level, number));
+                },
+                NEW_FILE => {
+                    let level = varint32(input)?;
+                    let number = varint64(input)?;
+                    let _size = varint64(input)?;
+                    let _smallest = length_prefixed(input)?;
+                    let _largest = length_prefixed(input)?;
+                    self.tables.insert((level, number));
+                },
+                _ => return None,
+            }
+        }
+        Some(())
+    }
+
+    pub fn has_table(&self, number: u64) -> bool {
+        self.tables
+            .iter()
+            .any(|&(_, n)| n == number)
+    }
+
+    pub const fn has_log(&self, number: u64) -> bool {
+        number >= self.log_number || (self.prev_log_number != 0 && number == self.prev_log_number)
+    }
+}
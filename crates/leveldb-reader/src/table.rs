@@ -0,0 +1,250 @@
+//! Sorted string table, the `*.ldb` and `*.sst` files
+//!
+//! <https://github.com/google/leveldb/blob/main/doc/table_format.md>
+
+use snafu::{OptionExt, ResultExt, ensure};
+
+use crate::{
+    Entry, EntryKind,
+    coding::{crc32c, fixed32, fixed64, unmask_crc, varint32, varint64},
+    error::{CompressionSnafu, CorruptedSnafu, SnappySnafu, TableError},
+};
+
+type Result<T> = std::result::Result<T, TableError>;
+
+const FOOTER_SIZE: usize = 48;
+const MAGIC: u64 = 0xDB47_7524_8B80_FB57;
+/// compression type (1), crc (4)
+const BLOCK_TRAILER_SIZE: usize = 5;
+
+const NO_COMPRESSION: u8 = 0;
+const SNAPPY_COMPRESSION: u8 = 1;
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+struct BlockHandle {
+    offset: usize,
+    size: usize,
+}
+
+impl BlockHandle {
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let offset = varint64(input)?.try_into().ok()?;
+        let size = varint64(input)?.try_into().ok()?;
+        Some(Self { offset, size })
+    }
+}
+
+/// All the writes of a table, sorted by the comparator of the database
+pub fn read_table(data: &[u8]) -> Result<Vec<Entry>> {
+    ensure!(
+        data.len() >= FOOTER_SIZE,
+        CorruptedSnafu { reason: "file too short" }
+    );
+    let footer = &data[data.len() - FOOTER_SIZE..];
+    ensure!(
+        fixed64(&footer[FOOTER_SIZE - 8..]) == Some(MAGIC),
+        CorruptedSnafu { reason: "bad magic" }
+    );
+
+    let mut input = footer;
+    let _metaindex =
+        BlockHandle::decode(&mut input).context(CorruptedSnafu { reason: "bad footer" })?;
+    let index = BlockHandle::decode(&mut input).context(CorruptedSnafu { reason: "bad footer" })?;
+
+    let mut res = vec![];
+    for (_, handle) in block_entries(&read_block(data, index)?)? {
+        let handle = BlockHandle::decode(&mut handle.as_slice())
+            .context(CorruptedSnafu { reason: "bad index entry" })?;
+        for (internal_key, value) in block_entries(&read_block(data, handle)?)? {
+            let split = internal_key
+                .len()
+                .checked_sub(8)
+                .context(CorruptedSnafu { reason: "bad internal key" })?;
+            let tag = fixed64(&internal_key[split..]).unwrap_or_default();
+            let kind = EntryKind::from_tag((tag & 0xFF) as u8)
+                .context(CorruptedSnafu { reason: "bad value type" })?;
+            let mut key = internal_key;
+            key.truncate(split);
+            res.push(Entry { key, seq: tag >> 8, kind, value });
+        }
+    }
+
+    Ok(res)
+}
+
+fn read_block(data: &[u8], handle: BlockHandle) -> Result<Vec<u8>> {
+    let block = handle
+        .offset
+        .checked_add(handle.size)
+        .and_then(|end| end.checked_add(BLOCK_TRAILER_SIZE))
+        .and_then(|end| data.get(handle.offset..end))
+        .context(CorruptedSnafu { reason: "block out of range" })?;
+    let (contents, trailer) = block.split_at(handle.size);
+    let compression = trailer[0];
+    ensure!(
+        fixed32(&trailer[1..]).map(unmask_crc) == Some(crc32c(&[contents, &[compression]])),
+        CorruptedSnafu { reason: "block checksum mismatch" }
+    );
+
+    match compression {
+        NO_COMPRESSION => Ok(contents.to_vec()),
+        SNAPPY_COMPRESSION => snap::raw::Decoder::new()
+            .decompress_vec(contents)
+            .context(SnappySnafu),
+        compression => CompressionSnafu { compression }.fail(),
+    }
+}
+
+/// Keys are prefix compressed against the previous one,
+/// the restart points at the end are only needed for seeking
+fn block_entries(block: &[u8]) -> Result<Vec<(Vec<u8>, Vec<u8>)>> {
+    let num_restarts = block
+        .len()
+        .checked_sub(4)
+        .and_then(|at| fixed32(&block[at..]))
+        .context(CorruptedSnafu { reason: "block too short" })? as usize;
+    let end = num_restarts
+        .checked_mul(4)
+        .and_then(|v| block.len().checked_sub(v + 4))
+        .context(CorruptedSnafu { reason: "bad restart array" })?;
+
+    let mut input = &block[..end];
+    let mut res: Vec<(Vec<u8>, Vec<u8>)> = vec![];
+    while !input.is_empty() {
+        let (shared, non_shared, value_len) =
+            entry_header(&mut input).context(CorruptedSnafu { reason: "bad block entry" })?;
+
+        let prev = res
+            .last()
+            .map_or(&[][..], |(k, _)| k.as_slice());
+        ensure!(
+            shared <= prev.len(),
+            CorruptedSnafu { reason: "bad shared key length" }
+        );
+        let (delta, rest) = input
+            .split_at_checked(non_shared)
+            .context(CorruptedSnafu { reason: "bad block entry" })?;
+        let (value, rest) = rest
+            .split_at_checked(value_len)
+            .context(CorruptedSnafu { reason: "bad block entry" })?;
+        input = rest;
+
+        let mut key = prev[..shared].to_vec();
+        key.extend_from_slice(delta);
+        res.push((key, value.to_vec()));
+    }
+    Ok(res)
+}
+
+/// shared key length, unshared key length, value length
+fn entry_header(input: &mut &[u8]) -> Option<(usize, usize, usize)> {
+    Some((
+        varint32(input)? as usize,
+        varint32(input)? as usize,
+        varint32(input)? as usize,
+    ))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::coding::{mask_crc, put_varint};
+
+    fn build_block(entries: &[(Vec<u8>, Vec<u8>)]) -> Vec<u8> {
+        let mut out = vec![];
+        let mut prev: &[u8] = &[];
+        for (key, value) in entries {
+            let shared = prev
+                .iter()
+                .zip(key)
+                .take_while(|(a, b)| a == b)
+                .count();
+            put_varint(&mut out, shared as u64);
+            put_varint(&mut out, (key.len() - shared) as u64);
+            put_varint(&mut out, value.len() as u64);
+            out.extend_from_slice(&key[shared..]);
+            out.extend_from_slice(value);
+            prev = key;
+        }
+        out.extend_from_slice(&0_u32.to_le_bytes());
+        out.extend_from_slice(&1_u32.to_le_bytes());
+        out
+    }
+
+    fn push_block(out: &mut Vec<u8>, block: &[u8], snappy: bool) -> Vec<u8> {
+        let (contents, compression) = if snappy {
+            (
+                snap::raw::Encoder::new()
+                    .compress_vec(block)
+                    .unwrap(),
+                SNAPPY_COMPRESSION,
+            )
+        }
+        else {
+            (block.to_vec(), NO_COMPRESSION)
+        };
+        let mut handle = vec![];
+        put_varint(&mut handle, out.len() as u64);
+        put_varint(&mut handle, contents.len() as u64);
+        out.extend_from_slice(&contents);
+        out.push(compression);
+        out.extend_from_slice(&mask_crc(crc32c(&[&contents, &[compression]])).to_le_bytes());
+        handle
+    }
+
+    /// key, sequence number, `None` for a deletion
+    pub type Write<'a> = (&'a [u8], u64, Option<&'a [u8]>);
+
+    /// A table with one data block per item, keys must be sorted
+    pub fn build_table(blocks: &[&[Write<'_>]]) -> Vec<u8> {
+        let mut out = vec![];
+        let mut index = vec![];
+        for (i, entries) in blocks.iter().enumerate() {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|(key, seq, value)| {
+                    let mut internal = key.to_vec();
+                    internal.extend_from_slice(
+                        &((seq << 8) | u64::from(value.is_some())).to_le_bytes(),
+                    );
+                    (internal, value.unwrap_or_default().to_vec())
+                })
+                .collect();
+            let handle = push_block(&mut out, &build_block(&entries), i % 2 == 0);
+            index.push((entries.last().unwrap().0.clone(), handle));
+        }
+        let metaindex = push_block(&mut out, &build_block(&[]), false);
+        let index = push_block(&mut out, &build_block(&index), false);
+
+        let mut footer = metaindex;
+        footer.extend_from_slice(&index);
+        footer.resize(FOOTER_SIZE - 8, 0);
+        footer.extend_from_slice(&MAGIC.to_le_bytes());
+        out.extend_from_slice(&footer);
+        out
+    }
+
+    #[test]
+    fn blocks() {
+        let table = build_table(&[
+            &[
+                (b"_https://a.com\x00\x01k1", 5, Some(b"\x01v1")),
+                (b"_https://a.com\x00\x01k2", 6, None),
+            ],
+            &[(b"_https://b.com\x00\x01k", 7, Some(b"\x01v"))],
+        ]);
+        let entries = read_table(&table).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].key, b"_https://a.com\x00\x01k2");
+        assert_eq!(entries[1].kind, EntryKind::Deletion);
+        assert_eq!(entries[2].seq, 7);
+        assert_eq!(entries[2].value, b"\x01v");
+
+        let mut broken = table;
+        broken[3] ^= 0xFF;
+        assert!(read_table(&broken).is_err());
+        assert!(read_table(b"short").is_err());
+        assert!(read_block(&[0; 16], BlockHandle { offset: 0, size: usize::MAX }).is_err());
+    }
+}
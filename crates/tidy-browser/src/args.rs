@@ -39,6 +39,33 @@ pub struct Args {
     #[arg(long, default_value(Format::Csv))]
     /// Out format
     pub out_format: Format,
+
+    #[arg(long)]
+    /// Export the full card numbers and IBANs of `web-data`, they are masked by default
+    pub unmasked: bool,
+
+    #[arg(long, requires("key_cache_passphrase_env"))]
+    /// Cache the Chromium keys on disk (encrypted), so later runs skip the keyring
+    pub key_cache: bool,
+
+    #[arg(
+        long,
+        default_value_t = 86400,
+        value_name("SECS"),
+        requires("key_cache")
+    )]
+    /// How long a cached key stays valid
+    pub key_cache_ttl: u64,
+
+    #[arg(
+        long,
+        value_name("NAME"),
+        requires("key_cache"),
+        value_hint(ValueHint::Other)
+    )]
+    /// Read the key cache passphrase from the environment variable NAME,
+    /// required by `--key-cache`
+    pub key_cache_passphrase_env: Option<String>,
 }
 
 #[derive(Clone, Copy)]
@@ -104,6 +131,8 @@ pub enum Value {
     #[default]
     Cookie,
     Login,
+    /// Chromium only, autofill entries, addresses, credit cards and IBANs
+    WebData,
 }
 
 #[derive(Clone)]
@@ -1,12 +1,21 @@
-use std::{collections::HashSet, fmt::Display, fs::File, io::IoSlice, path::PathBuf};
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    fs::File,
+    io::{ErrorKind, IoSlice},
+    path::{Path, PathBuf},
+};
 
 use decrypt_cookies::{
-    chromium::{GetCookies, GetLogins, builder::ChromiumBuilderError},
+    chromium::{
+        Address, ArtifactStatus, AutofillEntry, CreditCard, CreditCardCol, GetCookies, GetLogins,
+        GetWebData, Iban, MaskedCreditCard, MaskedIban, builder::ChromiumBuilderError,
+    },
     prelude::*,
 };
 use snafu::ResultExt;
 use strum::IntoEnumIterator;
-use tokio::task;
+use tokio::task::{self, JoinHandle};
 
 use crate::{
     args::{ChromiumName, Format, Value},
@@ -21,16 +30,236 @@ use crate::{
 pub struct ChromiumBased;
 
 fn login_csv_header<D: Display>(sep: D) -> String {
-    format!("url{sep}username{sep}display_name{sep}password{sep}date_created{sep}date_last_used{sep}modified")
+    format!("url{sep}username{sep}display_name{sep}password{sep}date_created{sep}date_last_used{sep}modified{sep}store")
+}
+
+fn autofill_csv_header<D: Display>(sep: D) -> String {
+    format!("name{sep}value{sep}date_created{sep}date_last_used{sep}count")
+}
+
+fn address_csv_header<D: Display>(sep: D) -> String {
+    format!(
+        "name{sep}company{sep}street_address{sep}city{sep}state{sep}zip{sep}country{sep}phone{sep}email{sep}use_count{sep}use_date"
+    )
+}
+
+fn credit_card_csv_header<D: Display>(sep: D) -> String {
+    format!(
+        "name_on_card{sep}card_number{sep}expiration{sep}nickname{sep}use_count{sep}use_date{sep}date_modified"
+    )
+}
+
+fn iban_csv_header<D: Display>(sep: D) -> String {
+    format!("value{sep}nickname{sep}use_count{sep}use_date")
+}
+
+/// Everything read from `Web Data`
+struct WebData {
+    autofill: Vec<AutofillEntry>,
+    addresses: Vec<Address>,
+    credit_cards: Vec<CreditCard>,
+    ibans: Vec<Iban>,
 }
 
 impl ChromiumBased {
+    /// `None` when the profile has no `Web Data`.
+    ///
+    /// With `host` only the cards saved on it are read, the rest of `Web Data` has no host.
+    async fn web_data<B>(
+        builder: ChromiumBuilder<B>,
+        host: Option<String>,
+    ) -> Result<Option<WebData>>
+    where
+        B: ChromiumPath + Send + Sync,
+    {
+        let getter = match builder.build_web_data().await {
+            Ok(getter) => getter,
+            Err(ChromiumBuilderError::Io { source, .. })
+                if source.kind() == ErrorKind::NotFound =>
+            {
+                let value = Value::WebData;
+                tracing::info!(browser = B::NAME, ?value, "not found in the profile, skip");
+                return Ok(None);
+            },
+            Err(e) => return Err(Box::new(e)).context(error::ChromiumBuilderSnafu),
+        };
+
+        if let Some(host) = host {
+            let credit_cards = getter
+                .credit_cards_filter(
+                    CreditCardCol::Origin
+                        .contains(host)
+                        .into_condition(),
+                )
+                .await
+                .map_err(Box::new)
+                .context(error::ChromiumSnafu)?;
+            return Ok(Some(WebData {
+                autofill: Vec::new(),
+                addresses: Vec::new(),
+                credit_cards,
+                ibans: Vec::new(),
+            }));
+        }
+
+        let (autofill, addresses, credit_cards, ibans) = tokio::join!(
+            getter.autofill_all(),
+            getter.addresses_all(),
+            getter.credit_cards_all(),
+            getter.ibans_all(),
+        );
+        let web_data = WebData {
+            autofill: autofill
+                .map_err(Box::new)
+                .context(error::ChromiumSnafu)?,
+            addresses: addresses
+                .map_err(Box::new)
+                .context(error::ChromiumSnafu)?,
+            credit_cards: credit_cards
+                .map_err(Box::new)
+                .context(error::ChromiumSnafu)?,
+            ibans: ibans
+                .map_err(Box::new)
+                .context(error::ChromiumSnafu)?,
+        };
+        Ok(Some(web_data))
+    }
+
+    /// Card numbers and IBANs are masked unless `unmasked`
+    fn write_web_data<S>(
+        output_dir: &Path,
+        web_data: WebData,
+        sep: S,
+        format: Format,
+        unmasked: bool,
+    ) -> [JoinHandle<Result<()>>; 4]
+    where
+        S: Display + Send + Clone + 'static,
+    {
+        let file = |csv, json, jsonl| {
+            output_dir.join(match format {
+                Format::Csv => csv,
+                Format::Json => json,
+                Format::JsonLines => jsonl,
+            })
+        };
+
+        [
+            utils::write_records(
+                file(
+                    crate::AUTOFILL_FILE_CSV,
+                    crate::AUTOFILL_FILE_JSON,
+                    crate::AUTOFILL_FILE_JSONL,
+                ),
+                web_data.autofill,
+                autofill_csv_header(sep.clone()),
+                AutofillEntry::to_csv,
+                sep.clone(),
+                format,
+            ),
+            utils::write_records(
+                file(
+                    crate::ADDRESSES_FILE_CSV,
+                    crate::ADDRESSES_FILE_JSON,
+                    crate::ADDRESSES_FILE_JSONL,
+                ),
+                web_data.addresses,
+                address_csv_header(sep.clone()),
+                Address::to_csv,
+                sep.clone(),
+                format,
+            ),
+            Self::write_cards(
+                file(
+                    crate::CREDIT_CARDS_FILE_CSV,
+                    crate::CREDIT_CARDS_FILE_JSON,
+                    crate::CREDIT_CARDS_FILE_JSONL,
+                ),
+                web_data.credit_cards,
+                sep.clone(),
+                format,
+                unmasked,
+            ),
+            Self::write_ibans(
+                file(
+                    crate::IBANS_FILE_CSV,
+                    crate::IBANS_FILE_JSON,
+                    crate::IBANS_FILE_JSONL,
+                ),
+                web_data.ibans,
+                sep,
+                format,
+                unmasked,
+            ),
+        ]
+    }
+
+    fn write_cards<S>(
+        out_file: PathBuf,
+        cards: Vec<CreditCard>,
+        sep: S,
+        format: Format,
+        unmasked: bool,
+    ) -> JoinHandle<Result<()>>
+    where
+        S: Display + Send + Clone + 'static,
+    {
+        let header = credit_card_csv_header(sep.clone());
+        if unmasked {
+            return utils::write_records(out_file, cards, header, CreditCard::to_csv, sep, format);
+        }
+        let cards = cards
+            .iter()
+            .map(CreditCard::masked)
+            .collect();
+        utils::write_records(
+            out_file,
+            cards,
+            header,
+            MaskedCreditCard::to_csv,
+            sep,
+            format,
+        )
+    }
+
+    fn write_ibans<S>(
+        out_file: PathBuf,
+        ibans: Vec<Iban>,
+        sep: S,
+        format: Format,
+        unmasked: bool,
+    ) -> JoinHandle<Result<()>>
+    where
+        S: Display + Send + Clone + 'static,
+    {
+        let header = iban_csv_header(sep.clone());
+        if unmasked {
+            return utils::write_records(out_file, ibans, header, Iban::to_csv, sep, format);
+        }
+        let ibans = ibans
+            .iter()
+            .map(Iban::masked)
+            .collect();
+        utils::write_records(out_file, ibans, header, MaskedIban::to_csv, sep, format)
+    }
+
+    /// Missing files are skipped, failed ones still report their error when read
+    fn present(browser: &str, value: Value, status: &ArtifactStatus) -> bool {
+        if let ArtifactStatus::Missing = status {
+            tracing::info!(browser, ?value, "not found in the profile, skip");
+            return false;
+        }
+        true
+    }
+
     pub(crate) async fn multi_data<H>(
         names: impl Iterator<Item = ChromiumName>,
         output_dir: PathBuf,
         sep: String,
         host: H,
         format: Format,
+        key_cache: Option<KeyCache>,
+        unmasked: bool,
     ) -> Result<()>
     where
         H: Into<Option<String>>,
@@ -41,9 +270,13 @@ impl ChromiumBased {
             let output_dir = output_dir.clone();
             let sep = sep.clone();
             let values = HashSet::from_iter(Value::iter());
+            let key_cache = key_cache.clone();
 
             tokio::task::spawn(async move {
-                Self::write_data(name, None, host, values, output_dir, sep, format).await
+                Self::write_data(
+                    name, None, host, values, output_dir, sep, format, key_cache, unmasked,
+                )
+                .await
             })
         }) {
             if let Err(e) = task
@@ -70,6 +303,7 @@ impl ChromiumBased {
         Ok(())
     }
 
+    #[expect(clippy::too_many_arguments, reason = "cli args")]
     pub async fn write_data<D, H, S>(
         name: ChromiumName,
         data_dir: D,
@@ -78,6 +312,8 @@ impl ChromiumBased {
         mut output_dir: PathBuf,
         sep: S,
         format: Format,
+        key_cache: Option<KeyCache>,
+        unmasked: bool,
     ) -> Result<()>
     where
         D: Into<Option<PathBuf>>,
@@ -92,18 +328,31 @@ impl ChromiumBased {
                 match name {
                     $(
                     ChromiumName::$browser => {
-                        let chromium = if let Some(dir) = data_dir {
+                        let builder = if let Some(dir) = data_dir {
                             ChromiumBuilder::<$browser>::with_user_data_dir(dir)
                         }
                         else {
                             ChromiumBuilder::new()
+                        };
+                        let builder = if let Some(key_cache) = key_cache {
+                            builder.key_cache(key_cache)
                         }
-                        .build()
-                        .await
-                        .map_err(|e|Box::new(e))
-                        .context(error::ChromiumBuilderSnafu)?;
+                        else {
+                            builder
+                        };
+                        let chromium = builder
+                            .clone()
+                            .build()
+                            .await
+                            .map_err(|e|Box::new(e))
+                            .context(error::ChromiumBuilderSnafu)?;
+                        // Derive the key once
+                        let builder = builder.crypto_of(&chromium);
+                        let artifacts = chromium.artifacts();
 
-                        let cookies = if values.contains(&Value::Cookie) {
+                        let cookies = if values.contains(&Value::Cookie)
+                            && Self::present($browser::NAME, Value::Cookie, &artifacts.cookies)
+                        {
                             let host = host.clone();
                             let chromium = chromium.clone();
                             let task = task::spawn(async move {
@@ -125,7 +374,10 @@ impl ChromiumBased {
                             None
                         };
 
-                        let logins = if values.contains(&Value::Login) {
+                        let logins = if values.contains(&Value::Login)
+                            && (artifacts.logins_for_account.is_available()
+                                || Self::present($browser::NAME, Value::Login, &artifacts.logins))
+                        {
                             let host = host.clone();
                             let task = task::spawn(async move {
                                 let logins = if let Some(host) = host {
@@ -143,7 +395,10 @@ impl ChromiumBased {
                         else {
                             None
                         };
-                        (cookies, logins, $browser::NAME)
+                        let web_data = values
+                            .contains(&Value::WebData)
+                            .then(|| task::spawn(Self::web_data(builder, host)));
+                        (cookies, logins, web_data, $browser::NAME)
                     },
                     )*
                 }
@@ -151,10 +406,10 @@ impl ChromiumBased {
         }
 
         #[cfg(target_os = "linux")]
-        let (cookies, logins, name) =
+        let (cookies, logins, web_data, name) =
             chromiums![Chrome, Edge, Chromium, Brave, Vivaldi, Yandex, Opera,];
         #[cfg(not(target_os = "linux"))]
-        let (cookies, logins, name) = chromiums![
+        let (cookies, logins, web_data, name) = chromiums![
             Chrome, Edge, Chromium, Brave, Vivaldi, Yandex, Opera, Arc, OperaGX, CocCoc,
         ];
         let (cookies, logins, cap) = match (cookies, logins) {
@@ -179,12 +434,29 @@ impl ChromiumBased {
             },
         };
 
+        let web_data = match web_data {
+            Some(task) => task
+                .await
+                .context(error::TokioTaskSnafu)??,
+            None => None,
+        };
+
         output_dir.push(name);
         tokio::fs::create_dir_all(&output_dir)
             .await
             .with_context(|_| error::IoSnafu { path: output_dir.clone() })?;
 
-        let mut tasks = Vec::with_capacity(cap);
+        let mut tasks = Vec::with_capacity(cap + 4);
+
+        if let Some(web_data) = web_data {
+            tasks.extend(Self::write_web_data(
+                &output_dir,
+                web_data,
+                sep.clone(),
+                format,
+                unmasked,
+            ));
+        }
 
         if let Some(cookies) = cookies {
             let out_file = output_dir.join({
@@ -1,7 +1,8 @@
-use std::{collections::HashSet, io, path::PathBuf, str::FromStr};
+use std::{collections::HashSet, io, path::PathBuf, str::FromStr, time::Duration};
 
 use clap::CommandFactory;
-use snafu::ResultExt;
+use decrypt_cookies::{browser::key_cache_dir, prelude::*};
+use snafu::{OptionExt, ResultExt};
 use strum::IntoEnumIterator;
 
 use crate::{
@@ -19,7 +20,28 @@ use crate::{
     safari::SafariBased,
 };
 
+fn key_cache(args: &Args) -> Result<Option<KeyCache>> {
+    let (true, Some(name)) = (args.key_cache, &args.key_cache_passphrase_env)
+    else {
+        return Ok(None);
+    };
+    let Some(dir) = key_cache_dir()
+    else {
+        tracing::warn!("Can not found cache dir, the key cache is disabled");
+        return Ok(None);
+    };
+    let pass = std::env::var(name)
+        .ok()
+        .context(error::KeyCachePassphraseSnafu { name })?;
+
+    Ok(Some(
+        KeyCache::new(dir, CacheSecret::Passphrase(pass.into()))
+            .ttl(Duration::from_secs(args.key_cache_ttl)),
+    ))
+}
+
 pub async fn run_cli(args: crate::args::Args) -> Result<()> {
+    let key_cache = key_cache(&args)?;
     let output_dir = args.output_dir;
 
     if args.all_browsers {
@@ -27,6 +49,7 @@ pub async fn run_cli(args: crate::args::Args) -> Result<()> {
             let output_dir = output_dir.clone();
             let sep = args.sep.clone();
             let host = args.host.clone();
+            let key_cache = key_cache.clone();
             async move {
                 ChromiumBased::multi_data(
                     ChromiumName::iter(),
@@ -34,6 +57,8 @@ pub async fn run_cli(args: crate::args::Args) -> Result<()> {
                     sep,
                     host,
                     args.out_format,
+                    key_cache,
+                    args.unmasked,
                 )
                 .await
             }
@@ -101,6 +126,8 @@ pub async fn run_cli(args: crate::args::Args) -> Result<()> {
                     output_dir,
                     args.sep,
                     args.out_format,
+                    key_cache,
+                    args.unmasked,
                 )
                 .await?;
             },
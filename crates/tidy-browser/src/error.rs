@@ -62,6 +62,12 @@ pub enum Error {
         #[snafu(implicit)]
         location: Location,
     },
+    #[snafu(display("Environment variable {name} is not set\n@:{location}"))]
+    KeyCachePassphrase {
+        name: String,
+        #[snafu(implicit)]
+        location: Location,
+    },
     #[snafu(display("{source}\n@:{location}"))]
     TokioTask {
         source: tokio::task::JoinError,
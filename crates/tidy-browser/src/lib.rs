@@ -19,3 +19,19 @@ const COOKIES_FILE_JSONL: &str = "cookies.jsonl";
 const LOGINS_FILE_CSV: &str = "logins.csv";
 const LOGINS_FILE_JSON: &str = "logins.json";
 const LOGINS_FILE_JSONL: &str = "logins.jsonl";
+
+const AUTOFILL_FILE_CSV: &str = "autofill.csv";
+const AUTOFILL_FILE_JSON: &str = "autofill.json";
+const AUTOFILL_FILE_JSONL: &str = "autofill.jsonl";
+
+const ADDRESSES_FILE_CSV: &str = "addresses.csv";
+const ADDRESSES_FILE_JSON: &str = "addresses.json";
+const ADDRESSES_FILE_JSONL: &str = "addresses.jsonl";
+
+const CREDIT_CARDS_FILE_CSV: &str = "credit_cards.csv";
+const CREDIT_CARDS_FILE_JSON: &str = "credit_cards.json";
+const CREDIT_CARDS_FILE_JSONL: &str = "credit_cards.jsonl";
+
+const IBANS_FILE_CSV: &str = "ibans.csv";
+const IBANS_FILE_JSON: &str = "ibans.json";
+const IBANS_FILE_JSONL: &str = "ibans.jsonl";
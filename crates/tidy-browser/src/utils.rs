@@ -82,3 +82,51 @@ where
         }
     })
 }
+
+/// `to_csv` converts a record to a csv line, without the line break
+pub(crate) fn write_records<T, S>(
+    out_file: PathBuf,
+    records: Vec<T>,
+    header: String,
+    to_csv: fn(&T, S) -> String,
+    sep: S,
+    format: Format,
+) -> JoinHandle<Result<(), error::Error>>
+where
+    T: Serialize + Send + 'static,
+    S: Display + Send + Clone + 'static,
+{
+    task::spawn_blocking(move || {
+        let mut file = File::options()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&out_file)
+            .with_context(|_| error::IoSnafu { path: out_file.clone() })?;
+
+        match format {
+            Format::Csv => {
+                let mut slices = Vec::with_capacity(2 + records.len() * 2);
+
+                slices.push(IoSlice::new(header.as_bytes()));
+                slices.push(IoSlice::new(b"\n"));
+
+                let csvs: Vec<_> = records
+                    .iter()
+                    .map(|v| to_csv(v, sep.clone()))
+                    .collect();
+
+                for csv in &csvs {
+                    slices.push(IoSlice::new(csv.as_bytes()));
+                    slices.push(IoSlice::new(b"\n"));
+                }
+
+                write_all_vectored(&mut file, &mut slices)
+                    .with_context(|_| error::IoSnafu { path: out_file })
+            },
+            Format::Json => serde_json::to_writer(file, &records).context(JsonSnafu),
+            Format::JsonLines => serde_jsonlines::write_json_lines(&out_file, &records)
+                .context(IoSnafu { path: out_file }),
+        }
+    })
+}